@@ -0,0 +1,75 @@
+//! Generates the gRPC services of `proto/task_app.proto`. The messages are
+//! written by hand in `src/grpc/proto.rs`, so building needs no `protoc`.
+
+use tonic_build::manual::{Builder, Method, MethodBuilder, Service};
+
+/// A method taking and returning messages of `crate::grpc::proto`.
+fn method(name: &str, route_name: &str, input: &str, output: &str) -> MethodBuilder {
+    Method::builder()
+        .name(name)
+        .route_name(route_name)
+        .input_type(format!("crate::grpc::proto::{}", input))
+        .output_type(format!("crate::grpc::proto::{}", output))
+        .codec_path("tonic::codec::ProstCodec")
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let task_service = Service::builder()
+        .name("TaskService")
+        .package("task_app")
+        .method(method("get_task", "GetTask", "GetTaskRequest", "Task").build())
+        .method(
+            method("list_tasks", "ListTasks", "ListTasksRequest", "Task")
+                .server_streaming()
+                .build(),
+        )
+        .method(method("create_task", "CreateTask", "CreateTaskRequest", "Task").build())
+        .method(method("update_task", "UpdateTask", "UpdateTaskRequest", "Task").build())
+        .method(
+            method(
+                "delete_task",
+                "DeleteTask",
+                "DeleteTaskRequest",
+                "DeleteTaskResponse",
+            )
+            .build(),
+        )
+        .method(
+            method(
+                "watch_tasks",
+                "WatchTasks",
+                "WatchTasksRequest",
+                "TaskEvent",
+            )
+            .server_streaming()
+            .build(),
+        )
+        .build();
+
+    let user_service = Service::builder()
+        .name("UserService")
+        .package("task_app")
+        .method(
+            method(
+                "get_current_user",
+                "GetCurrentUser",
+                "GetCurrentUserRequest",
+                "User",
+            )
+            .build(),
+        )
+        .method(method("get_user", "GetUser", "GetUserRequest", "User").build())
+        .method(
+            method("list_users", "ListUsers", "ListUsersRequest", "User")
+                .server_streaming()
+                .build(),
+        )
+        .build();
+
+    Builder::new()
+        .build_client(false)
+        .build_transport(false)
+        .compile(&[task_service, user_service]);
+}
@@ -7,6 +7,37 @@ mod m20241216_055637_create_permission_table;
 mod m20241216_092524_create_role_table;
 mod m20241216_095114_create_user_role_table;
 mod m20241217_163324_create_user_permission_table;
+mod m20241220_091530_create_task_event_table;
+mod m20241222_140210_create_webhook_table;
+mod m20241222_140845_create_webhook_delivery_table;
+mod m20241224_083012_create_notification_table;
+mod m20241224_084530_create_notification_preference_table;
+mod m20241226_101204_create_task_assignee_table;
+mod m20241226_101748_create_task_watcher_table;
+mod m20241228_093015_create_project_table;
+mod m20241228_094210_create_project_member_table;
+mod m20241228_101530_add_project_id_to_task_table;
+mod m20241230_081245_add_rank_to_task_table;
+mod m20241230_083410_create_board_column_table;
+mod m20250102_091500_add_task_keys;
+mod m20250104_083020_create_task_checklist_item_table;
+mod m20250104_084310_add_auto_complete_to_task_table;
+mod m20250106_092040_create_time_entry_table;
+mod m20250108_101225_add_estimate_and_due_date_to_task_table;
+mod m20250108_103540_add_weekly_capacity_to_user_profile_table;
+mod m20250110_084515_add_priority_to_task_table;
+mod m20250112_091530_create_saved_view_table;
+mod m20250112_092205_create_saved_view_role_table;
+mod m20250114_090210_add_labels_to_task_table;
+mod m20250114_093540_create_task_template_table;
+mod m20250116_084210_create_calendar_feed_table;
+mod m20250118_083015_create_task_change_table;
+mod m20250118_085240_create_caldav_resource_table;
+mod m20250120_081000_add_recurrence_to_task_table;
+mod m20250120_081500_add_timezone_to_user_profile_table;
+mod m20250122_090500_create_task_field_version_table;
+mod m20250124_083000_add_task_change_triggers_for_task_details;
+mod m20250124_090000_add_project_id_to_caldav_resource_table;
 
 pub struct Migrator;
 
@@ -21,6 +52,37 @@ impl MigratorTrait for Migrator {
             Box::new(m20241216_092524_create_role_table::Migration),
             Box::new(m20241216_095114_create_user_role_table::Migration),
             Box::new(m20241217_163324_create_user_permission_table::Migration),
+            Box::new(m20241220_091530_create_task_event_table::Migration),
+            Box::new(m20241222_140210_create_webhook_table::Migration),
+            Box::new(m20241222_140845_create_webhook_delivery_table::Migration),
+            Box::new(m20241224_083012_create_notification_table::Migration),
+            Box::new(m20241224_084530_create_notification_preference_table::Migration),
+            Box::new(m20241226_101204_create_task_assignee_table::Migration),
+            Box::new(m20241226_101748_create_task_watcher_table::Migration),
+            Box::new(m20241228_093015_create_project_table::Migration),
+            Box::new(m20241228_094210_create_project_member_table::Migration),
+            Box::new(m20241228_101530_add_project_id_to_task_table::Migration),
+            Box::new(m20241230_081245_add_rank_to_task_table::Migration),
+            Box::new(m20241230_083410_create_board_column_table::Migration),
+            Box::new(m20250102_091500_add_task_keys::Migration),
+            Box::new(m20250104_083020_create_task_checklist_item_table::Migration),
+            Box::new(m20250104_084310_add_auto_complete_to_task_table::Migration),
+            Box::new(m20250106_092040_create_time_entry_table::Migration),
+            Box::new(m20250108_101225_add_estimate_and_due_date_to_task_table::Migration),
+            Box::new(m20250108_103540_add_weekly_capacity_to_user_profile_table::Migration),
+            Box::new(m20250110_084515_add_priority_to_task_table::Migration),
+            Box::new(m20250112_091530_create_saved_view_table::Migration),
+            Box::new(m20250112_092205_create_saved_view_role_table::Migration),
+            Box::new(m20250114_090210_add_labels_to_task_table::Migration),
+            Box::new(m20250114_093540_create_task_template_table::Migration),
+            Box::new(m20250116_084210_create_calendar_feed_table::Migration),
+            Box::new(m20250118_083015_create_task_change_table::Migration),
+            Box::new(m20250118_085240_create_caldav_resource_table::Migration),
+            Box::new(m20250120_081000_add_recurrence_to_task_table::Migration),
+            Box::new(m20250120_081500_add_timezone_to_user_profile_table::Migration),
+            Box::new(m20250122_090500_create_task_field_version_table::Migration),
+            Box::new(m20250124_083000_add_task_change_triggers_for_task_details::Migration),
+            Box::new(m20250124_090000_add_project_id_to_caldav_resource_table::Migration),
         ]
     }
 }
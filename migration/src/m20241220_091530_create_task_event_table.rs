@@ -0,0 +1,81 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskEvent::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskEvent::Id))
+                    .col(integer(TaskEvent::TaskId))
+                    .col(integer_null(TaskEvent::UserId))
+                    .col(string(TaskEvent::Field))
+                    .col(text_null(TaskEvent::OldValue))
+                    .col(text_null(TaskEvent::NewValue))
+                    .col(date_time(TaskEvent::DateCreated).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task-event-task_id")
+                            .from(TaskEvent::Table, TaskEvent::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task-event-user_id")
+                            .from(TaskEvent::Table, TaskEvent::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task-event-task_id")
+                    .table(TaskEvent::Table)
+                    .col(TaskEvent::TaskId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskEvent {
+    Table,
+    Id,
+    TaskId,
+    UserId,
+    Field,
+    OldValue,
+    NewValue,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,57 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Webhook::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Webhook::Id))
+                    .col(integer(Webhook::UserId))
+                    .col(string(Webhook::Url))
+                    .col(string(Webhook::Secret))
+                    .col(text(Webhook::EventTypes))
+                    .col(boolean(Webhook::IsActive).default(true))
+                    .col(date_time(Webhook::DateCreated).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-webhook-user_id")
+                            .from(Webhook::Table, Webhook::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Webhook::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Webhook {
+    Table,
+    Id,
+    UserId,
+    Url,
+    Secret,
+    EventTypes,
+    IsActive,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
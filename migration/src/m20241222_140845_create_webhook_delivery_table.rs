@@ -0,0 +1,76 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookDelivery::Table)
+                    .if_not_exists()
+                    .col(pk_auto(WebhookDelivery::Id))
+                    .col(integer(WebhookDelivery::WebhookId))
+                    .col(string(WebhookDelivery::EventType))
+                    .col(text(WebhookDelivery::Payload))
+                    .col(string(WebhookDelivery::Status).default("pending"))
+                    .col(integer(WebhookDelivery::Attempts).default(0))
+                    .col(date_time(WebhookDelivery::NextAttemptAt))
+                    .col(integer_null(WebhookDelivery::ResponseStatus))
+                    .col(text_null(WebhookDelivery::LastError))
+                    .col(date_time(WebhookDelivery::DateCreated).default(Expr::current_timestamp()))
+                    .col(date_time_null(WebhookDelivery::DateDelivered))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-webhook-delivery-webhook_id")
+                            .from(WebhookDelivery::Table, WebhookDelivery::WebhookId)
+                            .to(Webhook::Table, Webhook::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-webhook-delivery-status-next_attempt_at")
+                    .table(WebhookDelivery::Table)
+                    .col(WebhookDelivery::Status)
+                    .col(WebhookDelivery::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WebhookDelivery::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WebhookDelivery {
+    Table,
+    Id,
+    WebhookId,
+    EventType,
+    Payload,
+    Status,
+    Attempts,
+    NextAttemptAt,
+    ResponseStatus,
+    LastError,
+    DateCreated,
+    DateDelivered,
+}
+
+#[derive(DeriveIden)]
+enum Webhook {
+    Table,
+    Id,
+}
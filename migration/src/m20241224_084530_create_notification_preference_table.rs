@@ -0,0 +1,70 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationPreference::Table)
+                    .if_not_exists()
+                    .col(pk_auto(NotificationPreference::Id))
+                    .col(integer(NotificationPreference::UserId))
+                    .col(string(NotificationPreference::Kind))
+                    .col(boolean(NotificationPreference::Enabled).default(true))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-notification-preference-user_id")
+                            .from(
+                                NotificationPreference::Table,
+                                NotificationPreference::UserId,
+                            )
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-notification-preference-user_id-kind")
+                    .table(NotificationPreference::Table)
+                    .col(NotificationPreference::UserId)
+                    .col(NotificationPreference::Kind)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(NotificationPreference::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NotificationPreference {
+    Table,
+    Id,
+    UserId,
+    Kind,
+    Enabled,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,77 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskAssignee::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskAssignee::Id))
+                    .col(integer(TaskAssignee::TaskId))
+                    .col(integer(TaskAssignee::UserId))
+                    .col(date_time(TaskAssignee::DateCreated).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task-assignee-task_id")
+                            .from(TaskAssignee::Table, TaskAssignee::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task-assignee-user_id")
+                            .from(TaskAssignee::Table, TaskAssignee::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task-assignee-task_id-user_id")
+                    .table(TaskAssignee::Table)
+                    .col(TaskAssignee::TaskId)
+                    .col(TaskAssignee::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskAssignee::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskAssignee {
+    Table,
+    Id,
+    TaskId,
+    UserId,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
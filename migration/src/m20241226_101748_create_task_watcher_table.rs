@@ -0,0 +1,77 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskWatcher::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskWatcher::Id))
+                    .col(integer(TaskWatcher::TaskId))
+                    .col(integer(TaskWatcher::UserId))
+                    .col(date_time(TaskWatcher::DateCreated).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task-watcher-task_id")
+                            .from(TaskWatcher::Table, TaskWatcher::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task-watcher-user_id")
+                            .from(TaskWatcher::Table, TaskWatcher::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task-watcher-task_id-user_id")
+                    .table(TaskWatcher::Table)
+                    .col(TaskWatcher::TaskId)
+                    .col(TaskWatcher::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskWatcher::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskWatcher {
+    Table,
+    Id,
+    TaskId,
+    UserId,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
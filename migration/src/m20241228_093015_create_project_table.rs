@@ -0,0 +1,57 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Project::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Project::Id))
+                    .col(string(Project::Name))
+                    .col(text_null(Project::Description))
+                    .col(integer(Project::OwnerId))
+                    .col(boolean(Project::IsInbox).default(false))
+                    .col(boolean(Project::IsArchived).default(false))
+                    .col(date_time(Project::DateCreated).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-project-owner_id")
+                            .from(Project::Table, Project::OwnerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Project::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Project {
+    Table,
+    Id,
+    Name,
+    Description,
+    OwnerId,
+    IsInbox,
+    IsArchived,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
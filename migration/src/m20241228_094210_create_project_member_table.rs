@@ -0,0 +1,79 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProjectMember::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ProjectMember::Id))
+                    .col(integer(ProjectMember::ProjectId))
+                    .col(integer(ProjectMember::UserId))
+                    .col(string(ProjectMember::Role))
+                    .col(date_time(ProjectMember::DateCreated).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-project-member-project_id")
+                            .from(ProjectMember::Table, ProjectMember::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-project-member-user_id")
+                            .from(ProjectMember::Table, ProjectMember::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-project-member-project_id-user_id")
+                    .table(ProjectMember::Table)
+                    .col(ProjectMember::ProjectId)
+                    .col(ProjectMember::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProjectMember::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProjectMember {
+    Table,
+    Id,
+    ProjectId,
+    UserId,
+    Role,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum Project {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
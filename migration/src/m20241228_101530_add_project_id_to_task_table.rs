@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // SQLite cannot add a foreign key through ALTER TABLE ... ADD CONSTRAINT,
+        // so the reference is declared inline.
+        db.execute_unprepared(
+            "ALTER TABLE task ADD COLUMN project_id INTEGER NULL
+            REFERENCES project (id) ON DELETE CASCADE ON UPDATE CASCADE",
+        )
+        .await?;
+
+        // Every existing user gets an Inbox holding the tasks they reported.
+        db.execute_unprepared(
+            "INSERT INTO project (name, owner_id, is_inbox)
+            SELECT 'Inbox', id, TRUE FROM user
+            WHERE id NOT IN (SELECT owner_id FROM project WHERE is_inbox)",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "INSERT INTO project_member (project_id, user_id, role)
+            SELECT id, owner_id, 'owner' FROM project WHERE is_inbox",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "UPDATE task SET project_id = (
+                SELECT project.id FROM project
+                WHERE project.owner_id = task.user_id AND project.is_inbox
+            )
+            WHERE project_id IS NULL",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared("ALTER TABLE task DROP COLUMN project_id")
+            .await?;
+
+        db.execute_unprepared("DELETE FROM project WHERE is_inbox")
+            .await?;
+
+        Ok(())
+    }
+}
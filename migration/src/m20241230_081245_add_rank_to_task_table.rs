@@ -0,0 +1,76 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .add_column(string(Task::Rank).default("i"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Keep the current board order (newest first) by numbering every
+        // column. Keys end with "i" so that new ranks fit between them.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE task SET rank = (
+                    SELECT printf('%09di', ordered.position) FROM (
+                        SELECT id, ROW_NUMBER() OVER (
+                            PARTITION BY project_id, status
+                            ORDER BY date_created DESC, id DESC
+                        ) AS position
+                        FROM task
+                    ) AS ordered
+                    WHERE ordered.id = task.id
+                )",
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task-project_id-status-rank")
+                    .table(Task::Table)
+                    .col(Task::ProjectId)
+                    .col(Task::Status)
+                    .col(Task::Rank)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-task-project_id-status-rank")
+                    .table(Task::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .drop_column(Task::Rank)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    ProjectId,
+    Status,
+    Rank,
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BoardColumn::Table)
+                    .if_not_exists()
+                    .col(pk_auto(BoardColumn::Id))
+                    .col(integer(BoardColumn::ProjectId))
+                    .col(string(BoardColumn::Status))
+                    .col(integer(BoardColumn::WipLimit))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-board-column-project_id")
+                            .from(BoardColumn::Table, BoardColumn::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-board-column-project_id-status")
+                    .table(BoardColumn::Table)
+                    .col(BoardColumn::ProjectId)
+                    .col(BoardColumn::Status)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BoardColumn::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum BoardColumn {
+    Table,
+    Id,
+    ProjectId,
+    Status,
+    WipLimit,
+}
+
+#[derive(DeriveIden)]
+enum Project {
+    Table,
+    Id,
+}
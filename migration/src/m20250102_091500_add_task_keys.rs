@@ -0,0 +1,207 @@
+use std::collections::HashSet;
+
+use sea_orm_migration::{prelude::*, schema::*, sea_orm::Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Same rule as `models::project::prefix_from` in the application.
+fn prefix_from(name: &str) -> String {
+    let words: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_uppercase())
+        .collect();
+
+    let prefix: String = match words.as_slice() {
+        [] => String::new(),
+        [word] => word.chars().take(3).collect(),
+        words => words
+            .iter()
+            .filter_map(|word| word.chars().next())
+            .take(4)
+            .collect(),
+    };
+
+    if prefix.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        prefix
+    } else {
+        format!("P{}", prefix)
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .add_column(string(Project::KeyPrefix).default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .add_column(integer(Project::NextTaskNumber).default(1))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .add_column(string_null(Task::Key))
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        let backend = db.get_database_backend();
+
+        // Inboxes are named after their owner, other projects after themselves.
+        let projects = db
+            .query_all(Statement::from_string(
+                backend,
+                "SELECT project.id, project.name, project.is_inbox, user.username
+                FROM project JOIN user ON user.id = project.owner_id
+                ORDER BY project.id",
+            ))
+            .await?;
+
+        let mut taken = HashSet::new();
+
+        for row in projects {
+            let id: i32 = row.try_get("", "id")?;
+            let name: String = row.try_get("", "name")?;
+            let is_inbox: bool = row.try_get("", "is_inbox")?;
+            let username: String = row.try_get("", "username")?;
+
+            let base = prefix_from(if is_inbox { &username } else { &name });
+            let mut prefix = base.clone();
+            let mut suffix = 2;
+
+            while !taken.insert(prefix.clone()) {
+                prefix = format!("{}{}", base, suffix);
+                suffix += 1;
+            }
+
+            db.execute(Statement::from_sql_and_values(
+                backend,
+                "UPDATE project SET key_prefix = ? WHERE id = ?",
+                [prefix.into(), id.into()],
+            ))
+            .await?;
+        }
+
+        db.execute_unprepared(
+            "UPDATE task SET key = (
+                SELECT project.key_prefix || '-' || ordered.number
+                FROM (
+                    SELECT id, project_id, ROW_NUMBER() OVER (
+                        PARTITION BY project_id
+                        ORDER BY date_created, id
+                    ) AS number
+                    FROM task
+                    WHERE project_id IS NOT NULL
+                ) AS ordered
+                JOIN project ON project.id = ordered.project_id
+                WHERE ordered.id = task.id
+            )",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "UPDATE project SET next_task_number = 1 + (
+                SELECT COUNT(*) FROM task WHERE task.project_id = project.id
+            )",
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-project-key_prefix")
+                    .table(Project::Table)
+                    .col(Project::KeyPrefix)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task-key")
+                    .table(Task::Table)
+                    .col(Task::Key)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-task-key")
+                    .table(Task::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-project-key_prefix")
+                    .table(Project::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .drop_column(Task::Key)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .drop_column(Project::NextTaskNumber)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .drop_column(Project::KeyPrefix)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Project {
+    Table,
+    KeyPrefix,
+    NextTaskNumber,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Key,
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskChecklistItem::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskChecklistItem::Id))
+                    .col(integer(TaskChecklistItem::TaskId))
+                    .col(string(TaskChecklistItem::Text))
+                    .col(boolean(TaskChecklistItem::IsDone).default(false))
+                    .col(integer(TaskChecklistItem::Position))
+                    .col(
+                        date_time(TaskChecklistItem::DateCreated)
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task-checklist-item-task_id")
+                            .from(TaskChecklistItem::Table, TaskChecklistItem::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task-checklist-item-task_id-position")
+                    .table(TaskChecklistItem::Table)
+                    .col(TaskChecklistItem::TaskId)
+                    .col(TaskChecklistItem::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskChecklistItem::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskChecklistItem {
+    Table,
+    Id,
+    TaskId,
+    Text,
+    IsDone,
+    Position,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
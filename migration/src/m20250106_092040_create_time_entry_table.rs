@@ -0,0 +1,102 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimeEntry::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TimeEntry::Id))
+                    .col(integer(TimeEntry::UserId))
+                    .col(integer(TimeEntry::TaskId))
+                    .col(date_time(TimeEntry::StartedAt))
+                    .col(date_time_null(TimeEntry::EndedAt))
+                    .col(text_null(TimeEntry::Note))
+                    .col(date_time(TimeEntry::DateCreated).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-time-entry-user_id")
+                            .from(TimeEntry::Table, TimeEntry::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-time-entry-task_id")
+                            .from(TimeEntry::Table, TimeEntry::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-time-entry-task_id")
+                    .table(TimeEntry::Table)
+                    .col(TimeEntry::TaskId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-time-entry-user_id-started_at")
+                    .table(TimeEntry::Table)
+                    .col(TimeEntry::UserId)
+                    .col(TimeEntry::StartedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // At most one running timer per user.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"CREATE UNIQUE INDEX "idx-time-entry-running" ON "time_entry" ("user_id") WHERE "ended_at" IS NULL"#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TimeEntry::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TimeEntry {
+    Table,
+    Id,
+    UserId,
+    TaskId,
+    StartedAt,
+    EndedAt,
+    Note,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 40 hours a week.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserProfile::Table)
+                    .add_column(integer(UserProfile::WeeklyCapacityMinutes).default(2400))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserProfile::Table)
+                    .drop_column(UserProfile::WeeklyCapacityMinutes)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserProfile {
+    Table,
+    WeeklyCapacityMinutes,
+}
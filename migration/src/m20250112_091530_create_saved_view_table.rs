@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SavedView::Table)
+                    .if_not_exists()
+                    .col(pk_auto(SavedView::Id))
+                    .col(integer(SavedView::UserId))
+                    .col(string(SavedView::Name))
+                    .col(string(SavedView::Slug))
+                    .col(text(SavedView::Filters))
+                    .col(string_null(SavedView::Sort))
+                    .col(text(SavedView::Columns).default(""))
+                    .col(string(SavedView::Visibility).default("private").check(
+                        Expr::col(SavedView::Visibility).is_in(["private", "roles", "everyone"]),
+                    ))
+                    .col(date_time(SavedView::DateCreated).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-saved-view-user_id")
+                            .from(SavedView::Table, SavedView::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-saved-view-user_id-slug")
+                    .table(SavedView::Table)
+                    .col(SavedView::UserId)
+                    .col(SavedView::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SavedView::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SavedView {
+    Table,
+    Id,
+    UserId,
+    Name,
+    Slug,
+    Filters,
+    Sort,
+    Columns,
+    Visibility,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
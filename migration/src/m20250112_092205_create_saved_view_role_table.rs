@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SavedViewRole::Table)
+                    .if_not_exists()
+                    .col(pk_auto(SavedViewRole::Id))
+                    .col(integer(SavedViewRole::SavedViewId))
+                    .col(integer(SavedViewRole::RoleId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-saved-view-role-saved_view_id")
+                            .from(SavedViewRole::Table, SavedViewRole::SavedViewId)
+                            .to(SavedView::Table, SavedView::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-saved-view-role-role_id")
+                            .from(SavedViewRole::Table, SavedViewRole::RoleId)
+                            .to(Role::Table, Role::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-saved-view-role-saved_view_id-role_id")
+                    .table(SavedViewRole::Table)
+                    .col(SavedViewRole::SavedViewId)
+                    .col(SavedViewRole::RoleId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SavedViewRole::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SavedViewRole {
+    Table,
+    Id,
+    SavedViewId,
+    RoleId,
+}
+
+#[derive(DeriveIden)]
+enum SavedView {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Role {
+    Table,
+    Id,
+}
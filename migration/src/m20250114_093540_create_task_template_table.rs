@@ -0,0 +1,71 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskTemplate::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskTemplate::Id))
+                    .col(integer(TaskTemplate::UserId))
+                    .col(integer_null(TaskTemplate::ProjectId))
+                    .col(string(TaskTemplate::Name))
+                    .col(text(TaskTemplate::Description).default(""))
+                    .col(text(TaskTemplate::Tasks))
+                    .col(date_time(TaskTemplate::DateCreated).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task-template-user_id")
+                            .from(TaskTemplate::Table, TaskTemplate::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task-template-project_id")
+                            .from(TaskTemplate::Table, TaskTemplate::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskTemplate::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskTemplate {
+    Table,
+    Id,
+    UserId,
+    ProjectId,
+    Name,
+    Description,
+    Tasks,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Project {
+    Table,
+    Id,
+}
@@ -0,0 +1,51 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CalendarFeed::Table)
+                    .if_not_exists()
+                    .col(pk_auto(CalendarFeed::Id))
+                    .col(integer_uniq(CalendarFeed::UserId))
+                    .col(string_uniq(CalendarFeed::Token))
+                    .col(date_time(CalendarFeed::DateCreated).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-calendar-feed-user_id")
+                            .from(CalendarFeed::Table, CalendarFeed::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CalendarFeed::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CalendarFeed {
+    Table,
+    Id,
+    UserId,
+    Token,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
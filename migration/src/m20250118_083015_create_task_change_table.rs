@@ -0,0 +1,91 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // No foreign key: the changes of deleted tasks must outlive them.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskChange::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskChange::Id))
+                    .col(integer(TaskChange::TaskId))
+                    .col(integer_null(TaskChange::ProjectId))
+                    .col(boolean(TaskChange::Deleted).default(false))
+                    .col(date_time(TaskChange::DateCreated).default(Expr::current_timestamp()))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task-change-project_id-id")
+                    .table(TaskChange::Table)
+                    .col(TaskChange::ProjectId)
+                    .col(TaskChange::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Triggers catch every write to a task, whichever code path makes it.
+        // A task moved to another project is deleted from the old one.
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"CREATE TRIGGER "task_change_insert" AFTER INSERT ON "task" BEGIN
+                INSERT INTO "task_change" ("task_id", "project_id") VALUES (NEW."id", NEW."project_id");
+            END"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE TRIGGER "task_change_update" AFTER UPDATE ON "task" BEGIN
+                INSERT INTO "task_change" ("task_id", "project_id", "deleted")
+                    SELECT OLD."id", OLD."project_id", TRUE WHERE OLD."project_id" IS NOT NEW."project_id";
+                INSERT INTO "task_change" ("task_id", "project_id") VALUES (NEW."id", NEW."project_id");
+            END"#,
+        )
+        .await?;
+
+        db.execute_unprepared(
+            r#"CREATE TRIGGER "task_change_delete" AFTER DELETE ON "task" BEGIN
+                INSERT INTO "task_change" ("task_id", "project_id", "deleted") VALUES (OLD."id", OLD."project_id", TRUE);
+            END"#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        for trigger in [
+            "task_change_insert",
+            "task_change_update",
+            "task_change_delete",
+        ] {
+            db.execute_unprepared(&format!(r#"DROP TRIGGER IF EXISTS "{}""#, trigger))
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(TaskChange::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskChange {
+    Table,
+    Id,
+    TaskId,
+    ProjectId,
+    Deleted,
+    DateCreated,
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Kept after the task is deleted, so sync reports can name it.
+        manager
+            .create_table(
+                Table::create()
+                    .table(CaldavResource::Table)
+                    .if_not_exists()
+                    .col(pk_auto(CaldavResource::Id))
+                    .col(integer_uniq(CaldavResource::TaskId))
+                    .col(string(CaldavResource::Name))
+                    .col(string(CaldavResource::Uid))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-caldav-resource-name")
+                    .table(CaldavResource::Table)
+                    .col(CaldavResource::Name)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CaldavResource::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CaldavResource {
+    Table,
+    Id,
+    TaskId,
+    Name,
+    Uid,
+}
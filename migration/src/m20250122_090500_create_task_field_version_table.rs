@@ -0,0 +1,128 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Task fields whose version is kept, the ones clients can change by sync.
+const FIELDS: [&str; 8] = [
+    "title",
+    "description",
+    "status",
+    "estimate_minutes",
+    "due_date",
+    "priority",
+    "labels",
+    "recurrence",
+];
+
+const CHANGE_UPDATE: &str = r#"
+    INSERT INTO "task_change" ("task_id", "project_id", "deleted")
+        SELECT OLD."id", OLD."project_id", TRUE WHERE OLD."project_id" IS NOT NEW."project_id";
+    INSERT INTO "task_change" ("task_id", "project_id") VALUES (NEW."id", NEW."project_id");
+"#;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskFieldVersion::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskFieldVersion::Id))
+                    .col(integer(TaskFieldVersion::TaskId))
+                    .col(string(TaskFieldVersion::Field))
+                    .col(integer(TaskFieldVersion::Version))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task-field-version-task_id")
+                            .from(TaskFieldVersion::Table, TaskFieldVersion::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task-field-version-task_id-field")
+                    .table(TaskFieldVersion::Table)
+                    .col(TaskFieldVersion::TaskId)
+                    .col(TaskFieldVersion::Field)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        // Tasks created before the change log get a first change, so every
+        // task has a version.
+        db.execute_unprepared(
+            r#"INSERT INTO "task_change" ("task_id", "project_id")
+                SELECT "id", "project_id" FROM "task"
+                WHERE "id" NOT IN (SELECT "task_id" FROM "task_change") ORDER BY "id""#,
+        )
+        .await?;
+
+        // A field is at the version of the change that last set it. The order
+        // of several triggers on one event is not defined, so the change
+        // trigger records both.
+        let versions: String = FIELDS
+            .iter()
+            .map(|field| {
+                format!(
+                    r#"INSERT INTO "task_field_version" ("task_id", "field", "version")
+                        SELECT NEW."id", '{0}', (SELECT MAX("id") FROM "task_change") WHERE OLD."{0}" IS NOT NEW."{0}"
+                        ON CONFLICT ("task_id", "field") DO UPDATE SET "version" = excluded."version";
+                    "#,
+                    field
+                )
+            })
+            .collect();
+
+        db.execute_unprepared(r#"DROP TRIGGER IF EXISTS "task_change_update""#)
+            .await?;
+        db.execute_unprepared(&format!(
+            r#"CREATE TRIGGER "task_change_update" AFTER UPDATE ON "task" BEGIN {}{} END"#,
+            CHANGE_UPDATE, versions
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(r#"DROP TRIGGER IF EXISTS "task_change_update""#)
+            .await?;
+        db.execute_unprepared(&format!(
+            r#"CREATE TRIGGER "task_change_update" AFTER UPDATE ON "task" BEGIN {} END"#,
+            CHANGE_UPDATE
+        ))
+        .await?;
+
+        manager
+            .drop_table(Table::drop().table(TaskFieldVersion::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskFieldVersion {
+    Table,
+    Id,
+    TaskId,
+    Field,
+    Version,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables holding details of a task, whose changes are changes to the task.
+const DETAIL_TABLES: [&str; 3] = ["task_assignee", "task_watcher", "task_checklist_item"];
+
+/// Events of a detail table recorded as a change, and the row they are about.
+const EVENTS: [(&str, &str); 3] = [("insert", "NEW"), ("update", "NEW"), ("delete", "OLD")];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // The task is gone when its details are deleted along with it, and
+        // the change of the task itself already covers them.
+        for table in DETAIL_TABLES {
+            for (event, row) in EVENTS {
+                db.execute_unprepared(&format!(
+                    r#"CREATE TRIGGER "{table}_change_{event}" AFTER {event} ON "{table}" BEGIN
+                        INSERT INTO "task_change" ("task_id", "project_id")
+                            SELECT "id", "project_id" FROM "task" WHERE "id" = {row}."task_id";
+                    END"#,
+                ))
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        for table in DETAIL_TABLES {
+            for (event, _) in EVENTS {
+                db.execute_unprepared(&format!(
+                    r#"DROP TRIGGER IF EXISTS "{table}_change_{event}""#
+                ))
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
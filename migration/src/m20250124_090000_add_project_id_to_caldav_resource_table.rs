@@ -0,0 +1,76 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The calendar a client stored the resource in. Left empty for the
+        // resources of tasks deleted before it was recorded.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CaldavResource::Table)
+                    .add_column(integer_null(CaldavResource::ProjectId))
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"UPDATE "caldav_resource" SET "project_id" =
+                (SELECT "project_id" FROM "task" WHERE "task"."id" = "caldav_resource"."task_id")"#,
+        )
+        .await?;
+
+        // Concurrent PUTs may have stored a name twice: the latest keeps it,
+        // the others fall back to their default name.
+        db.execute_unprepared(
+            r#"DELETE FROM "caldav_resource" WHERE "project_id" IS NOT NULL AND "id" NOT IN
+                (SELECT MAX("id") FROM "caldav_resource" WHERE "project_id" IS NOT NULL
+                    GROUP BY "project_id", "name")"#,
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-caldav-resource-project_id-name")
+                    .table(CaldavResource::Table)
+                    .col(CaldavResource::ProjectId)
+                    .col(CaldavResource::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-caldav-resource-project_id-name")
+                    .table(CaldavResource::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CaldavResource::Table)
+                    .drop_column(CaldavResource::ProjectId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CaldavResource {
+    Table,
+    ProjectId,
+    Name,
+}
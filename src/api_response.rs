@@ -1,6 +1,7 @@
 use axum::{response::IntoResponse, Json};
 use serde::Serialize;
 use serde_json::{json, Value};
+use utoipa::ToSchema;
 
 #[derive(Serialize)]
 pub enum JsonResponse {
@@ -35,19 +36,21 @@ impl JsonResponse {
     }
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ErrorResponse {
+    /// A message, or the validation errors of each field.
     pub error: Value,
     pub message: Option<String>,
 }
 
-#[derive(Serialize)]
-pub struct DataResponse {
-    pub data: Value,
+/// Envelope of every successful response. `T` is only set to document it.
+#[derive(Serialize, ToSchema)]
+pub struct DataResponse<T = Value> {
+    pub data: T,
     pub message: Option<String>,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct ResponseMetadata {
     pub count: u64,
     pub per_page: u64,
@@ -71,9 +74,10 @@ impl ResponseMetadata {
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct PaginatedResponse {
-    pub data: Value,
+/// Envelope of the paginated lists. `T` is only set to document it.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaginatedResponse<T = Value> {
+    pub data: T,
     pub _metadata: ResponseMetadata,
     pub message: Option<String>,
 }
@@ -0,0 +1,110 @@
+//! Versions of the REST API. `/api/v1` is the API as it first shipped, also
+//! served at `/api`, and answers with `Deprecation` and `Sunset` headers;
+//! `/api/v2` is where breaking changes go.
+//!
+//! Both versions share their handlers. Those answering with tasks are generic
+//! over `ApiVersion`, which picks the serializer of the version; the task
+//! exports and the sync protocol keep their own formats in every version.
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use serde::Serialize;
+
+use crate::serializer::{TaskSerializer, TaskV2Serializer};
+
+/// When v1 was deprecated, as an RFC 9745 date: 2026-10-19.
+const V1_DEPRECATION: &str = "@1792368000";
+
+/// When v1 will be removed, as an RFC 8594 HTTP date.
+const V1_SUNSET: &str = "Sat, 01 May 2027 00:00:00 GMT";
+
+pub trait ApiVersion: Send + Sync + 'static {
+    /// A task as this version returns it.
+    type Task: From<TaskSerializer> + Serialize + Send;
+}
+
+pub struct V1;
+
+impl ApiVersion for V1 {
+    type Task = TaskSerializer;
+}
+
+pub struct V2;
+
+impl ApiVersion for V2 {
+    type Task = TaskV2Serializer;
+}
+
+/// Marks the responses of v1 as deprecated, linking to the same route in v2.
+/// Runs within the nested v1 router, so the path is relative to it.
+pub async fn deprecate_v1(request: Request, next: Next) -> Response {
+    let successor = format!(
+        "</api/v2{}>; rel=\"successor-version\"",
+        request.uri().path()
+    );
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+
+    headers.insert("deprecation", HeaderValue::from_static(V1_DEPRECATION));
+    headers.insert("sunset", HeaderValue::from_static(V1_SUNSET));
+
+    if let Ok(successor) = HeaderValue::from_str(&successor) {
+        headers.append(header::LINK, successor);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::serializer::{ChecklistProgressSerializer, TaskStatus};
+
+    #[test]
+    fn v2_types_status_and_update_date() {
+        let task = TaskSerializer {
+            id: 1,
+            title: "Write report".to_string(),
+            description: String::new(),
+            status: "in_progress".to_string(),
+            reporter_id: 1,
+            key: None,
+            project_id: None,
+            rank: "n".to_string(),
+            assignees: Vec::new(),
+            checklist: ChecklistProgressSerializer { done: 0, total: 0 },
+            auto_complete: false,
+            estimate_minutes: None,
+            due_date: None,
+            priority: None,
+            labels: Vec::new(),
+            recurrence: None,
+            date_created: chrono::NaiveDateTime::default(),
+            date_updated: Some("2025-01-02 03:04:05".to_string()),
+        };
+
+        let v1 = json!(<V1 as ApiVersion>::Task::from(task.clone()));
+        assert_eq!(v1["status"], "in_progress");
+        assert_eq!(v1["date_updated"], "2025-01-02 03:04:05");
+
+        let v2 = json!(<V2 as ApiVersion>::Task::from(task.clone()));
+        assert_eq!(v2["status"], "in_progress");
+        assert_eq!(v2["date_updated"], "2025-01-02T03:04:05");
+
+        let legacy = TaskSerializer {
+            status: "todo".to_string(),
+            ..task
+        };
+        assert_eq!(
+            <V2 as ApiVersion>::Task::from(legacy).status,
+            TaskStatus::Pending
+        );
+    }
+}
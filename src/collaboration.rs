@@ -0,0 +1,412 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::{broadcast, watch, Notify};
+
+use crate::models::_entities::user;
+
+const CHANNEL_CAPACITY: usize = 1024;
+const DEFAULT_MAX_CONNECTIONS_PER_USER: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Viewer {
+    pub id: i32,
+    pub username: String,
+}
+
+impl From<&user::Model> for Viewer {
+    fn from(value: &user::Model) -> Self {
+        Self {
+            id: value.id,
+            username: value.username.clone(),
+        }
+    }
+}
+
+/// Messages sent to websocket clients.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Subscribed {
+        task_ids: Vec<i32>,
+    },
+    Presence {
+        task_id: i32,
+        viewers: Vec<Viewer>,
+    },
+    Typing {
+        task_id: i32,
+        user: Viewer,
+        is_typing: bool,
+    },
+    Change {
+        id: u64,
+        event: &'static str,
+        task_id: i32,
+        data: Value,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Messages received from websocket clients.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Subscribe { task_ids: Vec<i32> },
+    Unsubscribe { task_ids: Vec<i32> },
+    Typing { task_id: i32, is_typing: bool },
+}
+
+/// A presence or typing message for the subscribers of one task.
+#[derive(Debug, Clone)]
+pub struct RoomMessage {
+    pub task_id: i32,
+    pub from_user_id: i32,
+    pub message: ServerMessage,
+}
+
+impl RoomMessage {
+    /// Whether a connection of `user_id` subscribed to `subscribed` should
+    /// receive the message. Users are not sent their own typing.
+    pub fn is_for(&self, user_id: i32, subscribed: &HashSet<i32>) -> bool {
+        let is_own_typing =
+            matches!(self.message, ServerMessage::Typing { .. }) && self.from_user_id == user_id;
+
+        subscribed.contains(&self.task_id) && !is_own_typing
+    }
+}
+
+/// Users viewing a task, with the number of their connections viewing it.
+type TaskViewers = HashMap<i32, (Viewer, usize)>;
+
+#[derive(Debug)]
+struct Inner {
+    max_connections_per_user: usize,
+    connections: Mutex<HashMap<i32, usize>>,
+    viewers: Mutex<HashMap<i32, TaskViewers>>,
+    sender: broadcast::Sender<RoomMessage>,
+    shutdown: watch::Sender<bool>,
+    closed: Notify,
+}
+
+/// Shared state of the `/ws` collaboration channel.
+#[derive(Debug, Clone)]
+pub struct CollaborationHub {
+    inner: Arc<Inner>,
+}
+
+impl Default for CollaborationHub {
+    fn default() -> Self {
+        let max_connections_per_user = std::env::var("WS_MAX_CONNECTIONS_PER_USER")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_MAX_CONNECTIONS_PER_USER);
+
+        Self::new(max_connections_per_user)
+    }
+}
+
+impl CollaborationHub {
+    pub fn new(max_connections_per_user: usize) -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let (shutdown, _) = watch::channel(false);
+
+        Self {
+            inner: Arc::new(Inner {
+                max_connections_per_user,
+                connections: Mutex::new(HashMap::new()),
+                viewers: Mutex::new(HashMap::new()),
+                sender,
+                shutdown,
+                closed: Notify::new(),
+            }),
+        }
+    }
+
+    /// Registers a connection for `user`, or returns `None` when the user
+    /// already has the maximum number of open connections.
+    pub fn connect(&self, user: &user::Model) -> Option<ConnectionGuard> {
+        let mut connections = self.inner.connections.lock().expect("Hub lock poisoned");
+
+        let count = connections.entry(user.id).or_insert(0);
+
+        if *count >= self.inner.max_connections_per_user {
+            return None;
+        }
+
+        *count += 1;
+
+        Some(ConnectionGuard {
+            hub: self.clone(),
+            user_id: user.id,
+        })
+    }
+
+    fn disconnect(&self, user_id: i32) {
+        let mut connections = self.inner.connections.lock().expect("Hub lock poisoned");
+
+        if let Some(count) = connections.get_mut(&user_id) {
+            *count -= 1;
+
+            if *count == 0 {
+                connections.remove(&user_id);
+            }
+        }
+
+        if connections.is_empty() {
+            self.inner.closed.notify_waiters();
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<RoomMessage> {
+        self.inner.sender.subscribe()
+    }
+
+    fn publish(&self, task_id: i32, from_user_id: i32, message: ServerMessage) {
+        // Sending only fails when nobody is subscribed.
+        let _ = self.inner.sender.send(RoomMessage {
+            task_id,
+            from_user_id,
+            message,
+        });
+    }
+
+    fn publish_presence(&self, task_id: i32, viewers: Vec<Viewer>) {
+        self.publish(task_id, 0, ServerMessage::Presence { task_id, viewers });
+    }
+
+    pub fn join(&self, task_id: i32, viewer: &Viewer) {
+        let viewers = {
+            let mut tasks = self.inner.viewers.lock().expect("Hub lock poisoned");
+            let task_viewers = tasks.entry(task_id).or_default();
+
+            task_viewers
+                .entry(viewer.id)
+                .or_insert_with(|| (viewer.clone(), 0))
+                .1 += 1;
+
+            task_viewers.values().map(|(v, _)| v.clone()).collect()
+        };
+
+        self.publish_presence(task_id, viewers);
+    }
+
+    pub fn leave(&self, task_id: i32, user_id: i32) {
+        let viewers = {
+            let mut tasks = self.inner.viewers.lock().expect("Hub lock poisoned");
+
+            let Some(task_viewers) = tasks.get_mut(&task_id) else {
+                return;
+            };
+
+            if let Some((_, count)) = task_viewers.get_mut(&user_id) {
+                *count -= 1;
+
+                if *count == 0 {
+                    task_viewers.remove(&user_id);
+                }
+            }
+
+            let viewers: Vec<Viewer> = task_viewers.values().map(|(v, _)| v.clone()).collect();
+
+            if viewers.is_empty() {
+                tasks.remove(&task_id);
+            }
+
+            viewers
+        };
+
+        self.publish_presence(task_id, viewers);
+    }
+
+    pub fn typing(&self, task_id: i32, user: Viewer, is_typing: bool) {
+        self.publish(
+            task_id,
+            user.id,
+            ServerMessage::Typing {
+                task_id,
+                user,
+                is_typing,
+            },
+        );
+    }
+
+    pub fn shutdown_receiver(&self) -> watch::Receiver<bool> {
+        self.inner.shutdown.subscribe()
+    }
+
+    /// Asks every open connection to close.
+    pub fn shutdown(&self) {
+        self.inner.shutdown.send_replace(true);
+    }
+
+    /// Waits until every connection has been closed.
+    pub async fn closed(&self) {
+        loop {
+            let notified = self.inner.closed.notified();
+
+            if self
+                .inner
+                .connections
+                .lock()
+                .expect("Hub lock poisoned")
+                .is_empty()
+            {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+}
+
+/// Releases the connection slot of a user when dropped.
+pub struct ConnectionGuard {
+    hub: CollaborationHub,
+    user_id: i32,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.hub.disconnect(self.user_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn user(id: i32, username: &str) -> user::Model {
+        user::Model {
+            id,
+            name: username.to_string(),
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+            password: "password".to_string(),
+            date_created: chrono::Utc::now().naive_utc(),
+            date_updated: None,
+        }
+    }
+
+    /// Usernames of the next presence message about `task_id`.
+    fn next_presence(receiver: &mut broadcast::Receiver<RoomMessage>, task_id: i32) -> Vec<String> {
+        loop {
+            match receiver.try_recv().unwrap().message {
+                ServerMessage::Presence {
+                    task_id: id,
+                    viewers,
+                } if id == task_id => {
+                    let mut usernames: Vec<String> =
+                        viewers.into_iter().map(|viewer| viewer.username).collect();
+                    usernames.sort();
+                    return usernames;
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    #[test]
+    fn connections_past_the_limit_are_refused() {
+        let hub = CollaborationHub::new(2);
+        let alice = user(1, "alice");
+        let bob = user(2, "bob");
+
+        let first = hub.connect(&alice).unwrap();
+        let _second = hub.connect(&alice).unwrap();
+        assert!(hub.connect(&alice).is_none());
+
+        // The limit is per user.
+        assert!(hub.connect(&bob).is_some());
+
+        drop(first);
+        assert!(hub.connect(&alice).is_some());
+    }
+
+    #[test]
+    fn presence_drops_a_user_with_their_last_connection() {
+        let hub = CollaborationHub::new(5);
+        let mut receiver = hub.subscribe();
+        let alice = Viewer::from(&user(1, "alice"));
+        let bob = Viewer::from(&user(2, "bob"));
+
+        hub.join(7, &alice);
+        assert_eq!(next_presence(&mut receiver, 7), vec!["alice"]);
+
+        // A second connection of alice, e.g. another tab.
+        hub.join(7, &alice);
+        assert_eq!(next_presence(&mut receiver, 7), vec!["alice"]);
+
+        hub.join(7, &bob);
+        assert_eq!(next_presence(&mut receiver, 7), vec!["alice", "bob"]);
+
+        hub.leave(7, alice.id);
+        assert_eq!(next_presence(&mut receiver, 7), vec!["alice", "bob"]);
+
+        hub.leave(7, alice.id);
+        assert_eq!(next_presence(&mut receiver, 7), vec!["bob"]);
+
+        hub.leave(7, bob.id);
+        assert!(next_presence(&mut receiver, 7).is_empty());
+
+        // Leaving a task nobody views publishes nothing.
+        hub.leave(7, bob.id);
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn typing_is_not_echoed_to_the_sender() {
+        let hub = CollaborationHub::new(5);
+        let mut receiver = hub.subscribe();
+        let alice = Viewer::from(&user(1, "alice"));
+        let subscribed = HashSet::from([7]);
+
+        hub.typing(7, alice.clone(), true);
+        let typing = receiver.try_recv().unwrap();
+
+        assert!(!typing.is_for(alice.id, &subscribed));
+        assert!(typing.is_for(2, &subscribed));
+        assert!(!typing.is_for(2, &HashSet::from([8])));
+
+        // Presence is sent to everyone, the user who joined included.
+        hub.join(7, &alice);
+        let presence = receiver.try_recv().unwrap();
+
+        assert!(presence.is_for(alice.id, &subscribed));
+    }
+
+    #[tokio::test]
+    async fn closed_resolves_once_every_guard_is_dropped() {
+        let hub = CollaborationHub::new(5);
+        let mut shutdown = hub.shutdown_receiver();
+
+        let first = hub.connect(&user(1, "alice")).unwrap();
+        let second = hub.connect(&user(2, "bob")).unwrap();
+
+        hub.shutdown();
+        assert!(*shutdown.borrow_and_update());
+
+        let closed = tokio::spawn({
+            let hub = hub.clone();
+            async move { hub.closed().await }
+        });
+
+        drop(first);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!closed.is_finished());
+
+        drop(second);
+        tokio::time::timeout(Duration::from_secs(1), closed)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
-    api_response::JsonResponse,
+    api_response::{DataResponse, JsonResponse},
     auth::jwt::{create_user_token, UserToken},
     error::AppError,
     form::user_form::UserLogin,
@@ -27,6 +27,14 @@ pub async fn get_logout_route() -> Router<Arc<AppState>> {
     Router::new().route("/auth/logout", post(logout))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/login",
+    tag = "auth",
+    request_body = UserLogin,
+    responses((status = 200, body = DataResponse<UserToken>)),
+    security(()),
+)]
 #[axum::debug_handler]
 pub async fn login(
     State(app_state): State<Arc<AppState>>,
@@ -53,4 +61,10 @@ pub async fn login(
     Ok(JsonResponse::data(user_token, None))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    tag = "auth",
+    responses((status = 200, description = "Logged out")),
+)]
 pub async fn logout() {}
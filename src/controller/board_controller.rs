@@ -0,0 +1,226 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    routing::{get, put},
+    Extension, Json, Router,
+};
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue::NotSet, ColumnTrait, ConnectionTrait, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use validator::Validate;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    api_version::ApiVersion,
+    controller::project_controller::find_project,
+    error::AppError,
+    form::project_form::UpdateWipLimitsRequest,
+    models::{
+        _entities::{board_column, task, user},
+        project::{inbox, ProjectPermission},
+        task::{in_column, with_details, STATUSES},
+    },
+    serializer::{BoardColumnSerializer, BoardSerializer, ProjectSerializer, TaskSerializer},
+    AppState,
+};
+
+pub async fn get_routes<V: ApiVersion>() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/board", get(get_board::<V>))
+        .route("/projects/:project_id/wip_limits", put(update_wip_limits))
+}
+
+async fn wip_limits<C>(db: &C, project_id: i32) -> Result<HashMap<String, i32>, AppError>
+where
+    C: ConnectionTrait,
+{
+    Ok(board_column::Entity::find()
+        .filter(board_column::Column::ProjectId.eq(project_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|column| (column.status, column.wip_limit))
+        .collect())
+}
+
+/// Fails when moving a task into `status` would exceed the WIP limit of that
+/// column. `task_id` is the task being moved, which is not counted.
+pub async fn ensure_wip_limit<C>(
+    db: &C,
+    project_id: Option<i32>,
+    status: &str,
+    task_id: Option<i32>,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    ensure_wip_room(db, project_id, status, task_id, 1).await
+}
+
+/// Like `ensure_wip_limit`, for `count` tasks entering the column at once.
+pub async fn ensure_wip_room<C>(
+    db: &C,
+    project_id: Option<i32>,
+    status: &str,
+    task_id: Option<i32>,
+    count: u64,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    let Some(project_id) = project_id else {
+        return Ok(());
+    };
+
+    let Some(column) = board_column::Entity::find()
+        .filter(board_column::Column::ProjectId.eq(project_id))
+        .filter(board_column::Column::Status.eq(status))
+        .one(db)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let mut task_query = task::Entity::find().filter(in_column(Some(project_id), status));
+
+    if let Some(task_id) = task_id {
+        task_query = task_query.filter(task::Column::Id.ne(task_id));
+    }
+
+    if task_query.count(db).await? + count > column.wip_limit as u64 {
+        return Err(AppError::BadRequest(format!(
+            "The {} column has reached its WIP limit of {}.",
+            status, column.wip_limit
+        )));
+    }
+
+    Ok(())
+}
+
+/// Returns the tasks of a project grouped by status, in rank order. Uses the
+/// Inbox of the user when no `project_id` is given.
+#[utoipa::path(
+    get,
+    path = "/api/board",
+    tag = "projects",
+    params(
+        ("project_id" = Option<i32>, Query, description = "Project of the board, the Inbox by default"),
+    ),
+    responses((status = 200, body = DataResponse<BoardSerializer>)),
+)]
+pub async fn get_board<V: ApiVersion>(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<impl IntoResponse, AppError> {
+    let project = match params.get("project_id") {
+        Some(project_id) => {
+            let project_id = project_id
+                .parse::<i32>()
+                .map_err(|_| AppError::BadRequest("Invalid project.".to_string()))?;
+
+            find_project(&app_state.db, &user, project_id, ProjectPermission::View).await?
+        }
+        None => inbox(&app_state.db, &user).await?,
+    };
+
+    let tasks = task::Entity::find()
+        .filter(task::Column::ProjectId.eq(project.id))
+        .order_by_asc(task::Column::Rank)
+        .order_by_asc(task::Column::Id)
+        .all(&app_state.db)
+        .await?;
+
+    let mut tasks_by_status: HashMap<String, Vec<TaskSerializer>> = HashMap::new();
+
+    for task in with_details(&app_state.db, tasks).await? {
+        let task = TaskSerializer::from(task);
+
+        tasks_by_status
+            .entry(task.status.clone())
+            .or_default()
+            .push(task);
+    }
+
+    let limits = wip_limits(&app_state.db, project.id).await?;
+
+    let columns = STATUSES
+        .iter()
+        .map(|status| {
+            let tasks = tasks_by_status.remove(*status).unwrap_or_default();
+
+            BoardColumnSerializer {
+                status: status.to_string(),
+                wip_limit: limits.get(*status).copied(),
+                count: tasks.len(),
+                tasks: tasks.into_iter().map(V::Task::from).collect(),
+            }
+        })
+        .collect();
+
+    let board: BoardSerializer<V::Task> = BoardSerializer {
+        project: ProjectSerializer::from(project),
+        columns,
+    };
+
+    Ok(JsonResponse::data(board, None))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/wip_limits",
+    tag = "projects",
+    params(
+        ("project_id" = i32, Path, description = "Id of the project"),
+    ),
+    request_body = UpdateWipLimitsRequest,
+    responses((status = 200, body = DataResponse<HashMap<String, i32>>)),
+)]
+#[axum::debug_handler]
+pub async fn update_wip_limits(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(project_id): Path<i32>,
+    Json(limits_request): Json<UpdateWipLimitsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let project = find_project(&app_state.db, &user, project_id, ProjectPermission::Manage).await?;
+
+    limits_request.validate()?;
+
+    for (status, limit) in limits_request.limits {
+        match limit {
+            Some(limit) => {
+                board_column::Entity::insert(board_column::ActiveModel {
+                    id: NotSet,
+                    project_id: Set(project.id),
+                    status: Set(status),
+                    wip_limit: Set(limit as i32),
+                })
+                .on_conflict(
+                    OnConflict::columns([
+                        board_column::Column::ProjectId,
+                        board_column::Column::Status,
+                    ])
+                    .update_column(board_column::Column::WipLimit)
+                    .to_owned(),
+                )
+                .exec(&app_state.db)
+                .await?;
+            }
+            None => {
+                board_column::Entity::delete_many()
+                    .filter(board_column::Column::ProjectId.eq(project.id))
+                    .filter(board_column::Column::Status.eq(status))
+                    .exec(&app_state.db)
+                    .await?;
+            }
+        }
+    }
+
+    let limits = wip_limits(&app_state.db, project.id).await?;
+
+    Ok(JsonResponse::data(limits, None))
+}
@@ -0,0 +1,925 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderName, Method, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    routing::any,
+    Router,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sea_orm::{
+    sea_query::Query, ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, ConnectionTrait, DbErr,
+    EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, Set, SqlErr, TransactionError,
+    TransactionTrait,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    controller::{
+        calendar_controller::{ical_date_time, todo_row, write_todo, PRODID},
+        project_controller::find_project,
+        task_controller::{
+            announce_created, change_status, create_tasks, ensure_room_for, remove_task, save_task,
+        },
+        task_transfer_controller::row_request,
+    },
+    dav::{self, PropName, CALDAV, CALENDAR_SERVER, DAV},
+    error::AppError,
+    form::task_form::normalize_recurrence,
+    ical,
+    models::{
+        _entities::{caldav_resource, project, task, user},
+        caldav_resource::{default_resource, resources_of, task_ids_named},
+        project::{has_global_permission, has_permission, member_of, ProjectPermission},
+        task::join_labels,
+        task_change::{changes_since, latest_change},
+    },
+    utils::verify_password,
+    AppState,
+};
+
+const DAV_ROOT: &str = "/api/dav/";
+const PRINCIPAL: &str = "/api/dav/principal/";
+const CALENDAR_HOME: &str = "/api/dav/projects/";
+const SYNC_TOKEN_PREFIX: &str = "urn:task_app:sync:";
+const ALLOW: &str = "OPTIONS, GET, HEAD, PUT, DELETE, PROPFIND, REPORT";
+const TODO_CONTENT_TYPE: &str = "text/calendar; charset=utf-8; component=VTODO";
+
+/// Properties returned when a client asks for all of them.
+const PRINCIPAL_PROPS: [(&str, &str); 5] = [
+    (DAV, "resourcetype"),
+    (DAV, "displayname"),
+    (DAV, "current-user-principal"),
+    (CALDAV, "calendar-home-set"),
+    (CALDAV, "calendar-user-address-set"),
+];
+const CALENDAR_PROPS: [(&str, &str); 7] = [
+    (DAV, "resourcetype"),
+    (DAV, "displayname"),
+    (CALDAV, "supported-calendar-component-set"),
+    (DAV, "supported-report-set"),
+    (DAV, "current-user-privilege-set"),
+    (DAV, "sync-token"),
+    (CALENDAR_SERVER, "getctag"),
+];
+const TODO_PROPS: [(&str, &str); 3] = [
+    (DAV, "resourcetype"),
+    (DAV, "getetag"),
+    (DAV, "getcontenttype"),
+];
+
+/// Serves each project the user belongs to as a calendar of `VTODO`s.
+/// Clients authenticate with HTTP Basic and their username and password, so
+/// these routes stay outside the auth guard.
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/dav", any(dav_root))
+        .route("/dav/", any(dav_root))
+        .route("/dav/principal/", any(principal))
+        .route("/dav/projects/", any(calendar_home))
+        .route("/dav/projects/:project_id", any(calendar))
+        .route("/dav/projects/:project_id/", any(calendar))
+        .route("/dav/projects/:project_id/:name", any(calendar_object))
+}
+
+/// Lets clients find the server from its host name alone (RFC 6764).
+pub async fn get_well_known_route() -> Router<Arc<AppState>> {
+    Router::new().route(
+        "/.well-known/caldav",
+        any(|| async { Redirect::permanent(DAV_ROOT) }),
+    )
+}
+
+async fn dav_user(
+    app_state: &AppState,
+    headers: &HeaderMap,
+) -> Result<Option<user::Model>, AppError> {
+    let Some((username, password)) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Basic "))
+        .and_then(|credentials| STANDARD.decode(credentials.trim()).ok())
+        .and_then(|credentials| String::from_utf8(credentials).ok())
+        .and_then(|credentials| {
+            credentials
+                .split_once(':')
+                .map(|(username, password)| (username.to_string(), password.to_string()))
+        })
+    else {
+        return Ok(None);
+    };
+
+    let user = user::Entity::find()
+        .filter(user::Column::Username.eq(username))
+        .one(&app_state.db)
+        .await?;
+
+    match user {
+        Some(user) if verify_password(&user.password, &password)? => Ok(Some(user)),
+        _ => Ok(None),
+    }
+}
+
+fn challenge() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Basic realm=\"Tasks\"")],
+    )
+        .into_response()
+}
+
+fn options() -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::ALLOW, ALLOW),
+            (HeaderName::from_static("dav"), "1, 3, calendar-access"),
+        ],
+    )
+        .into_response()
+}
+
+fn method_not_allowed() -> Response {
+    (StatusCode::METHOD_NOT_ALLOWED, [(header::ALLOW, ALLOW)]).into_response()
+}
+
+fn multistatus(responses: &[dav::Response], sync_token: Option<&str>) -> Response {
+    (
+        StatusCode::MULTI_STATUS,
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        dav::multistatus(responses, sync_token),
+    )
+        .into_response()
+}
+
+/// Whether a PROPFIND covers the members of a collection. An infinite depth
+/// is treated as 1.
+fn includes_members(headers: &HeaderMap) -> bool {
+    headers
+        .get("depth")
+        .and_then(|depth| depth.to_str().ok())
+        .is_none_or(|depth| depth.trim() != "0")
+}
+
+fn propfind_props(body: &str) -> Result<Option<Vec<PropName>>, AppError> {
+    match dav::parse_request(body).map_err(AppError::BadRequest)? {
+        dav::Request::PropFind { props } => Ok(props),
+        _ => Err(AppError::BadRequest("Expected a propfind.".to_string())),
+    }
+}
+
+/// Answers each requested property with `value`, or every property in
+/// `defaults` when the client asked for all of them.
+fn respond(
+    href: String,
+    props: &Option<Vec<PropName>>,
+    defaults: &[(&str, &str)],
+    value: impl Fn(&PropName) -> Option<String>,
+) -> dav::Response {
+    let names = props.clone().unwrap_or_else(|| {
+        defaults
+            .iter()
+            .map(|(namespace, name)| PropName::new(namespace, name))
+            .collect()
+    });
+
+    dav::Response {
+        href,
+        props: names
+            .into_iter()
+            .map(|prop| {
+                let value = value(&prop);
+                (prop, value)
+            })
+            .collect(),
+        status: None,
+    }
+}
+
+fn href(href: &str) -> String {
+    format!("<d:href>{}</d:href>", dav::escape(href))
+}
+
+fn principal_prop(user: &user::Model, resourcetype: &str, prop: &PropName) -> Option<String> {
+    match (prop.namespace.as_str(), prop.name.as_str()) {
+        (DAV, "resourcetype") => Some(resourcetype.to_string()),
+        (DAV, "displayname") => Some(dav::escape(&user.name)),
+        (DAV, "current-user-principal") | (DAV, "principal-URL") => Some(href(PRINCIPAL)),
+        (CALDAV, "calendar-home-set") => Some(href(CALENDAR_HOME)),
+        (CALDAV, "calendar-user-address-set") => Some(href(&format!("mailto:{}", user.email))),
+        _ => None,
+    }
+}
+
+/// Answers PROPFIND on the root, the principal and the calendar home, which
+/// all point clients to the principal and its calendars.
+async fn principal_resource(
+    app_state: &AppState,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+    path: &str,
+    resourcetype: &str,
+) -> Result<Response, AppError> {
+    if method == Method::OPTIONS {
+        return Ok(options());
+    }
+
+    let Some(user) = dav_user(app_state, &headers).await? else {
+        return Ok(challenge());
+    };
+
+    if method.as_str() != "PROPFIND" {
+        return Ok(method_not_allowed());
+    }
+
+    let props = propfind_props(&body)?;
+
+    let mut responses = vec![respond(
+        path.to_string(),
+        &props,
+        &PRINCIPAL_PROPS,
+        |prop| principal_prop(&user, resourcetype, prop),
+    )];
+
+    if path == CALENDAR_HOME && includes_members(&headers) {
+        let sync_token = sync_token(latest_change(&app_state.db).await?);
+
+        for project in calendars_of(app_state, &user).await? {
+            let writable = has_permission(
+                &app_state.db,
+                &user,
+                &project,
+                ProjectPermission::ChangeTasks,
+            )
+            .await?;
+
+            responses.push(calendar_response(&project, &props, &sync_token, writable));
+        }
+    }
+
+    Ok(multistatus(&responses, None))
+}
+
+#[axum::debug_handler]
+pub async fn dav_root(
+    State(app_state): State<Arc<AppState>>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, AppError> {
+    principal_resource(
+        &app_state,
+        method,
+        headers,
+        body,
+        DAV_ROOT,
+        "<d:collection/>",
+    )
+    .await
+}
+
+#[axum::debug_handler]
+pub async fn principal(
+    State(app_state): State<Arc<AppState>>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, AppError> {
+    principal_resource(
+        &app_state,
+        method,
+        headers,
+        body,
+        PRINCIPAL,
+        "<d:principal/>",
+    )
+    .await
+}
+
+#[axum::debug_handler]
+pub async fn calendar_home(
+    State(app_state): State<Arc<AppState>>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, AppError> {
+    principal_resource(
+        &app_state,
+        method,
+        headers,
+        body,
+        CALENDAR_HOME,
+        "<d:collection/>",
+    )
+    .await
+}
+
+fn sync_token(change: i32) -> String {
+    format!("{}{}", SYNC_TOKEN_PREFIX, change)
+}
+
+fn calendar_href(project_id: i32) -> String {
+    format!("{}{}/", CALENDAR_HOME, project_id)
+}
+
+/// The projects listed as calendars: the ones `user` is a member of, or all
+/// of them with the global view permission. Archived projects are left out.
+async fn calendars_of(
+    app_state: &AppState,
+    user: &user::Model,
+) -> Result<Vec<project::Model>, AppError> {
+    let mut project_query = project::Entity::find().filter(project::Column::IsArchived.eq(false));
+
+    if !has_global_permission(&app_state.db, user, ProjectPermission::View).await? {
+        project_query = project_query.filter(member_of(user.id));
+    }
+
+    Ok(project_query
+        .order_by_desc(project::Column::IsInbox)
+        .order_by_asc(project::Column::Name)
+        .all(&app_state.db)
+        .await?)
+}
+
+fn calendar_response(
+    project: &project::Model,
+    props: &Option<Vec<PropName>>,
+    sync_token: &str,
+    writable: bool,
+) -> dav::Response {
+    respond(
+        calendar_href(project.id),
+        props,
+        &CALENDAR_PROPS,
+        |prop| match (prop.namespace.as_str(), prop.name.as_str()) {
+            (DAV, "resourcetype") => Some("<d:collection/><c:calendar/>".to_string()),
+            (DAV, "displayname") => Some(dav::escape(&project.name)),
+            (CALDAV, "calendar-description") => project.description.as_deref().map(dav::escape),
+            (CALDAV, "supported-calendar-component-set") => {
+                Some(r#"<c:comp name="VTODO"/>"#.to_string())
+            }
+            (DAV, "supported-report-set") => Some(
+                [
+                    "<c:calendar-query/>",
+                    "<c:calendar-multiget/>",
+                    "<d:sync-collection/>",
+                ]
+                .iter()
+                .map(|report| {
+                    format!(
+                        "<d:supported-report><d:report>{}</d:report></d:supported-report>",
+                        report
+                    )
+                })
+                .collect(),
+            ),
+            (DAV, "current-user-privilege-set") => {
+                let privileges: &[&str] = if writable {
+                    &["read", "write", "write-content", "bind", "unbind"]
+                } else {
+                    &["read"]
+                };
+
+                Some(
+                    privileges
+                        .iter()
+                        .map(|privilege| format!("<d:privilege><d:{}/></d:privilege>", privilege))
+                        .collect(),
+                )
+            }
+            (DAV, "current-user-principal") => Some(href(PRINCIPAL)),
+            (DAV, "sync-token") | (CALENDAR_SERVER, "getctag") => Some(dav::escape(sync_token)),
+            _ => None,
+        },
+    )
+}
+
+/// A task as a calendar object resource.
+struct Todo {
+    task: task::Model,
+    name: String,
+    data: String,
+    etag: String,
+}
+
+fn todo(task: task::Model, resource: Option<caldav_resource::Model>) -> Todo {
+    let (name, uid) = match resource {
+        Some(resource) => (resource.name, resource.uid),
+        None => default_resource(task.id),
+    };
+
+    // Stamped with its creation time, the same task always gives the same
+    // data, and so the same ETag.
+    let mut data = String::new();
+
+    ical::write_line(&mut data, "BEGIN", "VCALENDAR");
+    ical::write_line(&mut data, "VERSION", "2.0");
+    ical::write_line(&mut data, "PRODID", PRODID);
+    write_todo(&mut data, &task, &uid, &ical_date_time(task.date_created));
+    ical::write_line(&mut data, "END", "VCALENDAR");
+
+    let etag = format!("\"{}\"", hex::encode(Sha256::digest(&data)));
+
+    Todo {
+        task,
+        name,
+        data,
+        etag,
+    }
+}
+
+/// The tasks of `project_id`, or only the ones in `task_ids`.
+async fn todos_of<C>(
+    db: &C,
+    project_id: i32,
+    task_ids: Option<Vec<i32>>,
+) -> Result<Vec<Todo>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let mut task_query = task::Entity::find().filter(task::Column::ProjectId.eq(project_id));
+
+    if let Some(task_ids) = task_ids {
+        task_query = task_query.filter(task::Column::Id.is_in(task_ids));
+    }
+
+    let tasks = task_query.order_by_asc(task::Column::Id).all(db).await?;
+    let mut resources = resources_of(db, tasks.iter().map(|task| task.id).collect()).await?;
+
+    Ok(tasks
+        .into_iter()
+        .map(|task| {
+            let resource = resources.remove(&task.id);
+            todo(task, resource)
+        })
+        .collect())
+}
+
+async fn find_todo<C>(db: &C, project_id: i32, name: &str) -> Result<Option<Todo>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    for task_id in task_ids_named(db, name).await? {
+        if let Some(todo) = todos_of(db, project_id, Some(vec![task_id])).await?.pop() {
+            if todo.name == name {
+                return Ok(Some(todo));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn todo_response(project_id: i32, todo: &Todo, props: &Option<Vec<PropName>>) -> dav::Response {
+    respond(
+        format!("{}{}", calendar_href(project_id), todo.name),
+        props,
+        &TODO_PROPS,
+        |prop| match (prop.namespace.as_str(), prop.name.as_str()) {
+            (DAV, "resourcetype") => Some(String::new()),
+            (DAV, "getetag") => Some(dav::escape(&todo.etag)),
+            (DAV, "getcontenttype") => Some(TODO_CONTENT_TYPE.to_string()),
+            (CALDAV, "calendar-data") => Some(dav::escape(&todo.data)),
+            _ => None,
+        },
+    )
+}
+
+fn not_found(href: String) -> dav::Response {
+    dav::Response {
+        href,
+        status: Some(404),
+        ..Default::default()
+    }
+}
+
+/// Lists what changed in a calendar since `sync_token`: the tasks created or
+/// updated, and the ones deleted or moved away as not found.
+async fn sync_collection(
+    app_state: &AppState,
+    project_id: i32,
+    props: &Option<Vec<PropName>>,
+    sync_token: &str,
+) -> Result<Option<Vec<dav::Response>>, AppError> {
+    if sync_token.is_empty() {
+        let todos = todos_of(&app_state.db, project_id, None).await?;
+
+        return Ok(Some(
+            todos
+                .iter()
+                .map(|todo| todo_response(project_id, todo, props))
+                .collect(),
+        ));
+    }
+
+    let Some(since) = sync_token
+        .strip_prefix(SYNC_TOKEN_PREFIX)
+        .and_then(|change| change.parse().ok())
+    else {
+        return Ok(None);
+    };
+
+    // The latest change of each task tells whether it is still there.
+    let changed: BTreeMap<i32, bool> = changes_since(&app_state.db, project_id, since)
+        .await?
+        .into_iter()
+        .map(|change| (change.task_id, change.deleted))
+        .collect();
+
+    let todos = todos_of(
+        &app_state.db,
+        project_id,
+        Some(changed.keys().copied().collect()),
+    )
+    .await?;
+
+    let mut responses: Vec<dav::Response> = todos
+        .iter()
+        .map(|todo| todo_response(project_id, todo, props))
+        .collect();
+
+    let gone: Vec<i32> = changed
+        .into_keys()
+        .filter(|task_id| !todos.iter().any(|todo| todo.task.id == *task_id))
+        .collect();
+
+    let mut resources = resources_of(&app_state.db, gone.clone()).await?;
+
+    for task_id in gone {
+        let name = match resources.remove(&task_id) {
+            Some(resource) => resource.name,
+            None => default_resource(task_id).0,
+        };
+
+        responses.push(not_found(format!("{}{}", calendar_href(project_id), name)));
+    }
+
+    Ok(Some(responses))
+}
+
+#[axum::debug_handler]
+pub async fn calendar(
+    State(app_state): State<Arc<AppState>>,
+    Path(project_id): Path<i32>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, AppError> {
+    if method == Method::OPTIONS {
+        return Ok(options());
+    }
+
+    let Some(user) = dav_user(&app_state, &headers).await? else {
+        return Ok(challenge());
+    };
+
+    let project = find_project(&app_state.db, &user, project_id, ProjectPermission::View).await?;
+
+    // Taken first, so changes made while answering are sent again next time
+    // rather than missed.
+    let sync_token = sync_token(latest_change(&app_state.db).await?);
+
+    match method.as_str() {
+        "PROPFIND" => {
+            let props = propfind_props(&body)?;
+            let writable =
+                has_permission(&app_state.db, &user, &project, ProjectPermission::ChangeTasks)
+                    .await?;
+
+            let mut responses = vec![calendar_response(&project, &props, &sync_token, writable)];
+
+            if includes_members(&headers) {
+                for todo in todos_of(&app_state.db, project.id, None).await? {
+                    responses.push(todo_response(project.id, &todo, &props));
+                }
+            }
+
+            Ok(multistatus(&responses, None))
+        }
+        "REPORT" => match dav::parse_request(&body).map_err(AppError::BadRequest)? {
+            dav::Request::CalendarQuery { props, todos } => {
+                let mut responses = Vec::new();
+
+                if todos {
+                    for todo in todos_of(&app_state.db, project.id, None).await? {
+                        responses.push(todo_response(project.id, &todo, &props));
+                    }
+                }
+
+                Ok(multistatus(&responses, None))
+            }
+            dav::Request::CalendarMultiget { props, hrefs } => {
+                let mut responses = Vec::new();
+
+                for href in hrefs {
+                    let name = href.rsplit('/').next().unwrap_or_default();
+
+                    responses.push(match find_todo(&app_state.db, project.id, name).await? {
+                        Some(todo) => todo_response(project.id, &todo, &props),
+                        None => not_found(href),
+                    });
+                }
+
+                Ok(multistatus(&responses, None))
+            }
+            dav::Request::SyncCollection {
+                props,
+                sync_token: client_token,
+            } => match sync_collection(&app_state, project.id, &props, &client_token).await? {
+                Some(responses) => Ok(multistatus(&responses, Some(&sync_token))),
+                None => Ok((
+                    StatusCode::FORBIDDEN,
+                    [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+                    r#"<?xml version="1.0" encoding="utf-8"?><d:error xmlns:d="DAV:"><d:valid-sync-token/></d:error>"#,
+                )
+                    .into_response()),
+            },
+            dav::Request::PropFind { .. } => {
+                Err(AppError::BadRequest("Expected a report.".to_string()))
+            }
+        },
+        _ => Ok(method_not_allowed()),
+    }
+}
+
+/// Checks `If-Match` and `If-None-Match` against the ETag of the resource,
+/// `None` when it does not exist.
+fn preconditions_hold(headers: &HeaderMap, etag: Option<&str>) -> bool {
+    let matches = |name: header::HeaderName| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| {
+                etag.is_some_and(|etag| {
+                    value
+                        .split(',')
+                        .any(|candidate| candidate.trim() == "*" || candidate.trim() == etag)
+                })
+            })
+    };
+
+    matches(header::IF_MATCH) != Some(false) && matches(header::IF_NONE_MATCH) != Some(true)
+}
+
+/// Resource names clients may create, such as `<uuid>.ics`.
+fn is_resource_name(name: &str) -> bool {
+    name.len() <= 200
+        && name.strip_suffix(".ics").is_some_and(|stem| {
+            !stem.is_empty()
+                && stem
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~'))
+        })
+}
+
+/// Creates or replaces the task stored as `name` from the single `VTODO` in
+/// `body`. Properties the task has no field for are dropped.
+async fn put_todo(
+    app_state: &AppState,
+    user: user::Model,
+    project: project::Model,
+    name: String,
+    existing: Option<Todo>,
+    body: &str,
+) -> Result<Response, AppError> {
+    if project.is_archived {
+        return Err(AppError::BadRequest(
+            "Tasks cannot be added to an archived project.".to_string(),
+        ));
+    }
+
+    let calendars = ical::parse(body).map_err(AppError::BadRequest)?;
+    let mut vtodos = Vec::new();
+
+    for calendar in &calendars {
+        calendar.find_all("VTODO", &mut vtodos);
+    }
+
+    let [vtodo] = vtodos[..] else {
+        return Err(AppError::BadRequest(
+            "A calendar object must contain exactly one VTODO.".to_string(),
+        ));
+    };
+
+    let uid = vtodo
+        .property("UID")
+        .map(|uid| ical::unescape(&uid.value))
+        .ok_or(AppError::BadRequest("The VTODO has no UID.".to_string()))?;
+
+    let task_request = todo_row(vtodo)
+        .map_err(|message| vec![(None, message)])
+        .and_then(|row| row_request(&user, &HashMap::new(), row))
+        .map_err(|errors| {
+            AppError::BadRequest(
+                errors
+                    .into_iter()
+                    .map(|(field, message)| match field {
+                        Some(field) => format!("{}: {}", field, message),
+                        None => message,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })?;
+
+    if let Some(existing) = existing {
+        let task = existing.task;
+        let mut active_task: task::ActiveModel = task.clone().into();
+
+        change_status(&app_state.db, &task, &mut active_task, task_request.status).await?;
+
+        active_task.title = Set(task_request.title);
+        active_task.description = Set(task_request.description);
+        active_task.due_date = Set(task_request.due_date);
+        active_task.priority = Set(task_request.priority);
+        active_task.labels = Set(join_labels(&task_request.labels));
+        active_task.recurrence = Set(normalize_recurrence(task_request.recurrence));
+
+        save_task(app_state, user, task, active_task).await?;
+
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
+
+    if !is_resource_name(&name) {
+        return Err(AppError::BadRequest(
+            "Resource names must end with .ics and contain only letters, digits, '-', '_', '.' or '~'."
+                .to_string(),
+        ));
+    }
+
+    let mut task = task_request.into_active_model();
+    task.project_id = Set(Some(project.id));
+
+    let tasks = vec![(task, Vec::new())];
+    ensure_room_for(&app_state.db, tasks.iter().map(|(task, _)| task)).await?;
+
+    let project_id = project.id;
+
+    let created = app_state
+        .db
+        .transaction::<_, _, DbErr>(|txn| {
+            Box::pin(async move {
+                let created = create_tasks(txn, tasks).await?;
+
+                // The resources of the tasks deleted or moved away give up
+                // their name, which a client may use again.
+                caldav_resource::Entity::delete_many()
+                    .filter(caldav_resource::Column::ProjectId.eq(project_id))
+                    .filter(caldav_resource::Column::Name.eq(&name))
+                    .filter(
+                        caldav_resource::Column::TaskId.not_in_subquery(
+                            Query::select()
+                                .column(task::Column::Id)
+                                .from(task::Entity)
+                                .and_where(task::Column::ProjectId.eq(project_id))
+                                .to_owned(),
+                        ),
+                    )
+                    .exec(txn)
+                    .await?;
+
+                caldav_resource::ActiveModel {
+                    id: NotSet,
+                    task_id: Set(created[0].0.id),
+                    project_id: Set(Some(project_id)),
+                    name: Set(name),
+                    uid: Set(uid),
+                }
+                .insert(txn)
+                .await?;
+
+                Ok(created)
+            })
+        })
+        .await;
+
+    let created = match created {
+        Ok(created) => created,
+        // Another request stored a task under this name meanwhile.
+        Err(TransactionError::Transaction(err))
+            if matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+        {
+            return Ok(StatusCode::CONFLICT.into_response());
+        }
+        Err(e) => return Err(AppError::GenericError(e.to_string())), // should be database error
+    };
+
+    announce_created(app_state, created);
+
+    Ok(StatusCode::CREATED.into_response())
+}
+
+#[axum::debug_handler]
+pub async fn calendar_object(
+    State(app_state): State<Arc<AppState>>,
+    Path((project_id, name)): Path<(i32, String)>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, AppError> {
+    if method == Method::OPTIONS {
+        return Ok(options());
+    }
+
+    let Some(user) = dav_user(&app_state, &headers).await? else {
+        return Ok(challenge());
+    };
+
+    let permission = match method.as_str() {
+        "GET" | "HEAD" | "PROPFIND" => ProjectPermission::View,
+        _ => ProjectPermission::ChangeTasks,
+    };
+
+    let project = find_project(&app_state.db, &user, project_id, permission).await?;
+    let todo = find_todo(&app_state.db, project.id, &name).await?;
+
+    if !preconditions_hold(&headers, todo.as_ref().map(|todo| todo.etag.as_str())) {
+        return Ok(StatusCode::PRECONDITION_FAILED.into_response());
+    }
+
+    match method.as_str() {
+        "GET" | "HEAD" => Ok(match todo {
+            Some(todo) => (
+                [
+                    (header::CONTENT_TYPE, TODO_CONTENT_TYPE.to_string()),
+                    (header::ETAG, todo.etag),
+                ],
+                todo.data,
+            )
+                .into_response(),
+            None => StatusCode::NOT_FOUND.into_response(),
+        }),
+        "PROPFIND" => Ok(match todo {
+            Some(todo) => {
+                let props = propfind_props(&body)?;
+                multistatus(&[todo_response(project.id, &todo, &props)], None)
+            }
+            None => StatusCode::NOT_FOUND.into_response(),
+        }),
+        "PUT" => put_todo(&app_state, user, project, name, todo, &body).await,
+        "DELETE" => match todo {
+            Some(todo) => {
+                remove_task(&app_state, todo.task).await?;
+                Ok(StatusCode::NO_CONTENT.into_response())
+            }
+            None => Ok(StatusCode::NOT_FOUND.into_response()),
+        },
+        _ => Ok(method_not_allowed()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{app_state, create_project, create_user};
+
+    const TODO: &str = "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nUID:standup@client\r\nSUMMARY:Stand-up\r\nEND:VTODO\r\nEND:VCALENDAR\r\n";
+
+    #[tokio::test]
+    async fn resource_name_is_stored_once_per_calendar() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let project = create_project(&app_state, &alice, "ops", &[]).await;
+        let other = create_project(&app_state, &alice, "web", &[]).await;
+
+        let put = |project: &project::Model| {
+            put_todo(
+                &app_state,
+                alice.clone(),
+                project.clone(),
+                "standup.ics".to_string(),
+                None,
+                TODO,
+            )
+        };
+
+        assert_eq!(put(&project).await.unwrap().status(), StatusCode::CREATED);
+
+        // As a second request that missed the first one would.
+        assert_eq!(put(&project).await.unwrap().status(), StatusCode::CONFLICT);
+
+        // Other calendars have names of their own.
+        assert_eq!(put(&other).await.unwrap().status(), StatusCode::CREATED);
+
+        let tasks = task::Entity::find().all(&app_state.db).await.unwrap();
+        assert_eq!(tasks.len(), 2);
+
+        // The name of a deleted task can be used again.
+        let todo = find_todo(&app_state.db, project.id, "standup.ics")
+            .await
+            .unwrap()
+            .unwrap();
+        remove_task(&app_state, todo.task).await.unwrap();
+
+        assert_eq!(put(&project).await.unwrap().status(), StatusCode::CREATED);
+        assert!(find_todo(&app_state.db, project.id, "standup.ics")
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
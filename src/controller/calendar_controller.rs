@@ -0,0 +1,526 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Router,
+};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use sea_orm::{ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, EntityTrait, QueryFilter, Set};
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    controller::{
+        task_controller::task_list_query,
+        task_transfer_controller::{import_rows, ImportRow},
+    },
+    error::AppError,
+    ical,
+    models::{
+        _entities::{calendar_feed, task, user},
+        caldav_resource::default_resource,
+        calendar_feed::new_token,
+        task::{labels_of, COMPLETED},
+    },
+    recurrence::Recurrence,
+    serializer::{CalendarFeedSerializer, ImportReportSerializer},
+    AppState,
+};
+
+/// Routes for managing the feed and importing, behind the auth guard.
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/calendar/feed",
+            get(get_feed).post(reset_feed).delete(revoke_feed),
+        )
+        .route("/calendar/import", post(import_calendar))
+}
+
+/// The feed itself. Calendar clients cannot send bearer tokens, so it is
+/// authenticated by the feed token in its URL instead.
+pub async fn get_feed_route() -> Router<Arc<AppState>> {
+    Router::new().route("/calendar/tasks.ics", get(tasks_calendar))
+}
+
+/// Identifies this app in the calendars it writes.
+pub const PRODID: &str = "-//task_app//Tasks//EN";
+
+pub fn ical_date_time(date_time: NaiveDateTime) -> String {
+    date_time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn todo_status(status: &str) -> &'static str {
+    match status {
+        "in_progress" => "IN-PROCESS",
+        COMPLETED => "COMPLETED",
+        _ => "NEEDS-ACTION",
+    }
+}
+
+/// Task status of a `VTODO` status. Unknown ones are returned as they are, to
+/// be rejected when the task is validated.
+fn task_status(status: &str) -> &str {
+    match status {
+        "NEEDS-ACTION" => "pending",
+        "IN-PROCESS" => "in_progress",
+        "COMPLETED" => COMPLETED,
+        status => status,
+    }
+}
+
+/// iCalendar priorities go from 1, the highest, to 9; 0 means undefined.
+fn todo_priority(priority: &str) -> u8 {
+    match priority {
+        "urgent" => 1,
+        "high" => 3,
+        "medium" => 5,
+        _ => 9,
+    }
+}
+
+fn task_priority(priority: &str) -> Option<String> {
+    let priority = match priority.trim().parse::<u8>() {
+        Ok(0) => return None,
+        Ok(1..=2) => "urgent",
+        Ok(3..=4) => "high",
+        Ok(5) => "medium",
+        Ok(6..=9) => "low",
+        _ => priority,
+    };
+
+    Some(priority.to_string())
+}
+
+/// Due date of a `DUE` value, which may be a date or a date and time.
+fn task_due_date(due: &str) -> String {
+    due.get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .map_or(due.to_string(), |date| date.to_string())
+}
+
+/// Writes `task` as a `VTODO` identified by `uid`.
+pub fn write_todo(out: &mut String, task: &task::Model, uid: &str, stamp: &str) {
+    ical::write_line(out, "BEGIN", "VTODO");
+    ical::write_line(out, "UID", &ical::escape(uid));
+    ical::write_line(out, "DTSTAMP", stamp);
+    ical::write_line(out, "CREATED", &ical_date_time(task.date_created));
+
+    ical::write_line(out, "SUMMARY", &ical::escape(&task.title));
+
+    if !task.description.is_empty() {
+        ical::write_line(out, "DESCRIPTION", &ical::escape(&task.description));
+    }
+
+    ical::write_line(out, "STATUS", todo_status(&task.status));
+
+    if let Some(due_date) = task.due_date {
+        ical::write_line(
+            out,
+            "DUE;VALUE=DATE",
+            &due_date.format("%Y%m%d").to_string(),
+        );
+    }
+
+    if let Some(priority) = &task.priority {
+        ical::write_line(out, "PRIORITY", &todo_priority(priority).to_string());
+    }
+
+    let labels = labels_of(task);
+
+    if !labels.is_empty() {
+        let categories: Vec<String> = labels.iter().map(|label| ical::escape(label)).collect();
+        ical::write_line(out, "CATEGORIES", &categories.join(","));
+    }
+
+    if let Some(recurrence) = &task.recurrence {
+        ical::write_line(out, "RRULE", recurrence);
+    }
+
+    ical::write_line(out, "END", "VTODO");
+}
+
+/// Tasks of the feed owner with a due date as `VTODO` items. The task list
+/// parameters, such as `project_id` or `view`, narrow the feed down.
+#[utoipa::path(
+    get,
+    path = "/api/calendar/tasks.ics",
+    tag = "calendar",
+    params(
+        ("token" = String, Query, description = "Token of the feed"),
+    ),
+    responses((status = 200, description = "The tasks as an iCalendar feed", content_type = "text/calendar", body = String)),
+    security(()),
+)]
+#[axum::debug_handler]
+pub async fn tasks_calendar(
+    State(app_state): State<Arc<AppState>>,
+    Query(mut params): Query<HashMap<String, String>>,
+) -> Result<Response, AppError> {
+    let token = params
+        .remove("token")
+        .ok_or(AppError::Unauthorized("No feed token given".to_string()))?;
+
+    let feed = calendar_feed::Entity::find()
+        .filter(calendar_feed::Column::Token.eq(token))
+        .one(&app_state.db)
+        .await?
+        .ok_or(AppError::Unauthorized("Unknown feed token".to_string()))?;
+
+    let user = user::Entity::find_by_id(feed.user_id)
+        .one(&app_state.db)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    let (task_query, _) = task_list_query(&app_state, &user, &mut params).await?;
+    let tasks = task_query
+        .filter(task::Column::DueDate.is_not_null())
+        .all(&app_state.db)
+        .await?;
+
+    let stamp = ical_date_time(Utc::now().naive_utc());
+    let mut calendar = String::new();
+
+    ical::write_line(&mut calendar, "BEGIN", "VCALENDAR");
+    ical::write_line(&mut calendar, "VERSION", "2.0");
+    ical::write_line(&mut calendar, "PRODID", PRODID);
+    ical::write_line(&mut calendar, "X-WR-CALNAME", "Tasks");
+
+    for task in &tasks {
+        let (_, uid) = default_resource(task.id);
+        write_todo(&mut calendar, task, &uid, &stamp);
+    }
+
+    ical::write_line(&mut calendar, "END", "VCALENDAR");
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "inline; filename=\"tasks.ics\"",
+            ),
+        ],
+        calendar,
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/calendar/feed",
+    tag = "calendar",
+    responses((status = 200, body = DataResponse<CalendarFeedSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn get_feed(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let feed = calendar_feed::Entity::find()
+        .filter(calendar_feed::Column::UserId.eq(user.id))
+        .one(&app_state.db)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    Ok(JsonResponse::data(CalendarFeedSerializer::from(feed), None))
+}
+
+/// Creates the feed of the user, or gives it a new token so the old URL stops
+/// working.
+#[utoipa::path(
+    post,
+    path = "/api/calendar/feed",
+    tag = "calendar",
+    responses((status = 200, body = DataResponse<CalendarFeedSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn reset_feed(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let feed = calendar_feed::Entity::find()
+        .filter(calendar_feed::Column::UserId.eq(user.id))
+        .one(&app_state.db)
+        .await?;
+
+    let mut feed: calendar_feed::ActiveModel = match feed {
+        Some(feed) => feed.into(),
+        None => calendar_feed::ActiveModel {
+            id: NotSet,
+            user_id: Set(user.id),
+            ..Default::default()
+        },
+    };
+
+    feed.token = Set(new_token());
+    feed.date_created = Set(Utc::now().naive_utc());
+
+    let feed: calendar_feed::Model = feed.save(&app_state.db).await?.try_into()?;
+
+    Ok(JsonResponse::data(CalendarFeedSerializer::from(feed), None))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/calendar/feed",
+    tag = "calendar",
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+#[axum::debug_handler]
+pub async fn revoke_feed(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    calendar_feed::Entity::delete_many()
+        .filter(calendar_feed::Column::UserId.eq(user.id))
+        .exec(&app_state.db)
+        .await?;
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Calendar feed revoked successfully".to_string()),
+    ))
+}
+
+/// Import row of a `VTODO`, with its properties turned into task fields.
+pub fn todo_row(todo: &ical::Component) -> ImportRow {
+    let mut row = Vec::new();
+
+    for property in &todo.properties {
+        let (field, value) = match property.name.as_str() {
+            "SUMMARY" => ("title", ical::unescape(&property.value)),
+            "DESCRIPTION" => ("description", ical::unescape(&property.value)),
+            "STATUS" => ("status", task_status(&property.value).to_string()),
+            "DUE" => ("due_date", task_due_date(&property.value)),
+            "PRIORITY" => match task_priority(&property.value) {
+                Some(priority) => ("priority", priority),
+                None => continue,
+            },
+            "CATEGORIES" => ("labels", ical::split_list(&property.value).join(",")),
+            "RRULE" => match Recurrence::parse(&property.value) {
+                Some(recurrence) => ("recurrence", recurrence.to_string()),
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        row.push((field.to_string(), Some(value)));
+    }
+
+    Ok(row)
+}
+
+/// Imports the `VTODO` items of an `.ics` file sent as the request body,
+/// with the same checks as the task import. Takes `project_id` and `dry_run`
+/// as query parameters.
+#[utoipa::path(
+    post,
+    path = "/api/calendar/import",
+    tag = "calendar",
+    params(
+        ("project_id" = Option<i32>, Query, description = "Project of the imported tasks, the Inbox by default"),
+        ("dry_run" = Option<bool>, Query, description = "Only validate the items"),
+    ),
+    request_body(content = String, description = "An iCalendar file", content_type = "text/calendar"),
+    responses((status = 200, body = DataResponse<ImportReportSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn import_calendar(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(params): Query<HashMap<String, String>>,
+    body: String,
+) -> Result<impl IntoResponse, AppError> {
+    let project_id = params
+        .get("project_id")
+        .map(|project_id| {
+            project_id
+                .parse::<i32>()
+                .map_err(|_| AppError::BadRequest("project_id must be a number.".to_string()))
+        })
+        .transpose()?;
+    let dry_run = params
+        .get("dry_run")
+        .is_some_and(|dry_run| dry_run == "true");
+
+    let calendars = ical::parse(&body).map_err(AppError::BadRequest)?;
+
+    let mut todos = Vec::new();
+
+    for calendar in &calendars {
+        calendar.find_all("VTODO", &mut todos);
+    }
+
+    let rows = todos.into_iter().map(todo_row).collect();
+
+    let (report, message) = import_rows(
+        &app_state,
+        &user,
+        rows,
+        &HashMap::new(),
+        project_id,
+        dry_run,
+    )
+    .await?;
+
+    Ok(JsonResponse::data(report, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+
+    use super::*;
+    use crate::testing::{app_state, create_project, create_task, create_user, response_data};
+
+    fn todo_of(text: &str) -> ical::Component {
+        let mut todos = Vec::new();
+        let calendars = ical::parse(text).unwrap();
+        calendars[0].find_all("VTODO", &mut todos);
+
+        todos[0].clone()
+    }
+
+    #[test]
+    fn recurrence_round_trips_as_rrule() {
+        let task = task::Model {
+            id: 1,
+            title: "Water the plants".to_string(),
+            description: String::new(),
+            status: "pending".to_string(),
+            date_created: NaiveDateTime::default(),
+            date_updated: None,
+            user_id: 1,
+            project_id: None,
+            rank: "n".to_string(),
+            key: None,
+            auto_complete: false,
+            estimate_minutes: None,
+            due_date: None,
+            priority: None,
+            labels: String::new(),
+            recurrence: Some("FREQ=WEEKLY;INTERVAL=2".to_string()),
+        };
+
+        let mut text = String::from("BEGIN:VCALENDAR\r\n");
+        write_todo(&mut text, &task, "task-1", "20250101T000000Z");
+        text.push_str("END:VCALENDAR\r\n");
+
+        assert!(text.contains("RRULE:FREQ=WEEKLY;INTERVAL=2\r\n"));
+
+        let row = todo_row(&todo_of(&text)).unwrap();
+        assert!(row.contains(&(
+            "recurrence".to_string(),
+            Some("FREQ=WEEKLY;INTERVAL=2".to_string())
+        )));
+
+        let row = todo_row(&todo_of(
+            "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nSUMMARY:Stand-up\r\nRRULE:freq=daily;interval=1\r\nEND:VTODO\r\nEND:VCALENDAR\r\n",
+        ))
+        .unwrap();
+        assert!(row.contains(&("recurrence".to_string(), Some("FREQ=DAILY".to_string()))));
+
+        // Rules that cannot be stored are left out.
+        let row = todo_row(&todo_of(
+            "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nSUMMARY:Stand-up\r\nRRULE:FREQ=WEEKLY;BYDAY=MO\r\nEND:VTODO\r\nEND:VCALENDAR\r\n",
+        ))
+        .unwrap();
+        assert!(row.iter().all(|(field, _)| field != "recurrence"));
+    }
+
+    /// The token of a new feed of `user`.
+    async fn feed_token(app_state: &Arc<AppState>, user: &user::Model) -> String {
+        let feed = response_data(
+            reset_feed(State(app_state.clone()), Extension(user.clone()))
+                .await
+                .unwrap(),
+        )
+        .await;
+
+        feed["path"]
+            .as_str()
+            .unwrap()
+            .split_once("token=")
+            .unwrap()
+            .1
+            .to_string()
+    }
+
+    async fn feed(app_state: &Arc<AppState>, token: Option<&str>) -> Result<String, AppError> {
+        let params = token
+            .map(|token| HashMap::from([("token".to_string(), token.to_string())]))
+            .unwrap_or_default();
+        let response = tasks_calendar(State(app_state.clone()), Query(params)).await?;
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        Ok(String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    async fn set_due_date(app_state: &AppState, task: task::Model) {
+        let mut active_task: task::ActiveModel = task.into();
+        active_task.due_date = Set(NaiveDate::from_ymd_opt(2025, 3, 1));
+        active_task.update(&app_state.db).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn feed_rejects_wrong_and_revoked_tokens() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+
+        let first = feed_token(&app_state, &alice).await;
+        assert!(feed(&app_state, Some(&first)).await.is_ok());
+
+        let missing = feed(&app_state, None).await;
+        assert!(matches!(missing, Err(AppError::Unauthorized(_))));
+
+        let wrong = feed(&app_state, Some("not-a-token")).await;
+        assert!(matches!(wrong, Err(AppError::Unauthorized(_))));
+
+        // A new token replaces the old one.
+        let second = feed_token(&app_state, &alice).await;
+        assert_ne!(first, second);
+        let replaced = feed(&app_state, Some(&first)).await;
+        assert!(matches!(replaced, Err(AppError::Unauthorized(_))));
+        assert!(feed(&app_state, Some(&second)).await.is_ok());
+
+        revoke_feed(State(app_state.clone()), Extension(alice.clone()))
+            .await
+            .unwrap();
+        let revoked = feed(&app_state, Some(&second)).await;
+        assert!(matches!(revoked, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn feed_lists_the_due_tasks_its_owner_can_see() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let ops = create_project(&app_state, &alice, "ops", &[]).await;
+        let secret = create_project(&app_state, &bob, "secret", &[]).await;
+
+        let due = create_task(&app_state, &alice, &ops, "Deploy").await;
+        set_due_date(&app_state, due).await;
+        create_task(&app_state, &alice, &ops, "Someday").await;
+        let hidden = create_task(&app_state, &bob, &secret, "Hidden").await;
+        set_due_date(&app_state, hidden).await;
+
+        let token = feed_token(&app_state, &alice).await;
+        let calendar = feed(&app_state, Some(&token)).await.unwrap();
+
+        let mut todos = Vec::new();
+        let calendars = ical::parse(&calendar).unwrap();
+        calendars[0].find_all("VTODO", &mut todos);
+
+        let summaries: Vec<&str> = todos
+            .iter()
+            .flat_map(|todo| &todo.properties)
+            .filter(|property| property.name == "SUMMARY")
+            .map(|property| property.value.as_str())
+            .collect();
+        assert_eq!(summaries, vec!["Deploy"]);
+        assert!(calendar.contains("DUE;VALUE=DATE:20250301"));
+    }
+}
@@ -0,0 +1,470 @@
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::{get, put},
+    Extension, Json, Router,
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+use validator::Validate;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    controller::{
+        board_controller::ensure_wip_limit,
+        task_controller::{find_task, publish_task_change, save_task},
+    },
+    error::AppError,
+    form::task_form::{
+        CreateChecklistItemRequest, ReorderChecklistRequest, UpdateChecklistItemRequest,
+    },
+    models::{
+        _entities::{task, task_checklist_item, user},
+        project::ProjectPermission,
+        task::{next_rank, TaskRef, COMPLETED},
+        task_checklist_item::{next_position, progress_of},
+    },
+    serializer::ChecklistItemSerializer,
+    AppState,
+};
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/tasks/:task_id/checklist",
+            get(get_checklist).post(create_checklist_item),
+        )
+        .route("/tasks/:task_id/checklist/order", put(reorder_checklist))
+        .route(
+            "/tasks/:task_id/checklist/:item_id",
+            put(update_checklist_item).delete(delete_checklist_item),
+        )
+}
+
+async fn checklist_items(
+    app_state: &AppState,
+    task_id: i32,
+) -> Result<Vec<task_checklist_item::Model>, DbErr> {
+    task_checklist_item::Entity::find()
+        .filter(task_checklist_item::Column::TaskId.eq(task_id))
+        .order_by_asc(task_checklist_item::Column::Position)
+        .order_by_asc(task_checklist_item::Column::Id)
+        .all(&app_state.db)
+        .await
+}
+
+async fn find_item(
+    app_state: &AppState,
+    task_id: i32,
+    item_id: i32,
+) -> Result<task_checklist_item::Model, AppError> {
+    Ok(task_checklist_item::Entity::find_by_id(item_id)
+        .filter(task_checklist_item::Column::TaskId.eq(task_id))
+        .one(&app_state.db)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?)
+}
+
+/// Publishes the new checklist progress of `task`, completing the task first
+/// when it opted in and every item is now done. Unchecking an item never
+/// reopens a completed task.
+async fn checklist_changed(
+    app_state: &AppState,
+    user: user::Model,
+    task: task::Model,
+) -> Result<(), AppError> {
+    let progress = progress_of(&app_state.db, vec![task.id])
+        .await?
+        .remove(&task.id)
+        .unwrap_or_default();
+
+    if task.auto_complete && task.status != COMPLETED && progress.is_complete() {
+        // A full column leaves the task where it is rather than failing the
+        // checklist change.
+        match ensure_wip_limit(&app_state.db, task.project_id, COMPLETED, Some(task.id)).await {
+            Ok(()) => {
+                let mut active_task: task::ActiveModel = task.clone().into();
+
+                active_task.status = Set(COMPLETED.to_string());
+                active_task.rank = Set(next_rank(&app_state.db, task.project_id, COMPLETED).await?);
+
+                save_task(app_state, user, task, active_task).await?;
+
+                return Ok(());
+            }
+            Err(AppError::BadRequest(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    publish_task_change(app_state, task, vec![]).await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/checklist",
+    tag = "checklist",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    responses((status = 200, body = DataResponse<Vec<ChecklistItemSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_checklist(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+) -> Result<impl IntoResponse, AppError> {
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::View).await?;
+
+    let items: Vec<ChecklistItemSerializer> = checklist_items(&app_state, task.id)
+        .await?
+        .into_iter()
+        .map(ChecklistItemSerializer::from)
+        .collect();
+
+    Ok(JsonResponse::data(items, None))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/checklist",
+    tag = "checklist",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    request_body = CreateChecklistItemRequest,
+    responses((status = 200, body = DataResponse<ChecklistItemSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn create_checklist_item(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+    Json(item_request): Json<CreateChecklistItemRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    item_request.validate()?;
+
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    let item = task_checklist_item::ActiveModel {
+        id: NotSet,
+        task_id: Set(task.id),
+        text: Set(item_request.text),
+        is_done: Set(item_request.is_done),
+        position: Set(next_position(&app_state.db, task.id).await?),
+        date_created: NotSet,
+    }
+    .insert(&app_state.db)
+    .await?;
+
+    checklist_changed(&app_state, user, task).await?;
+
+    Ok(JsonResponse::data(
+        ChecklistItemSerializer::from(item),
+        None,
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/checklist/{item_id}",
+    tag = "checklist",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+        ("item_id" = i32, Path, description = "Id of the checklist item"),
+    ),
+    request_body = UpdateChecklistItemRequest,
+    responses((status = 200, body = DataResponse<ChecklistItemSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn update_checklist_item(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path((task_ref, item_id)): Path<(TaskRef, i32)>,
+    Json(item_request): Json<UpdateChecklistItemRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    item_request.validate()?;
+
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    let mut item: task_checklist_item::ActiveModel =
+        find_item(&app_state, task.id, item_id).await?.into();
+
+    if let Some(text) = item_request.text {
+        item.text = Set(text);
+    }
+
+    if let Some(is_done) = item_request.is_done {
+        item.is_done = Set(is_done);
+    }
+
+    let item = item.update(&app_state.db).await?;
+
+    checklist_changed(&app_state, user, task).await?;
+
+    Ok(JsonResponse::data(
+        ChecklistItemSerializer::from(item),
+        None,
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/checklist/{item_id}",
+    tag = "checklist",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+        ("item_id" = i32, Path, description = "Id of the checklist item"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+#[axum::debug_handler]
+pub async fn delete_checklist_item(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path((task_ref, item_id)): Path<(TaskRef, i32)>,
+) -> Result<impl IntoResponse, AppError> {
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    let item = find_item(&app_state, task.id, item_id).await?;
+
+    task_checklist_item::Entity::delete_by_id(item.id)
+        .exec(&app_state.db)
+        .await?;
+
+    checklist_changed(&app_state, user, task).await?;
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Checklist item deleted successfully".to_string()),
+    ))
+}
+
+/// Reorders the checklist of a task. `item_ids` must list every item of the
+/// checklist exactly once.
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/checklist/order",
+    tag = "checklist",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    request_body = ReorderChecklistRequest,
+    responses((status = 200, body = DataResponse<Vec<ChecklistItemSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn reorder_checklist(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+    Json(reorder_request): Json<ReorderChecklistRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    reorder_request.validate()?;
+
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    let current: HashSet<i32> = checklist_items(&app_state, task.id)
+        .await?
+        .into_iter()
+        .map(|item| item.id)
+        .collect();
+
+    let requested: HashSet<i32> = reorder_request.item_ids.iter().copied().collect();
+
+    if requested != current || requested.len() != reorder_request.item_ids.len() {
+        return Err(AppError::BadRequest(
+            "item_ids must list every checklist item of the task exactly once.".to_string(),
+        ));
+    }
+
+    let item_ids = reorder_request.item_ids;
+
+    app_state
+        .db
+        .transaction::<_, (), DbErr>(|txn| {
+            Box::pin(async move {
+                for (position, item_id) in item_ids.into_iter().enumerate() {
+                    task_checklist_item::Entity::update_many()
+                        .col_expr(
+                            task_checklist_item::Column::Position,
+                            (position as i32 + 1).into(),
+                        )
+                        .filter(task_checklist_item::Column::Id.eq(item_id))
+                        .exec(txn)
+                        .await?;
+                }
+
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| AppError::GenericError(e.to_string()))?; // should be database error
+
+    let items: Vec<ChecklistItemSerializer> = checklist_items(&app_state, task.id)
+        .await?
+        .into_iter()
+        .map(ChecklistItemSerializer::from)
+        .collect();
+
+    Ok(JsonResponse::data(items, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::_entities::{board_column, project},
+        testing::{app_state, create_project, create_task, create_user, response_data},
+    };
+
+    /// A task of `alice` in a new project, completing itself with its checklist.
+    async fn auto_completing_task(
+        app_state: &AppState,
+        alice: &user::Model,
+    ) -> (project::Model, task::Model) {
+        let project = create_project(app_state, alice, "ops", &[]).await;
+        let task = create_task(app_state, alice, &project, "Deploy").await;
+
+        let mut active_task: task::ActiveModel = task.into();
+        active_task.auto_complete = Set(true);
+        let task = active_task.update(&app_state.db).await.unwrap();
+
+        (project, task)
+    }
+
+    async fn add_item(
+        app_state: &Arc<AppState>,
+        user: &user::Model,
+        task: &task::Model,
+        text: &str,
+    ) -> i32 {
+        let item = response_data(
+            create_checklist_item(
+                State(app_state.clone()),
+                Extension(user.clone()),
+                Path(TaskRef::Id(task.id)),
+                Json(CreateChecklistItemRequest {
+                    text: text.to_string(),
+                    is_done: false,
+                }),
+            )
+            .await
+            .unwrap(),
+        )
+        .await;
+
+        item["id"].as_i64().unwrap() as i32
+    }
+
+    async fn set_done(
+        app_state: &Arc<AppState>,
+        user: &user::Model,
+        task: &task::Model,
+        item_id: i32,
+        is_done: bool,
+    ) {
+        update_checklist_item(
+            State(app_state.clone()),
+            Extension(user.clone()),
+            Path((TaskRef::Id(task.id), item_id)),
+            Json(UpdateChecklistItemRequest {
+                text: None,
+                is_done: Some(is_done),
+            }),
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn status_of(app_state: &AppState, task: &task::Model) -> String {
+        task::Entity::find_by_id(task.id)
+            .one(&app_state.db)
+            .await
+            .unwrap()
+            .unwrap()
+            .status
+    }
+
+    #[tokio::test]
+    async fn task_completes_when_every_item_is_done() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let (_, task) = auto_completing_task(&app_state, &alice).await;
+
+        let first = add_item(&app_state, &alice, &task, "Build").await;
+        let second = add_item(&app_state, &alice, &task, "Release").await;
+
+        set_done(&app_state, &alice, &task, first, true).await;
+        assert_eq!(status_of(&app_state, &task).await, "pending");
+
+        set_done(&app_state, &alice, &task, second, true).await;
+        assert_eq!(status_of(&app_state, &task).await, COMPLETED);
+    }
+
+    #[tokio::test]
+    async fn unchecking_an_item_keeps_the_task_completed() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let (_, task) = auto_completing_task(&app_state, &alice).await;
+
+        let item = add_item(&app_state, &alice, &task, "Build").await;
+        set_done(&app_state, &alice, &task, item, true).await;
+        assert_eq!(status_of(&app_state, &task).await, COMPLETED);
+
+        set_done(&app_state, &alice, &task, item, false).await;
+        assert_eq!(status_of(&app_state, &task).await, COMPLETED);
+
+        let progress = progress_of(&app_state.db, vec![task.id])
+            .await
+            .unwrap()
+            .remove(&task.id)
+            .unwrap();
+        assert!(!progress.is_complete());
+
+        // A task that does not opt in stays pending.
+        let mut active_task: task::ActiveModel = task.clone().into();
+        active_task.status = Set("pending".to_string());
+        active_task.auto_complete = Set(false);
+        active_task.update(&app_state.db).await.unwrap();
+
+        set_done(&app_state, &alice, &task, item, true).await;
+        assert_eq!(status_of(&app_state, &task).await, "pending");
+    }
+
+    #[tokio::test]
+    async fn full_completed_column_leaves_the_task_open() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let (project, task) = auto_completing_task(&app_state, &alice).await;
+
+        let done = create_task(&app_state, &alice, &project, "Plan").await;
+        let mut active_task: task::ActiveModel = done.into();
+        active_task.status = Set(COMPLETED.to_string());
+        active_task.update(&app_state.db).await.unwrap();
+
+        board_column::ActiveModel {
+            project_id: Set(project.id),
+            status: Set(COMPLETED.to_string()),
+            wip_limit: Set(1),
+            ..Default::default()
+        }
+        .insert(&app_state.db)
+        .await
+        .unwrap();
+
+        let item = add_item(&app_state, &alice, &task, "Build").await;
+        set_done(&app_state, &alice, &task, item, true).await;
+
+        assert_eq!(status_of(&app_state, &task).await, "pending");
+
+        let item = find_item(&app_state, task.id, item).await.unwrap();
+        assert!(item.is_done);
+    }
+}
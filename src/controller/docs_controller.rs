@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use axum::{
+    response::{Html, IntoResponse},
+    routing::get,
+    Json, Router,
+};
+use utoipa::OpenApi;
+
+use crate::{openapi::ApiDoc, AppState};
+
+/// Browses the OpenAPI document with Redoc.
+const DOCS_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <title>Task App API</title>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+  </head>
+  <body>
+    <redoc spec-url="/api/openapi.json"></redoc>
+    <script src="https://cdn.redoc.ly/redoc/latest/bundles/redoc.standalone.js"></script>
+  </body>
+</html>
+"#;
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/openapi.json", get(get_openapi))
+        .route("/docs", get(get_docs))
+}
+
+/// The OpenAPI 3.1 document of the API.
+#[utoipa::path(
+    get,
+    path = "/api/openapi.json",
+    tag = "docs",
+    responses((status = 200, description = "The OpenAPI document", content_type = "application/json", body = Object)),
+    security(()),
+)]
+pub async fn get_openapi() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
+
+/// Interactive documentation of the API.
+#[utoipa::path(
+    get,
+    path = "/api/docs",
+    tag = "docs",
+    responses((status = 200, description = "The documentation page", content_type = "text/html", body = String)),
+    security(()),
+)]
+pub async fn get_docs() -> impl IntoResponse {
+    Html(DOCS_PAGE)
+}
@@ -0,0 +1,76 @@
+use std::{convert::Infallible, sync::Arc, time::Duration};
+
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::get,
+    Extension, Router,
+};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream, StreamExt,
+};
+
+use crate::{events::TaskChange, models::_entities::user, AppState};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/events", get(get_events))
+}
+
+fn change_event(change: TaskChange) -> Event {
+    Event::default()
+        .id(change.id.to_string())
+        .event(change.kind.as_str())
+        .data(change.data.to_string())
+}
+
+/// Tells the client that changes were missed and its task list must be reloaded.
+fn resync_event() -> Event {
+    Event::default().event("resync").data("{}")
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "events",
+    params(
+        ("Last-Event-ID" = Option<u64>, Header, description = "Id of the last event received, to replay the ones missed"),
+    ),
+    responses((status = 200, description = "Server-sent events of the task changes the user can see", content_type = "text/event-stream", body = String)),
+)]
+#[axum::debug_handler]
+pub async fn get_events(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_event_id = headers
+        .get("Last-Event-ID")
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.trim().parse::<u64>().ok());
+
+    let subscription = app_state.events.subscribe(last_event_id);
+
+    let resync = tokio_stream::iter(subscription.missed.then(resync_event));
+
+    let replay_user = user.clone();
+    let replay = tokio_stream::iter(subscription.replay)
+        .filter(move |change| change.is_visible_to(&replay_user))
+        .map(change_event);
+
+    let live = BroadcastStream::new(subscription.receiver).filter_map(move |change| match change {
+        Ok(change) => change.is_visible_to(&user).then(|| change_event(change)),
+        Err(BroadcastStreamRecvError::Lagged(_)) => Some(resync_event()),
+    });
+
+    let stream = resync.chain(replay).chain(live).map(Ok);
+
+    Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(HEARTBEAT_INTERVAL)
+            .text("heartbeat"),
+    )
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use async_graphql::http::GraphiQLSource;
+use axum::{
+    extract::State,
+    response::{Html, IntoResponse},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+
+use crate::{error::AppError, graphql, models::_entities::user, AppState};
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/graphql", post(execute_graphql))
+}
+
+/// The GraphiQL page sends the token itself, so it is served without one.
+pub async fn get_playground_route() -> Router<Arc<AppState>> {
+    Router::new().route("/graphiql", get(get_graphiql))
+}
+
+/// Runs a GraphQL query or mutation. Errors are reported in the `errors` of
+/// the GraphQL response, with the HTTP status REST would use in their
+/// `status` extension.
+#[utoipa::path(
+    post,
+    path = "/api/graphql",
+    tag = "graphql",
+    request_body(content = Object, description = "A GraphQL request with `query`, and optionally `variables` and `operationName`"),
+    responses((status = 200, description = "A GraphQL response with `data` and `errors`", body = Object)),
+)]
+pub async fn execute_graphql(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Json(request): Json<async_graphql::Request>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = graphql::execute(app_state, user, request).await?;
+
+    Ok(Json(response))
+}
+
+/// GraphiQL, to explore the GraphQL schema and run queries.
+#[utoipa::path(
+    get,
+    path = "/api/graphiql",
+    tag = "graphql",
+    responses((status = 200, description = "The GraphiQL page", content_type = "text/html", body = String)),
+    security(()),
+)]
+pub async fn get_graphiql() -> impl IntoResponse {
+    Html(GraphiQLSource::build().endpoint("/api/graphql").finish())
+}
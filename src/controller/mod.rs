@@ -1,6 +1,25 @@
 pub mod auth_controller;
+pub mod board_controller;
+pub mod caldav_controller;
+pub mod calendar_controller;
+pub mod checklist_controller;
+pub mod docs_controller;
+pub mod event_controller;
+pub mod graphql_controller;
+pub mod notification_controller;
 pub mod permission_controller;
+pub mod project_controller;
+pub mod quick_add_controller;
+pub mod report_controller;
 pub mod role_controller;
+pub mod saved_view_controller;
+pub mod stats_controller;
+pub mod sync_controller;
 pub mod task_controller;
+pub mod task_transfer_controller;
+pub mod template_controller;
+pub mod time_entry_controller;
 pub mod user_controller;
 pub mod user_role_controller;
+pub mod webhook_controller;
+pub mod ws_controller;
@@ -0,0 +1,456 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    response::IntoResponse,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue::NotSet, ColumnTrait, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set,
+};
+use validator::Validate;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse, PaginatedResponse, ResponseMetadata},
+    error::AppError,
+    form::notification_form::UpdateNotificationPreferencesRequest,
+    models::{
+        _entities::{notification, notification_preference, user},
+        notification::NotificationActor,
+    },
+    notification::NotificationKind,
+    serializer::{NotificationPreferenceSerializer, NotificationSerializer},
+    AppState,
+};
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/notifications", get(get_notifications))
+        .route("/notifications/read_all", post(mark_all_read))
+        .route(
+            "/notifications/preferences",
+            get(get_preferences).put(update_preferences),
+        )
+        .route("/notifications/:notification_id/read", post(mark_read))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications",
+    tag = "notifications",
+    params(
+        ("unread" = Option<bool>, Query, description = "Only unread notifications"),
+        ("page" = Option<u64>, Query, description = "Page number, from 1"),
+    ),
+    responses((status = 200, body = PaginatedResponse<Vec<NotificationSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_notifications(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(params): Query<HashMap<String, String>>,
+    OriginalUri(original_uri): OriginalUri,
+) -> Result<impl IntoResponse, AppError> {
+    let mut notification_query =
+        notification::Entity::find().filter(notification::Column::UserId.eq(user.id));
+
+    if params.get("unread").is_some_and(|unread| unread == "true") {
+        notification_query = notification_query.filter(notification::Column::ReadAt.is_null());
+    }
+
+    let page = params
+        .get("page")
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(1);
+
+    let per_page = std::env::var("PER_PAGE")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(10);
+
+    let notification_count = notification_query.clone().count(&app_state.db).await?;
+
+    let response_metadata = ResponseMetadata {
+        count: notification_count,
+        per_page,
+        total_page: notification_count.div_ceil(per_page),
+        current_url: Some(original_uri.to_string()),
+        ..Default::default()
+    };
+
+    let notifications: Vec<NotificationSerializer> = notification_query
+        .find_also_linked(NotificationActor)
+        .order_by(notification::Column::DateCreated, sea_orm::Order::Desc)
+        .order_by(notification::Column::Id, sea_orm::Order::Desc)
+        .paginate(&app_state.db, per_page)
+        .fetch_page(page - 1)
+        .await?
+        .into_iter()
+        .map(NotificationSerializer::from)
+        .collect();
+
+    Ok(JsonResponse::paginate(
+        notifications,
+        response_metadata,
+        None,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/notifications/{notification_id}/read",
+    tag = "notifications",
+    params(
+        ("notification_id" = i32, Path, description = "Id of the notification"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+#[axum::debug_handler]
+pub async fn mark_read(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(notification_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let res = notification::Entity::update_many()
+        .col_expr(
+            notification::Column::ReadAt,
+            chrono::Utc::now().naive_utc().into(),
+        )
+        .filter(notification::Column::Id.eq(notification_id))
+        .filter(notification::Column::UserId.eq(user.id))
+        .filter(notification::Column::ReadAt.is_null())
+        .exec(&app_state.db)
+        .await?;
+
+    if res.rows_affected == 0 {
+        notification::Entity::find_by_id(notification_id)
+            .filter(notification::Column::UserId.eq(user.id))
+            .one(&app_state.db)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+    }
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Notification marked as read.".to_string()),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/notifications/read_all",
+    tag = "notifications",
+    responses((status = 200, body = DataResponse<u64>)),
+)]
+#[axum::debug_handler]
+pub async fn mark_all_read(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let res = notification::Entity::update_many()
+        .col_expr(
+            notification::Column::ReadAt,
+            chrono::Utc::now().naive_utc().into(),
+        )
+        .filter(notification::Column::UserId.eq(user.id))
+        .filter(notification::Column::ReadAt.is_null())
+        .exec(&app_state.db)
+        .await?;
+
+    Ok(JsonResponse::data(
+        res.rows_affected,
+        Some("All notifications marked as read.".to_string()),
+    ))
+}
+
+async fn preferences_of(
+    app_state: &AppState,
+    user: &user::Model,
+) -> Result<Vec<NotificationPreferenceSerializer>, AppError> {
+    let stored: HashMap<String, bool> = notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.eq(user.id))
+        .all(&app_state.db)
+        .await?
+        .into_iter()
+        .map(|preference| (preference.kind, preference.enabled))
+        .collect();
+
+    Ok(NotificationKind::ALL
+        .iter()
+        .map(|kind| NotificationPreferenceSerializer {
+            kind: kind.as_str().to_string(),
+            enabled: stored.get(kind.as_str()).copied().unwrap_or(true),
+        })
+        .collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications/preferences",
+    tag = "notifications",
+    responses((status = 200, body = DataResponse<Vec<NotificationPreferenceSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_preferences(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let preferences = preferences_of(&app_state, &user).await?;
+
+    Ok(JsonResponse::data(preferences, None))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/notifications/preferences",
+    tag = "notifications",
+    request_body = UpdateNotificationPreferencesRequest,
+    responses((status = 200, body = DataResponse<Vec<NotificationPreferenceSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn update_preferences(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Json(preference_request): Json<UpdateNotificationPreferencesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    preference_request.validate()?;
+
+    let preferences: Vec<notification_preference::ActiveModel> = preference_request
+        .preferences
+        .into_iter()
+        .map(|(kind, enabled)| notification_preference::ActiveModel {
+            id: NotSet,
+            user_id: Set(user.id),
+            kind: Set(kind),
+            enabled: Set(enabled),
+        })
+        .collect();
+
+    if !preferences.is_empty() {
+        notification_preference::Entity::insert_many(preferences)
+            .on_conflict(
+                OnConflict::columns([
+                    notification_preference::Column::UserId,
+                    notification_preference::Column::Kind,
+                ])
+                .update_column(notification_preference::Column::Enabled)
+                .to_owned(),
+            )
+            .exec(&app_state.db)
+            .await?;
+    }
+
+    let preferences = preferences_of(&app_state, &user).await?;
+
+    Ok(JsonResponse::data(
+        preferences,
+        Some("Notification preferences updated.".to_string()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::to_bytes, http::Uri};
+    use sea_orm::ActiveModelTrait;
+
+    use super::*;
+    use crate::testing::{app_state, create_user, response_data};
+
+    async fn notify_user(app_state: &AppState, user: &user::Model, message: &str) -> i32 {
+        notification::ActiveModel {
+            user_id: Set(user.id),
+            kind: Set(NotificationKind::TaskAssigned.as_str().to_string()),
+            message: Set(message.to_string()),
+            date_created: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        }
+        .insert(&app_state.db)
+        .await
+        .unwrap()
+        .id
+    }
+
+    async fn list(
+        app_state: &Arc<AppState>,
+        user: &user::Model,
+        params: &[(&str, &str)],
+    ) -> serde_json::Value {
+        let params = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        let response = get_notifications(
+            State(app_state.clone()),
+            Extension(user.clone()),
+            Query(params),
+            OriginalUri(Uri::from_static("/api/notifications")),
+        )
+        .await
+        .unwrap()
+        .into_response();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn metadata_uses_the_page_size() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+
+        for i in 0..12 {
+            notify_user(&app_state, &alice, &format!("notification {}", i)).await;
+        }
+
+        let response = list(&app_state, &alice, &[]).await;
+        let metadata = &response["_metadata"];
+        let per_page = metadata["per_page"].as_u64().unwrap();
+
+        assert_eq!(metadata["count"], 12);
+        assert_eq!(
+            metadata["total_page"].as_u64().unwrap(),
+            12u64.div_ceil(per_page)
+        );
+        assert_eq!(
+            response["data"].as_array().unwrap().len() as u64,
+            per_page.min(12)
+        );
+    }
+
+    #[tokio::test]
+    async fn read_state_is_kept_per_user() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+
+        let first = notify_user(&app_state, &alice, "first").await;
+        notify_user(&app_state, &alice, "second").await;
+        notify_user(&app_state, &alice, "third").await;
+        let bobs = notify_user(&app_state, &bob, "bob's").await;
+
+        mark_read(
+            State(app_state.clone()),
+            Extension(alice.clone()),
+            Path(first),
+        )
+        .await
+        .unwrap();
+
+        // Marking it again is not an error.
+        mark_read(
+            State(app_state.clone()),
+            Extension(alice.clone()),
+            Path(first),
+        )
+        .await
+        .unwrap();
+
+        let unread = list(&app_state, &alice, &[("unread", "true")]).await;
+        let messages: Vec<&str> = unread["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|notification| notification["message"].as_str().unwrap())
+            .collect();
+        assert_eq!(messages.len(), 2);
+        assert!(!messages.contains(&"first"));
+
+        let all = list(&app_state, &alice, &[]).await;
+        assert_eq!(all["_metadata"]["count"], 3);
+
+        let marked = mark_read(
+            State(app_state.clone()),
+            Extension(alice.clone()),
+            Path(bobs),
+        )
+        .await;
+        assert!(matches!(
+            marked,
+            Err(AppError::DatabaseError(sqlx::Error::RowNotFound))
+        ));
+
+        let count = response_data(
+            mark_all_read(State(app_state.clone()), Extension(alice.clone()))
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(count, 2);
+
+        let unread = list(&app_state, &alice, &[("unread", "true")]).await;
+        assert_eq!(unread["_metadata"]["count"], 0);
+
+        let bobs_unread = list(&app_state, &bob, &[("unread", "true")]).await;
+        assert_eq!(bobs_unread["_metadata"]["count"], 1);
+    }
+
+    #[tokio::test]
+    async fn preferences_default_to_enabled_and_are_upserted() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+
+        let enabled = |preferences: &serde_json::Value, kind: NotificationKind| {
+            preferences
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|preference| preference["kind"] == kind.as_str())
+                .unwrap()["enabled"]
+                .as_bool()
+                .unwrap()
+        };
+
+        let preferences = response_data(
+            get_preferences(State(app_state.clone()), Extension(alice.clone()))
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(
+            preferences.as_array().unwrap().len(),
+            NotificationKind::ALL.len()
+        );
+        assert!(NotificationKind::ALL
+            .into_iter()
+            .all(|kind| enabled(&preferences, kind)));
+
+        for value in [false, true, false] {
+            let request = UpdateNotificationPreferencesRequest {
+                preferences: HashMap::from([(
+                    NotificationKind::TaskAssigned.as_str().to_string(),
+                    value,
+                )]),
+            };
+            let preferences = response_data(
+                update_preferences(
+                    State(app_state.clone()),
+                    Extension(alice.clone()),
+                    Json(request),
+                )
+                .await
+                .unwrap(),
+            )
+            .await;
+
+            assert_eq!(enabled(&preferences, NotificationKind::TaskAssigned), value);
+            assert!(enabled(&preferences, NotificationKind::TaskStatusChanged));
+        }
+
+        let stored = notification_preference::Entity::find()
+            .filter(notification_preference::Column::UserId.eq(alice.id))
+            .count(&app_state.db)
+            .await
+            .unwrap();
+        assert_eq!(stored, 1);
+
+        let request = UpdateNotificationPreferencesRequest {
+            preferences: HashMap::from([("task_deleted".to_string(), false)]),
+        };
+        let updated =
+            update_preferences(State(app_state.clone()), Extension(alice), Json(request)).await;
+        assert!(matches!(updated, Err(AppError::Validation(_))));
+    }
+}
@@ -10,8 +10,12 @@ use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
 use validator::Validate;
 
 use crate::{
-    api_response::JsonResponse, error::AppError, form::permission_form::CreatePermissionRequest,
-    models::_entities::permission, serializer::PermissionSerializer, AppState,
+    api_response::{DataResponse, JsonResponse},
+    error::AppError,
+    form::permission_form::CreatePermissionRequest,
+    models::_entities::permission,
+    serializer::PermissionSerializer,
+    AppState,
 };
 
 pub async fn get_routes() -> Router<Arc<AppState>> {
@@ -25,6 +29,12 @@ pub async fn get_routes() -> Router<Arc<AppState>> {
         )
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/permissions",
+    tag = "permissions",
+    responses((status = 200, body = DataResponse<Vec<PermissionSerializer>>)),
+)]
 #[axum::debug_handler]
 pub async fn get_permissions(
     State(app_state): State<Arc<AppState>>,
@@ -39,6 +49,13 @@ pub async fn get_permissions(
     Ok(JsonResponse::data(permissions, None))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/permissions",
+    tag = "permissions",
+    request_body = CreatePermissionRequest,
+    responses((status = 200, body = DataResponse<PermissionSerializer>)),
+)]
 #[axum::debug_handler]
 pub async fn create_permission(
     State(app_state): State<Arc<AppState>>,
@@ -55,6 +72,15 @@ pub async fn create_permission(
     Ok(JsonResponse::data(permission, None))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/permissions/{permission_id}",
+    tag = "permissions",
+    params(
+        ("permission_id" = i32, Path, description = "Id of the permission"),
+    ),
+    responses((status = 200, body = DataResponse<PermissionSerializer>)),
+)]
 pub async fn get_permission(
     State(app_state): State<Arc<AppState>>,
     Path(permission_id): Path<i32>,
@@ -67,6 +93,16 @@ pub async fn get_permission(
 
     Ok(JsonResponse::data(permission_serializer, None))
 }
+#[utoipa::path(
+    put,
+    path = "/api/permissions/{permission_id}",
+    tag = "permissions",
+    params(
+        ("permission_id" = i32, Path, description = "Id of the permission"),
+    ),
+    request_body = CreatePermissionRequest,
+    responses((status = 200, body = DataResponse<PermissionSerializer>)),
+)]
 pub async fn update_permission(
     State(app_state): State<Arc<AppState>>,
     Path(permission_id): Path<i32>,
@@ -89,6 +125,15 @@ pub async fn update_permission(
 
     Ok(JsonResponse::data(permission_serializer, None))
 }
+#[utoipa::path(
+    delete,
+    path = "/api/permissions/{permission_id}",
+    tag = "permissions",
+    params(
+        ("permission_id" = i32, Path, description = "Id of the permission"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
 pub async fn delete_permission(
     State(app_state): State<Arc<AppState>>,
     Path(permission_id): Path<i32>,
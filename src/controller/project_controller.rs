@@ -0,0 +1,497 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    response::IntoResponse,
+    routing::{get, put},
+    Extension, Json, Router,
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use validator::Validate;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse, PaginatedResponse, ResponseMetadata},
+    api_version::ApiVersion,
+    controller::task_controller::insert_task,
+    error::AppError,
+    form::{
+        project_form::{CreateProjectRequest, UpdateProjectMemberRequest, UpdateProjectRequest},
+        task_form::CreateTaskRequest,
+    },
+    models::{
+        _entities::{project, project_member, task, user},
+        project::{
+            add_member, has_global_permission, has_permission, is_prefix_available, member_of,
+            unique_prefix, ProjectPermission, ProjectRole,
+        },
+        task::with_details,
+    },
+    serializer::{ProjectMemberSerializer, ProjectSerializer, TaskSerializer},
+    AppState,
+};
+
+pub async fn get_routes<V: ApiVersion>() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/projects", get(get_projects).post(create_project))
+        .route(
+            "/projects/:project_id",
+            get(get_project).put(update_project).delete(delete_project),
+        )
+        .route("/projects/:project_id/members", get(get_project_members))
+        .route(
+            "/projects/:project_id/members/:user_id",
+            put(update_project_member).delete(delete_project_member),
+        )
+        .route(
+            "/projects/:project_id/tasks",
+            get(get_project_tasks::<V>).post(create_project_task::<V>),
+        )
+}
+
+/// Finds a project and checks that `user` may perform `permission` on it.
+pub async fn find_project<C>(
+    db: &C,
+    user: &user::Model,
+    project_id: i32,
+    permission: ProjectPermission,
+) -> Result<project::Model, AppError>
+where
+    C: ConnectionTrait,
+{
+    let project = project::Entity::find_by_id(project_id)
+        .one(db)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    if !has_permission(db, user, &project, permission).await? {
+        return Err(AppError::Unauthorized(format!(
+            "User {} lacks {} on project {}.",
+            user.id,
+            permission.code_name(),
+            project.id
+        )));
+    }
+
+    Ok(project)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects",
+    tag = "projects",
+    params(
+        ("archived" = Option<bool>, Query, description = "List the archived projects instead"),
+        ("page" = Option<u64>, Query, description = "Page number, from 1"),
+    ),
+    responses((status = 200, body = PaginatedResponse<Vec<ProjectSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_projects(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(params): Query<HashMap<String, String>>,
+    OriginalUri(original_uri): OriginalUri,
+) -> Result<impl IntoResponse, AppError> {
+    let mut project_query = project::Entity::find();
+
+    if !has_global_permission(&app_state.db, &user, ProjectPermission::View).await? {
+        project_query = project_query.filter(member_of(user.id));
+    }
+
+    let archived = params.get("archived").is_some_and(|v| v == "true");
+
+    project_query = project_query.filter(project::Column::IsArchived.eq(archived));
+
+    let page = params
+        .get("page")
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(1);
+
+    let project_count = project_query.clone().count(&app_state.db).await?;
+
+    let response_metadata = ResponseMetadata::new(project_count, Some(original_uri.to_string()));
+
+    let projects: Vec<ProjectSerializer> = project_query
+        .order_by(project::Column::IsInbox, sea_orm::Order::Desc)
+        .order_by(project::Column::Name, sea_orm::Order::Asc)
+        .paginate(&app_state.db, 10)
+        .fetch_page(page - 1)
+        .await?
+        .into_iter()
+        .map(ProjectSerializer::from)
+        .collect();
+
+    Ok(JsonResponse::paginate(projects, response_metadata, None))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects",
+    tag = "projects",
+    request_body = CreateProjectRequest,
+    responses((status = 200, body = DataResponse<ProjectSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn create_project(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Json(project_request): Json<CreateProjectRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    project_request.validate()?;
+
+    let key_prefix = match project_request.key_prefix {
+        Some(key_prefix) => {
+            if !is_prefix_available(&app_state.db, &key_prefix).await? {
+                return Err(AppError::BadRequest(format!(
+                    "The key prefix {} is already taken.",
+                    key_prefix
+                )));
+            }
+
+            key_prefix
+        }
+        None => unique_prefix(&app_state.db, &project_request.name).await?,
+    };
+
+    let project = app_state
+        .db
+        .transaction::<_, project::Model, DbErr>(|txn| {
+            Box::pin(async move {
+                let project = project::ActiveModel {
+                    id: NotSet,
+                    name: Set(project_request.name),
+                    description: Set(project_request.description),
+                    owner_id: Set(user.id),
+                    key_prefix: Set(key_prefix),
+                    next_task_number: NotSet,
+                    is_inbox: Set(false),
+                    is_archived: Set(false),
+                    date_created: NotSet,
+                }
+                .insert(txn)
+                .await?;
+
+                add_member(txn, project.id, user.id, ProjectRole::Owner).await?;
+
+                Ok(project)
+            })
+        })
+        .await
+        .map_err(|e| AppError::GenericError(e.to_string()))?; // should be database error
+
+    Ok(JsonResponse::data(ProjectSerializer::from(project), None))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}",
+    tag = "projects",
+    params(
+        ("project_id" = i32, Path, description = "Id of the project"),
+    ),
+    responses((status = 200, body = DataResponse<ProjectSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn get_project(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(project_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let project: ProjectSerializer =
+        find_project(&app_state.db, &user, project_id, ProjectPermission::View)
+            .await?
+            .into();
+
+    Ok(JsonResponse::data(project, None))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}",
+    tag = "projects",
+    params(
+        ("project_id" = i32, Path, description = "Id of the project"),
+    ),
+    request_body = UpdateProjectRequest,
+    responses((status = 200, body = DataResponse<ProjectSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn update_project(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(project_id): Path<i32>,
+    Json(project_request): Json<UpdateProjectRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let project = find_project(&app_state.db, &user, project_id, ProjectPermission::Manage).await?;
+
+    project_request.validate()?;
+
+    if project.is_inbox && project_request.is_archived == Some(true) {
+        return Err(AppError::BadRequest(
+            "The Inbox cannot be archived.".to_string(),
+        ));
+    }
+
+    let mut project: project::ActiveModel = project.into();
+
+    project.name = Set(project_request.name);
+    project.description = Set(project_request.description);
+
+    if let Some(is_archived) = project_request.is_archived {
+        project.is_archived = Set(is_archived);
+    }
+
+    let project: ProjectSerializer = project.update(&app_state.db).await?.into();
+
+    Ok(JsonResponse::data(project, None))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}",
+    tag = "projects",
+    params(
+        ("project_id" = i32, Path, description = "Id of the project"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+#[axum::debug_handler]
+pub async fn delete_project(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(project_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let project = find_project(&app_state.db, &user, project_id, ProjectPermission::Manage).await?;
+
+    if project.is_inbox {
+        return Err(AppError::BadRequest(
+            "The Inbox cannot be deleted.".to_string(),
+        ));
+    }
+
+    project::Entity::delete_by_id(project.id)
+        .exec(&app_state.db)
+        .await?;
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Project deleted successfully".to_string()),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/members",
+    tag = "projects",
+    params(
+        ("project_id" = i32, Path, description = "Id of the project"),
+    ),
+    responses((status = 200, body = DataResponse<Vec<ProjectMemberSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_project_members(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(project_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let project = find_project(&app_state.db, &user, project_id, ProjectPermission::View).await?;
+
+    let members: Vec<ProjectMemberSerializer> = project_member::Entity::find()
+        .filter(project_member::Column::ProjectId.eq(project.id))
+        .find_also_related(user::Entity)
+        .order_by(project_member::Column::DateCreated, sea_orm::Order::Asc)
+        .all(&app_state.db)
+        .await?
+        .into_iter()
+        .map(ProjectMemberSerializer::from)
+        .collect();
+
+    Ok(JsonResponse::data(members, None))
+}
+
+/// Adds a member to the project or changes their role.
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/members/{user_id}",
+    tag = "projects",
+    params(
+        ("project_id" = i32, Path, description = "Id of the project"),
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    request_body = UpdateProjectMemberRequest,
+    responses((status = 200, body = DataResponse<ProjectMemberSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn update_project_member(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path((project_id, user_id)): Path<(i32, i32)>,
+    Json(member_request): Json<UpdateProjectMemberRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let project = find_project(&app_state.db, &user, project_id, ProjectPermission::Manage).await?;
+
+    member_request.validate()?;
+
+    if user_id == project.owner_id {
+        return Err(AppError::BadRequest(
+            "The role of the project owner cannot be changed.".to_string(),
+        ));
+    }
+
+    if project.is_inbox {
+        return Err(AppError::BadRequest(
+            "The Inbox cannot be shared.".to_string(),
+        ));
+    }
+
+    let member_user = user::Entity::find_by_id(user_id)
+        .one(&app_state.db)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    let member = project_member::Entity::find()
+        .filter(project_member::Column::ProjectId.eq(project.id))
+        .filter(project_member::Column::UserId.eq(member_user.id))
+        .one(&app_state.db)
+        .await?;
+
+    let member = match member {
+        Some(member) => {
+            let mut member: project_member::ActiveModel = member.into();
+
+            member.role = Set(member_request.role);
+            member.update(&app_state.db).await?
+        }
+        None => {
+            project_member::ActiveModel {
+                id: NotSet,
+                project_id: Set(project.id),
+                user_id: Set(member_user.id),
+                role: Set(member_request.role),
+                date_created: NotSet,
+            }
+            .insert(&app_state.db)
+            .await?
+        }
+    };
+
+    let member = ProjectMemberSerializer::from((member, Some(member_user)));
+
+    Ok(JsonResponse::data(member, None))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/members/{user_id}",
+    tag = "projects",
+    params(
+        ("project_id" = i32, Path, description = "Id of the project"),
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+#[axum::debug_handler]
+pub async fn delete_project_member(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path((project_id, user_id)): Path<(i32, i32)>,
+) -> Result<impl IntoResponse, AppError> {
+    let project = find_project(&app_state.db, &user, project_id, ProjectPermission::Manage).await?;
+
+    if user_id == project.owner_id {
+        return Err(AppError::BadRequest(
+            "The project owner cannot be removed.".to_string(),
+        ));
+    }
+
+    let res = project_member::Entity::delete_many()
+        .filter(project_member::Column::ProjectId.eq(project.id))
+        .filter(project_member::Column::UserId.eq(user_id))
+        .exec(&app_state.db)
+        .await?;
+
+    if res.rows_affected == 0 {
+        return Err(sqlx::Error::RowNotFound.into());
+    }
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Member removed successfully".to_string()),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/tasks",
+    tag = "projects",
+    params(
+        ("project_id" = i32, Path, description = "Id of the project"),
+        ("status" = Option<String>, Query, description = "Only tasks with this status"),
+        ("page" = Option<u64>, Query, description = "Page number, from 1"),
+    ),
+    responses((status = 200, body = PaginatedResponse<Vec<TaskSerializer>>)),
+)]
+pub async fn get_project_tasks<V: ApiVersion>(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(project_id): Path<i32>,
+    Query(params): Query<HashMap<String, String>>,
+    OriginalUri(original_uri): OriginalUri,
+) -> Result<impl IntoResponse, AppError> {
+    let project = find_project(&app_state.db, &user, project_id, ProjectPermission::View).await?;
+
+    let mut task_query = task::Entity::find().filter(task::Column::ProjectId.eq(project.id));
+
+    if let Some(status) = params.get("status") {
+        task_query = task_query.filter(task::Column::Status.eq(status))
+    }
+
+    let page = params
+        .get("page")
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(1);
+
+    let task_count = task_query.clone().count(&app_state.db).await?;
+
+    let response_metadata = ResponseMetadata::new(task_count, Some(original_uri.to_string()));
+
+    let tasks = task_query
+        .order_by(task::Column::DateCreated, sea_orm::Order::Desc)
+        .paginate(&app_state.db, 10)
+        .fetch_page(page - 1)
+        .await?;
+
+    let tasks: Vec<V::Task> = with_details(&app_state.db, tasks)
+        .await?
+        .into_iter()
+        .map(|task| V::Task::from(TaskSerializer::from(task)))
+        .collect();
+
+    Ok(JsonResponse::paginate(tasks, response_metadata, None))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/tasks",
+    tag = "projects",
+    params(
+        ("project_id" = i32, Path, description = "Id of the project"),
+    ),
+    request_body = CreateTaskRequest,
+    responses((status = 200, body = DataResponse<TaskSerializer>)),
+)]
+pub async fn create_project_task<V: ApiVersion>(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(project_id): Path<i32>,
+    Json(mut task_request): Json<CreateTaskRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    task_request.project_id = Some(project_id);
+
+    let task = insert_task(&app_state, &user, task_request).await?;
+
+    Ok(JsonResponse::data(V::Task::from(task), None))
+}
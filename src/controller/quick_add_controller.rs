@@ -0,0 +1,97 @@
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{extract::State, response::IntoResponse, routing::post, Extension, Json, Router};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use validator::Validate;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    api_version::ApiVersion,
+    controller::task_controller::{add_assignees, insert_task, publish_task_change},
+    error::AppError,
+    form::task_form::{CreateTaskRequest, QuickAddRequest},
+    models::{
+        _entities::{task, user},
+        task::STATUSES,
+        user_profile::local_now,
+    },
+    quick_add,
+    serializer::{QuickAddParseSerializer, QuickAddSerializer},
+    AppState,
+};
+
+pub async fn get_routes<V: ApiVersion>() -> Router<Arc<AppState>> {
+    Router::new().route("/tasks/quick", post(quick_add_task::<V>))
+}
+
+/// Creates a task from one line of text, such as
+/// `Pay rent tomorrow !high #finance @alice every month`, read in the time
+/// zone of the user. Returns the task with what was understood of the text.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/quick",
+    tag = "tasks",
+    request_body = QuickAddRequest,
+    responses((status = 200, body = DataResponse<QuickAddSerializer>)),
+)]
+pub async fn quick_add_task<V: ApiVersion>(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Json(quick_add_request): Json<QuickAddRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    quick_add_request.validate()?;
+
+    let now = local_now(&app_state.db, user.id).await?;
+    let quick_task = quick_add::parse(&quick_add_request.text, now);
+
+    let assignees = user::Entity::find()
+        .filter(user::Column::Username.is_in(quick_task.assignees.clone()))
+        .all(&app_state.db)
+        .await?;
+
+    if let Some(username) = quick_task.assignees.iter().find(|username| {
+        !assignees
+            .iter()
+            .any(|assignee| &&assignee.username == username)
+    }) {
+        return Err(AppError::BadRequest(format!("Unknown user @{}.", username)));
+    }
+
+    let task_request = CreateTaskRequest {
+        title: quick_task.title.clone(),
+        description: String::new(),
+        status: STATUSES[0].to_string(),
+        user_id: user.id,
+        project_id: quick_add_request.project_id,
+        auto_complete: false,
+        estimate_minutes: None,
+        due_date: quick_task.due_date,
+        priority: quick_task.priority.clone(),
+        labels: quick_task.labels.clone(),
+        recurrence: quick_task
+            .recurrence
+            .map(|recurrence| recurrence.to_string()),
+    };
+
+    let mut task_serializer = insert_task(&app_state, &user, task_request).await?;
+
+    if !assignees.is_empty() {
+        let task = task::Entity::find_by_id(task_serializer.id)
+            .one(&app_state.db)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let user_ids: HashSet<i32> = assignees.iter().map(|assignee| assignee.id).collect();
+
+        add_assignees(&app_state, user, &task, user_ids).await?;
+
+        task_serializer = publish_task_change(&app_state, task, vec![]).await?;
+    }
+
+    Ok(JsonResponse::data(
+        QuickAddSerializer {
+            task: V::Task::from(task_serializer),
+            parsed: QuickAddParseSerializer::from(quick_task),
+        },
+        None,
+    ))
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    routing::get,
+    Extension, Router,
+};
+use chrono::NaiveDate;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    controller::project_controller::find_project,
+    error::AppError,
+    models::{
+        _entities::{project_member, task, user, user_profile},
+        project::{has_global_permission, ProjectPermission},
+        task::{in_projects_of, with_details, COMPLETED},
+        user_profile::{daily_capacity, DEFAULT_WEEKLY_CAPACITY_MINUTES},
+    },
+    serializer::{
+        UserSerializer, UserWorkloadSerializer, WorkloadDaySerializer, WorkloadSerializer,
+    },
+    utils::{parse_date_range, parse_id},
+    AppState,
+};
+
+/// Longest range, in days, a workload report can cover.
+const MAX_REPORT_DAYS: i64 = 366;
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/reports/workload", get(get_workload))
+}
+
+/// Estimated minutes assigned to one user on one day.
+#[derive(Debug, Default)]
+struct DayLoad {
+    minutes: i32,
+    task_ids: Vec<i32>,
+}
+
+/// Compares the estimates of the open tasks due between `from` and `to` with
+/// the capacity of their assignees, day by day. A task counts on its due date,
+/// and its estimate is split evenly between its assignees.
+#[utoipa::path(
+    get,
+    path = "/api/reports/workload",
+    tag = "reports",
+    params(
+        ("from" = Option<chrono::NaiveDate>, Query, description = "First day, e.g. 2025-01-01"),
+        ("to" = Option<chrono::NaiveDate>, Query, description = "Last day, inclusive"),
+        ("project_id" = Option<i32>, Query, description = "Only tasks of this project"),
+    ),
+    responses((status = 200, body = DataResponse<WorkloadSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn get_workload(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<impl IntoResponse, AppError> {
+    let (from, to) = parse_date_range(&params)?;
+
+    if (to - from).num_days() >= MAX_REPORT_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "A report can cover at most {} days.",
+            MAX_REPORT_DAYS
+        )));
+    }
+
+    let mut task_query = task::Entity::find()
+        .filter(task::Column::Status.ne(COMPLETED))
+        .filter(task::Column::DueDate.between(from, to));
+
+    if !has_global_permission(&app_state.db, &user, ProjectPermission::View).await? {
+        task_query = task_query.filter(in_projects_of(user.id));
+    }
+
+    // Members of the project are listed even when nothing is assigned to them.
+    let mut user_ids = BTreeSet::new();
+
+    if let Some(project_id) = parse_id(&params, "project_id")? {
+        find_project(&app_state.db, &user, project_id, ProjectPermission::View).await?;
+
+        task_query = task_query.filter(task::Column::ProjectId.eq(project_id));
+
+        user_ids.extend(
+            project_member::Entity::find()
+                .filter(project_member::Column::ProjectId.eq(project_id))
+                .all(&app_state.db)
+                .await?
+                .into_iter()
+                .map(|member| member.user_id),
+        );
+    }
+
+    let tasks = task_query
+        .order_by_asc(task::Column::DueDate)
+        .order_by_asc(task::Column::Id)
+        .all(&app_state.db)
+        .await?;
+
+    let mut loads: HashMap<i32, BTreeMap<NaiveDate, DayLoad>> = HashMap::new();
+    let mut unestimated: HashMap<i32, Vec<i32>> = HashMap::new();
+
+    for (task, assignees, _) in with_details(&app_state.db, tasks).await? {
+        let Some(due_date) = task.due_date else {
+            continue;
+        };
+
+        for assignee in &assignees {
+            user_ids.insert(assignee.id);
+
+            let Some(estimate_minutes) = task.estimate_minutes else {
+                unestimated.entry(assignee.id).or_default().push(task.id);
+                continue;
+            };
+
+            let load = loads
+                .entry(assignee.id)
+                .or_default()
+                .entry(due_date)
+                .or_default();
+
+            load.minutes += estimate_minutes / assignees.len() as i32;
+            load.task_ids.push(task.id);
+        }
+    }
+
+    let users = user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids))
+        .find_also_related(user_profile::Entity)
+        .order_by_asc(user::Column::Id)
+        .all(&app_state.db)
+        .await?;
+
+    let users = users
+        .into_iter()
+        .map(|(user, profile)| {
+            let weekly_capacity_minutes = profile.map_or(DEFAULT_WEEKLY_CAPACITY_MINUTES, |p| {
+                p.weekly_capacity_minutes
+            });
+
+            let mut user_loads = loads.remove(&user.id).unwrap_or_default();
+
+            let days: Vec<WorkloadDaySerializer> = from
+                .iter_days()
+                .take_while(|date| *date <= to)
+                .map(|date| {
+                    let load = user_loads.remove(&date).unwrap_or_default();
+                    let capacity_minutes = daily_capacity(weekly_capacity_minutes, date);
+
+                    WorkloadDaySerializer {
+                        date,
+                        capacity_minutes,
+                        assigned_minutes: load.minutes,
+                        overloaded: load.minutes > capacity_minutes,
+                        task_ids: load.task_ids,
+                    }
+                })
+                .collect();
+
+            let capacity_minutes: i32 = days.iter().map(|day| day.capacity_minutes).sum();
+            let assigned_minutes: i32 = days.iter().map(|day| day.assigned_minutes).sum();
+
+            UserWorkloadSerializer {
+                weekly_capacity_minutes,
+                capacity_minutes,
+                assigned_minutes,
+                utilization: (capacity_minutes > 0).then(|| {
+                    (assigned_minutes as f64 / capacity_minutes as f64 * 100.0).round() / 100.0
+                }),
+                overloaded: assigned_minutes > capacity_minutes,
+                unestimated_task_ids: unestimated.remove(&user.id).unwrap_or_default(),
+                user: UserSerializer::from(user),
+                days,
+            }
+        })
+        .collect();
+
+    let workload = WorkloadSerializer { from, to, users };
+
+    Ok(JsonResponse::data(workload, None))
+}
@@ -10,7 +10,7 @@ use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
 use validator::Validate;
 
 use crate::{
-    api_response::JsonResponse,
+    api_response::{DataResponse, JsonResponse},
     error::AppError,
     form::role_form::{CreateRoleRequest, UpdateRoleRequest},
     models::_entities::role,
@@ -27,6 +27,12 @@ pub async fn get_routes() -> Router<Arc<AppState>> {
         )
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/roles",
+    tag = "roles",
+    responses((status = 200, body = DataResponse<Vec<RoleSerializer>>)),
+)]
 #[axum::debug_handler]
 pub async fn get_roles(
     State(app_state): State<Arc<AppState>>,
@@ -41,6 +47,13 @@ pub async fn get_roles(
     Ok(JsonResponse::data(roles, None))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/roles",
+    tag = "roles",
+    request_body = CreateRoleRequest,
+    responses((status = 200, body = DataResponse<RoleSerializer>)),
+)]
 #[axum::debug_handler]
 pub async fn create_role(
     State(app_state): State<Arc<AppState>>,
@@ -57,6 +70,15 @@ pub async fn create_role(
     Ok(JsonResponse::data(role, None))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/roles/{role_id}",
+    tag = "roles",
+    params(
+        ("role_id" = i32, Path, description = "Id of the role"),
+    ),
+    responses((status = 200, body = DataResponse<RoleSerializer>)),
+)]
 pub async fn get_role(
     State(app_state): State<Arc<AppState>>,
     Path(role_id): Path<i32>,
@@ -69,6 +91,16 @@ pub async fn get_role(
 
     Ok(JsonResponse::data(role_serializer, None))
 }
+#[utoipa::path(
+    put,
+    path = "/api/roles/{role_id}",
+    tag = "roles",
+    params(
+        ("role_id" = i32, Path, description = "Id of the role"),
+    ),
+    request_body = UpdateRoleRequest,
+    responses((status = 200, body = DataResponse<RoleSerializer>)),
+)]
 pub async fn update_role(
     State(app_state): State<Arc<AppState>>,
     Path(role_id): Path<i32>,
@@ -89,6 +121,15 @@ pub async fn update_role(
 
     Ok(JsonResponse::data(role_serializer, None))
 }
+#[utoipa::path(
+    delete,
+    path = "/api/roles/{role_id}",
+    tag = "roles",
+    params(
+        ("role_id" = i32, Path, description = "Id of the role"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
 pub async fn delete_role(
     State(app_state): State<Arc<AppState>>,
     Path(role_id): Path<i32>,
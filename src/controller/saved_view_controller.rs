@@ -0,0 +1,452 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set, TransactionTrait, TryIntoModel,
+};
+use validator::Validate;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    controller::task_controller::task_filters,
+    error::AppError,
+    form::saved_view_form::SaveViewRequest,
+    models::{
+        _entities::{role, saved_view, saved_view_role, user},
+        saved_view::{roles_of, visible_to},
+    },
+    serializer::SavedViewSerializer,
+    AppState,
+};
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/views", get(get_views).post(create_view))
+        .route(
+            "/views/:view_id",
+            get(get_view).put(update_view).delete(delete_view),
+        )
+}
+
+/// Finds a view `user` can use, or only one they own with `owned`.
+async fn find_view(
+    app_state: &AppState,
+    user: &user::Model,
+    view_id: i32,
+    owned: bool,
+) -> Result<saved_view::Model, AppError> {
+    let view = saved_view::Entity::find_by_id(view_id)
+        .filter(visible_to(user.id))
+        .one(&app_state.db)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    if owned && view.user_id != user.id {
+        return Err(AppError::Unauthorized(format!(
+            "Saved view {} belongs to user {}",
+            view.id, view.user_id
+        )));
+    }
+
+    Ok(view)
+}
+
+/// Validates `view_request` and stores it in `view`, replacing the roles the
+/// view is shared with.
+async fn save_view(
+    app_state: &AppState,
+    user: &user::Model,
+    mut view: saved_view::ActiveModel,
+    view_request: SaveViewRequest,
+) -> Result<SavedViewSerializer, AppError> {
+    view_request.validate()?;
+
+    // Rejects ids that do not parse before they are stored.
+    task_filters(user, &view_request.filters)?;
+
+    let mut slug_query = saved_view::Entity::find()
+        .filter(saved_view::Column::UserId.eq(user.id))
+        .filter(saved_view::Column::Slug.eq(&view_request.slug));
+
+    if let Some(view_id) = view.id.try_as_ref() {
+        slug_query = slug_query.filter(saved_view::Column::Id.ne(*view_id));
+    }
+
+    if slug_query.count(&app_state.db).await? > 0 {
+        return Err(AppError::BadRequest(format!(
+            "You already have a view called {}.",
+            view_request.slug
+        )));
+    }
+
+    let mut role_ids = view_request.role_ids;
+
+    role_ids.sort_unstable();
+    role_ids.dedup();
+
+    let role_count = role::Entity::find()
+        .filter(role::Column::Id.is_in(role_ids.clone()))
+        .count(&app_state.db)
+        .await?;
+
+    if role_count != role_ids.len() as u64 {
+        return Err(AppError::BadRequest("Unknown role.".to_string()));
+    }
+
+    view.name = Set(view_request.name);
+    view.slug = Set(view_request.slug);
+    view.filters = Set(serde_json::to_string(&view_request.filters)
+        .map_err(|e| AppError::GenericError(e.to_string()))?);
+    view.sort = Set(view_request.sort);
+    view.columns = Set(view_request.columns.join(","));
+    view.visibility = Set(view_request.visibility);
+
+    let view = app_state
+        .db
+        .transaction::<_, saved_view::Model, DbErr>(|txn| {
+            let role_ids = role_ids.clone();
+
+            Box::pin(async move {
+                let view = view.save(txn).await?.try_into_model()?;
+
+                saved_view_role::Entity::delete_many()
+                    .filter(saved_view_role::Column::SavedViewId.eq(view.id))
+                    .exec(txn)
+                    .await?;
+
+                if !role_ids.is_empty() {
+                    saved_view_role::Entity::insert_many(role_ids.into_iter().map(|role_id| {
+                        saved_view_role::ActiveModel {
+                            id: NotSet,
+                            saved_view_id: Set(view.id),
+                            role_id: Set(role_id),
+                        }
+                    }))
+                    .exec(txn)
+                    .await?;
+                }
+
+                Ok(view)
+            })
+        })
+        .await
+        .map_err(|e| AppError::GenericError(e.to_string()))?; // should be database error
+
+    Ok(SavedViewSerializer::from((view, role_ids)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/views",
+    tag = "views",
+    responses((status = 200, body = DataResponse<Vec<SavedViewSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_views(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let views = saved_view::Entity::find()
+        .filter(visible_to(user.id))
+        .order_by_asc(saved_view::Column::Name)
+        .order_by_asc(saved_view::Column::Id)
+        .all(&app_state.db)
+        .await?;
+
+    let mut roles = roles_of(&app_state.db, views.iter().map(|view| view.id).collect()).await?;
+
+    let views: Vec<SavedViewSerializer> = views
+        .into_iter()
+        .map(|view| {
+            let role_ids = roles.remove(&view.id).unwrap_or_default();
+            SavedViewSerializer::from((view, role_ids))
+        })
+        .collect();
+
+    Ok(JsonResponse::data(views, None))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/views",
+    tag = "views",
+    request_body = SaveViewRequest,
+    responses((status = 200, body = DataResponse<SavedViewSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn create_view(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Json(view_request): Json<SaveViewRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let view = saved_view::ActiveModel {
+        id: NotSet,
+        user_id: Set(user.id),
+        date_created: NotSet,
+        ..Default::default()
+    };
+
+    let view = save_view(&app_state, &user, view, view_request).await?;
+
+    Ok(JsonResponse::data(view, None))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/views/{view_id}",
+    tag = "views",
+    params(
+        ("view_id" = i32, Path, description = "Id of the saved view"),
+    ),
+    responses((status = 200, body = DataResponse<SavedViewSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn get_view(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(view_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let view = find_view(&app_state, &user, view_id, false).await?;
+
+    let role_ids = roles_of(&app_state.db, vec![view.id])
+        .await?
+        .remove(&view.id)
+        .unwrap_or_default();
+
+    Ok(JsonResponse::data(
+        SavedViewSerializer::from((view, role_ids)),
+        None,
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/views/{view_id}",
+    tag = "views",
+    params(
+        ("view_id" = i32, Path, description = "Id of the saved view"),
+    ),
+    request_body = SaveViewRequest,
+    responses((status = 200, body = DataResponse<SavedViewSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn update_view(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(view_id): Path<i32>,
+    Json(view_request): Json<SaveViewRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let view = find_view(&app_state, &user, view_id, true).await?;
+
+    let view = save_view(&app_state, &user, view.into(), view_request).await?;
+
+    Ok(JsonResponse::data(view, None))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/views/{view_id}",
+    tag = "views",
+    params(
+        ("view_id" = i32, Path, description = "Id of the saved view"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+#[axum::debug_handler]
+pub async fn delete_view(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(view_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let view = find_view(&app_state, &user, view_id, true).await?;
+
+    saved_view::Entity::delete_by_id(view.id)
+        .exec(&app_state.db)
+        .await?;
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Saved view deleted successfully".to_string()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        models::_entities::user_role,
+        testing::{app_state, create_user, response_data},
+    };
+
+    fn view_request(filters: &[(&str, &str)], role_ids: Vec<i32>) -> SaveViewRequest {
+        SaveViewRequest {
+            name: "On call".to_string(),
+            slug: "on-call".to_string(),
+            filters: filters
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>(),
+            sort: None,
+            columns: Vec::new(),
+            visibility: if role_ids.is_empty() {
+                "private".to_string()
+            } else {
+                "roles".to_string()
+            },
+            role_ids,
+        }
+    }
+
+    async fn create_role(app_state: &AppState, name: &str, holders: &[&user::Model]) -> i32 {
+        let role = role::ActiveModel {
+            name: Set(name.to_string()),
+            ..Default::default()
+        }
+        .insert(&app_state.db)
+        .await
+        .unwrap();
+
+        for holder in holders {
+            user_role::ActiveModel {
+                user_id: Set(holder.id),
+                role_id: Set(role.id),
+                ..Default::default()
+            }
+            .insert(&app_state.db)
+            .await
+            .unwrap();
+        }
+
+        role.id
+    }
+
+    async fn view_ids(app_state: &Arc<AppState>, user: &user::Model) -> Vec<i64> {
+        let views = response_data(
+            get_views(State(app_state.clone()), Extension(user.clone()))
+                .await
+                .unwrap(),
+        )
+        .await;
+
+        views
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|view| view["id"].as_i64().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn unknown_filters_are_rejected() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+
+        for filters in [
+            [("colour", "red")],
+            [("status", "archived")],
+            [("watching", "maybe")],
+        ] {
+            let created = create_view(
+                State(app_state.clone()),
+                Extension(alice.clone()),
+                Json(view_request(&filters, vec![])),
+            )
+            .await;
+            assert!(matches!(created, Err(AppError::Validation(_))));
+        }
+
+        let created = create_view(
+            State(app_state.clone()),
+            Extension(alice.clone()),
+            Json(view_request(&[("status", "pending")], vec![])),
+        )
+        .await;
+        assert!(created.is_ok());
+
+        let views = saved_view::Entity::find()
+            .count(&app_state.db)
+            .await
+            .unwrap();
+        assert_eq!(views, 1);
+    }
+
+    #[tokio::test]
+    async fn view_shared_with_a_role_is_visible_to_its_holders_only() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let carol = create_user(&app_state, "carol").await;
+        let dave = create_user(&app_state, "dave").await;
+        let ops = create_role(&app_state, "ops", &[&bob]).await;
+        create_role(&app_state, "support", &[&carol]).await;
+
+        let unknown_role = create_view(
+            State(app_state.clone()),
+            Extension(alice.clone()),
+            Json(view_request(&[], vec![9999])),
+        )
+        .await;
+        assert!(matches!(unknown_role, Err(AppError::BadRequest(_))));
+
+        let view = response_data(
+            create_view(
+                State(app_state.clone()),
+                Extension(alice.clone()),
+                Json(view_request(&[("status", "pending")], vec![ops])),
+            )
+            .await
+            .unwrap(),
+        )
+        .await;
+        let view_id = view["id"].as_i64().unwrap();
+
+        assert_eq!(view_ids(&app_state, &alice).await, vec![view_id]);
+        assert_eq!(view_ids(&app_state, &bob).await, vec![view_id]);
+        assert!(view_ids(&app_state, &carol).await.is_empty());
+        assert!(view_ids(&app_state, &dave).await.is_empty());
+
+        let shared = get_view(
+            State(app_state.clone()),
+            Extension(bob.clone()),
+            Path(view_id as i32),
+        )
+        .await;
+        assert!(shared.is_ok());
+
+        let hidden = get_view(
+            State(app_state.clone()),
+            Extension(carol.clone()),
+            Path(view_id as i32),
+        )
+        .await;
+        assert!(matches!(
+            hidden,
+            Err(AppError::DatabaseError(sqlx::Error::RowNotFound))
+        ));
+
+        // Holders of the role may use the view, not change it.
+        let updated = update_view(
+            State(app_state.clone()),
+            Extension(bob.clone()),
+            Path(view_id as i32),
+            Json(view_request(&[], vec![])),
+        )
+        .await;
+        assert!(matches!(updated, Err(AppError::Unauthorized(_))));
+
+        user_role::Entity::delete_many()
+            .filter(user_role::Column::UserId.eq(bob.id))
+            .exec(&app_state.db)
+            .await
+            .unwrap();
+        assert!(view_ids(&app_state, &bob).await.is_empty());
+    }
+}
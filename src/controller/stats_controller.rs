@@ -0,0 +1,267 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    routing::get,
+    Extension, Router,
+};
+use chrono::{Days, NaiveDate, Utc};
+use sea_orm::{ColumnTrait, Condition};
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    controller::project_controller::find_project,
+    error::AppError,
+    models::{
+        _entities::{task, user},
+        project::{has_global_permission, ProjectPermission},
+        task::{in_projects_of, STATUSES},
+    },
+    serializer::{
+        AssigneeCountSerializer, BurndownPointSerializer, BurndownSerializer,
+        DailyTaskCountSerializer, TaskStatsSerializer,
+    },
+    stats,
+    utils::{parse_date_range, parse_id},
+    AppState,
+};
+
+/// Days covered when no range is given.
+const DEFAULT_DAYS: u64 = 30;
+
+/// Longest range, in days, a series can cover.
+const MAX_DAYS: i64 = 366;
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/stats/tasks", get(get_task_stats))
+        .route("/stats/burndown", get(get_burndown))
+}
+
+/// Reads the date range of a series, the last 30 days by default.
+fn series_range(params: &HashMap<String, String>) -> Result<(NaiveDate, NaiveDate), AppError> {
+    let (from, to) = if params.contains_key("from") || params.contains_key("to") {
+        parse_date_range(params)?
+    } else {
+        let to = Utc::now().date_naive();
+        (to - Days::new(DEFAULT_DAYS - 1), to)
+    };
+
+    if (to - from).num_days() >= MAX_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "A series can cover at most {} days.",
+            MAX_DAYS
+        )));
+    }
+
+    Ok((from, to))
+}
+
+/// Tasks the statistics are computed on: the ones `user` can see, limited to
+/// `project_id` when given.
+async fn stats_scope(
+    app_state: &AppState,
+    user: &user::Model,
+    project_id: Option<i32>,
+) -> Result<Condition, AppError> {
+    let mut scope = Condition::all();
+
+    if !has_global_permission(&app_state.db, user, ProjectPermission::View).await? {
+        scope = scope.add(in_projects_of(user.id));
+    }
+
+    if let Some(project_id) = project_id {
+        find_project(&app_state.db, user, project_id, ProjectPermission::View).await?;
+
+        scope = scope.add(task::Column::ProjectId.eq(project_id));
+    }
+
+    Ok(scope)
+}
+
+fn midnight(date: NaiveDate) -> chrono::NaiveDateTime {
+    date.and_hms_opt(0, 0, 0).expect("Midnight is valid")
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/stats/tasks",
+    tag = "reports",
+    params(
+        ("from" = Option<chrono::NaiveDate>, Query, description = "First day, e.g. 2025-01-01"),
+        ("to" = Option<chrono::NaiveDate>, Query, description = "Last day, inclusive"),
+        ("project_id" = Option<i32>, Query, description = "Only tasks of this project"),
+    ),
+    responses((status = 200, body = DataResponse<TaskStatsSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn get_task_stats(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<impl IntoResponse, AppError> {
+    let (from, to) = series_range(&params)?;
+    let scope = stats_scope(&app_state, &user, parse_id(&params, "project_id")?).await?;
+
+    let day_after = to
+        .checked_add_days(Days::new(1))
+        .ok_or(AppError::BadRequest("to is out of range.".to_string()))?;
+    let (start, end) = (midnight(from), midnight(day_after));
+
+    let mut by_status: BTreeMap<String, i64> = STATUSES
+        .iter()
+        .map(|status| (status.to_string(), 0))
+        .collect();
+
+    for (status, count) in
+        stats::count_by(&app_state.db, scope.clone(), task::Column::Status).await?
+    {
+        by_status.insert(status.unwrap_or_default(), count);
+    }
+
+    let by_priority: BTreeMap<String, i64> =
+        stats::count_by(&app_state.db, scope.clone(), task::Column::Priority)
+            .await?
+            .into_iter()
+            .map(|(priority, count)| (priority.unwrap_or("none".to_string()), count))
+            .collect();
+
+    let (assignees, unassigned) = stats::count_by_assignee(&app_state.db, scope.clone()).await?;
+
+    let mut by_assignee: Vec<AssigneeCountSerializer> = assignees
+        .into_iter()
+        .map(|assignee| AssigneeCountSerializer {
+            user_id: Some(assignee.user_id),
+            username: Some(assignee.username),
+            count: assignee.count,
+        })
+        .collect();
+
+    by_assignee.push(AssigneeCountSerializer {
+        user_id: None,
+        username: None,
+        count: unassigned as i64,
+    });
+
+    let mut daily: BTreeMap<String, DailyTaskCountSerializer> = from
+        .iter_days()
+        .take_while(|date| *date <= to)
+        .map(|date| {
+            let date = date.to_string();
+
+            (
+                date.clone(),
+                DailyTaskCountSerializer {
+                    date,
+                    created: 0,
+                    completed: 0,
+                },
+            )
+        })
+        .collect();
+
+    for day in stats::created_per_day(&app_state.db, scope.clone(), start, end).await? {
+        if let Some(daily) = daily.get_mut(&day.day) {
+            daily.created = day.count;
+        }
+    }
+
+    for day in stats::completed_per_day(&app_state.db, scope.clone(), start, end).await? {
+        if let Some(daily) = daily.get_mut(&day.day) {
+            daily.completed = day.count;
+        }
+    }
+
+    let (cycle_time, lead_time) = stats::average_times(&app_state.db, scope, start, end).await?;
+
+    let round = |hours: f64| (hours * 100.0).round() / 100.0;
+
+    let task_stats = TaskStatsSerializer {
+        from,
+        to,
+        total: by_status.values().sum(),
+        by_status,
+        by_priority,
+        by_assignee,
+        daily: daily.into_values().collect(),
+        average_cycle_time_hours: cycle_time.map(round),
+        average_lead_time_hours: lead_time.map(round),
+    };
+
+    Ok(JsonResponse::data(task_stats, None))
+}
+
+/// Remaining open tasks per day, with the ideal line going from the first
+/// day's remaining tasks down to zero on the last day.
+#[utoipa::path(
+    get,
+    path = "/api/stats/burndown",
+    tag = "reports",
+    params(
+        ("from" = Option<chrono::NaiveDate>, Query, description = "First day, e.g. 2025-01-01"),
+        ("to" = Option<chrono::NaiveDate>, Query, description = "Last day, inclusive"),
+        ("project_id" = Option<i32>, Query, description = "Only tasks of this project"),
+    ),
+    responses((status = 200, body = DataResponse<BurndownSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn get_burndown(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<impl IntoResponse, AppError> {
+    let (from, to) = series_range(&params)?;
+    let project_id = parse_id(&params, "project_id")?;
+    let scope = stats_scope(&app_state, &user, project_id).await?;
+
+    let points = stats::burndown(&app_state.db, scope, from, to).await?;
+
+    let start = points.first().map_or(0, |point| point.remaining_tasks) as f64;
+    let steps = points.len().saturating_sub(1).max(1) as f64;
+
+    let series = points
+        .into_iter()
+        .enumerate()
+        .map(|(i, point)| BurndownPointSerializer {
+            date: point.day,
+            remaining_tasks: point.remaining_tasks,
+            remaining_minutes: point.remaining_minutes,
+            ideal_tasks: (start * (1.0 - i as f64 / steps) * 100.0).round() / 100.0,
+        })
+        .collect();
+
+    let burndown = BurndownSerializer {
+        from,
+        to,
+        project_id,
+        series,
+    };
+
+    Ok(JsonResponse::data(burndown, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{app_state, create_user};
+
+    #[tokio::test]
+    async fn last_representable_day_is_rejected() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+
+        let params = HashMap::from([
+            ("from".to_string(), "+262142-12-31".to_string()),
+            ("to".to_string(), "+262142-12-31".to_string()),
+        ]);
+        assert!(parse_date_range(&params).is_ok());
+
+        let stats = get_task_stats(State(app_state), Extension(alice), Query(params)).await;
+
+        assert!(matches!(stats, Err(AppError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,641 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, Set};
+use serde_json::Value;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    controller::{
+        task_controller::{
+            authorize_task, change_status, insert_task, remove_task, save_task, serialize_task,
+        },
+        task_transfer_controller::{json_field, row_request},
+    },
+    error::AppError,
+    form::task_form::{normalize_recurrence, SyncChange, SyncRequest},
+    models::{
+        _entities::{task, user},
+        project::{has_global_permission, ProjectPermission},
+        task::{in_projects_of, join_labels, with_details},
+        task_change::{self, changed_tasks, first_changes, latest_change, task_version},
+        task_event::{field_value, TRACKED_FIELDS},
+        task_field_version::field_versions,
+    },
+    serializer::{
+        SyncChangesSerializer, SyncConflictSerializer, SyncErrorSerializer, SyncResultSerializer,
+        SyncTaskSerializer, SyncTombstoneSerializer, TaskSerializer,
+    },
+    AppState,
+};
+
+/// Most tasks returned by one request of the change feed.
+const SYNC_PAGE_SIZE: u64 = 500;
+
+/// Most changes a client can push at once.
+const MAX_SYNC_CHANGES: usize = 500;
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/sync", get(get_changes).post(push_changes))
+}
+
+/// Reads a token issued by `get_changes`, the sequence number of the last
+/// change the client has seen.
+fn parse_token(token: &str) -> Result<i32, AppError> {
+    token
+        .parse()
+        .ok()
+        .filter(|since: &i32| *since >= 0)
+        .ok_or(AppError::BadRequest("Invalid sync token.".to_string()))
+}
+
+/// Returns the tasks created, updated and deleted since the change `since`,
+/// or every task when it is missing, each with its version. Tasks moved out
+/// of the projects of the user are reported as deleted, and the ones moved
+/// into them as updated, so clients should add updated tasks they lack.
+///
+/// When `has_more` is set the client should ask again with the new token
+/// before pushing its changes.
+#[utoipa::path(
+    get,
+    path = "/api/sync",
+    tag = "sync",
+    params(
+        ("since" = Option<String>, Query, description = "Token of the last sync, none for a first sync"),
+    ),
+    responses((status = 200, body = DataResponse<SyncChangesSerializer>)),
+)]
+pub async fn get_changes(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<impl IntoResponse, AppError> {
+    let since = match params.get("since") {
+        Some(token) => parse_token(token)?,
+        None => 0,
+    };
+
+    // Taken first, so changes made while the feed is read are left for the
+    // next sync rather than skipped.
+    let until = latest_change(&app_state.db).await?;
+
+    let sees_all = has_global_permission(&app_state.db, &user, ProjectPermission::View).await?;
+
+    let mut condition = Condition::all();
+    let mut task_query = task::Entity::find();
+
+    if !sees_all {
+        condition = condition.add(task_change::in_projects_of(user.id));
+        task_query = task_query.filter(in_projects_of(user.id));
+    }
+
+    let mut versions =
+        changed_tasks(&app_state.db, condition, since, until, SYNC_PAGE_SIZE + 1).await?;
+
+    let has_more = versions.len() as u64 > SYNC_PAGE_SIZE;
+    versions.truncate(SYNC_PAGE_SIZE as usize);
+
+    let token = match (has_more, versions.last()) {
+        (true, Some(last)) => last.version,
+        _ => until,
+    };
+
+    let task_ids: Vec<i32> = versions.iter().map(|version| version.task_id).collect();
+
+    let tasks = task_query
+        .filter(task::Column::Id.is_in(task_ids.clone()))
+        .all(&app_state.db)
+        .await?;
+
+    let mut tasks: HashMap<i32, TaskSerializer> = with_details(&app_state.db, tasks)
+        .await?
+        .into_iter()
+        .map(|task| (task.0.id, TaskSerializer::from(task)))
+        .collect();
+
+    let first_changes = first_changes(&app_state.db, task_ids).await?;
+
+    let mut changes = SyncChangesSerializer {
+        token: token.to_string(),
+        has_more,
+        created: Vec::new(),
+        updated: Vec::new(),
+        deleted: Vec::new(),
+    };
+
+    for version in versions {
+        let Some(task) = tasks.remove(&version.task_id) else {
+            changes.deleted.push(SyncTombstoneSerializer {
+                id: version.task_id,
+                version: version.version,
+            });
+            continue;
+        };
+
+        let task = SyncTaskSerializer {
+            version: version.version,
+            task,
+        };
+
+        if first_changes
+            .get(&task.task.id)
+            .is_some_and(|first| *first > since)
+        {
+            changes.created.push(task);
+        } else {
+            changes.updated.push(task);
+        }
+    }
+
+    Ok(JsonResponse::data(changes, None))
+}
+
+/// Applies the changes a client made offline, in order, and reports the
+/// outcome of each. A field changed on the server since the base version of
+/// an update is a conflict and keeps its server value, while the other
+/// fields of the update are applied. A task changed since the base version
+/// of its deletion is not deleted. A change that fails is rejected without
+/// affecting the others.
+#[utoipa::path(
+    post,
+    path = "/api/sync",
+    tag = "sync",
+    request_body = SyncRequest,
+    responses((status = 200, body = DataResponse<Vec<SyncResultSerializer>>)),
+)]
+pub async fn push_changes(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Json(sync_request): Json<SyncRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if sync_request.changes.len() > MAX_SYNC_CHANGES {
+        return Err(AppError::BadRequest(format!(
+            "At most {} changes can be pushed at once.",
+            MAX_SYNC_CHANGES
+        )));
+    }
+
+    let mut results = Vec::new();
+
+    for change in sync_request.changes {
+        let result = match push_change(&app_state, &user, &change).await {
+            Ok(result) => result,
+            Err(error) => rejected(&change, None, error),
+        };
+
+        results.push(result);
+    }
+
+    Ok(JsonResponse::data(results, None))
+}
+
+async fn push_change(
+    app_state: &AppState,
+    user: &user::Model,
+    change: &SyncChange,
+) -> Result<SyncResultSerializer, AppError> {
+    let Some(task_id) = change.id else {
+        return create_task(app_state, user, change).await;
+    };
+
+    let Some(task) = task::Entity::find_by_id(task_id).one(&app_state.db).await? else {
+        // Deleted on the server, which the next sync reports.
+        return Ok(SyncResultSerializer {
+            status: "deleted",
+            ..sync_result(change)
+        });
+    };
+
+    authorize_task(&app_state.db, user, &task, ProjectPermission::ChangeTasks).await?;
+
+    if change.deleted {
+        delete_task(app_state, change, task).await
+    } else {
+        update_task(app_state, user, change, task).await
+    }
+}
+
+fn sync_result(change: &SyncChange) -> SyncResultSerializer {
+    SyncResultSerializer {
+        client_id: change.client_id.clone(),
+        id: change.id,
+        status: "rejected",
+        version: None,
+        task: None,
+        conflicts: Vec::new(),
+        errors: Vec::new(),
+    }
+}
+
+/// Reports a change the server refused. Errors that are not about the change
+/// itself are logged, and reported without their details.
+fn rejected(change: &SyncChange, field: Option<String>, error: AppError) -> SyncResultSerializer {
+    let message = match error {
+        AppError::BadRequest(message) => message,
+        AppError::Unauthorized(_) => "You are not authorized.".to_string(),
+        AppError::DatabaseError(sqlx::Error::RowNotFound) => "Not found.".to_string(),
+        error => {
+            tracing::error!("Sync change failed: {:?}", error);
+            "The change could not be applied.".to_string()
+        }
+    };
+
+    SyncResultSerializer {
+        errors: vec![SyncErrorSerializer { field, message }],
+        ..sync_result(change)
+    }
+}
+
+fn field_errors(
+    change: &SyncChange,
+    errors: Vec<(Option<String>, String)>,
+) -> SyncResultSerializer {
+    SyncResultSerializer {
+        errors: errors
+            .into_iter()
+            .map(|(field, message)| SyncErrorSerializer { field, message })
+            .collect(),
+        ..sync_result(change)
+    }
+}
+
+/// Value of `field` as compared between client and server: empty values count
+/// as missing, and labels and recurrences are in their stored form.
+fn sync_value(field: &str, value: Option<String>) -> Option<String> {
+    let value = value.filter(|value| !value.trim().is_empty())?;
+
+    match field {
+        "labels" => {
+            let labels: Vec<String> = value.split(',').map(str::to_string).collect();
+            Some(join_labels(&labels))
+        }
+        "recurrence" => normalize_recurrence(Some(value)),
+        _ => Some(value),
+    }
+}
+
+async fn create_task(
+    app_state: &AppState,
+    user: &user::Model,
+    change: &SyncChange,
+) -> Result<SyncResultSerializer, AppError> {
+    let mut row = Vec::new();
+    let mut errors = Vec::new();
+
+    for (field, value) in &change.fields {
+        match json_field(value) {
+            Ok(value) => row.push((field.clone(), value)),
+            Err(message) => errors.push((Some(field.clone()), message)),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Ok(field_errors(change, errors));
+    }
+
+    let task_request = match row_request(user, &HashMap::new(), row) {
+        Ok(task_request) => task_request,
+        Err(errors) => return Ok(field_errors(change, errors)),
+    };
+
+    let task = match insert_task(app_state, user, task_request).await {
+        Ok(task) => task,
+        Err(error) => return Ok(rejected(change, None, error)),
+    };
+
+    Ok(SyncResultSerializer {
+        id: Some(task.id),
+        status: "created",
+        version: Some(task_version(&app_state.db, task.id).await?),
+        task: Some(task),
+        ..sync_result(change)
+    })
+}
+
+async fn update_task(
+    app_state: &AppState,
+    user: &user::Model,
+    change: &SyncChange,
+    task: task::Model,
+) -> Result<SyncResultSerializer, AppError> {
+    let versions = field_versions(&app_state.db, task.id).await?;
+    let server_task = serde_json::to_value(serialize_task(&app_state.db, task.clone()).await?)
+        .map_err(|e| AppError::GenericError(e.to_string()))?;
+
+    let mut row: HashMap<String, Option<String>> = TRACKED_FIELDS
+        .iter()
+        .map(|field| (field.to_string(), field_value(&task, field)))
+        .collect();
+    let mut changed = false;
+    let mut conflicts = Vec::new();
+    let mut errors = Vec::new();
+
+    for (field, value) in &change.fields {
+        if !TRACKED_FIELDS.contains(&field.as_str()) {
+            errors.push((
+                Some(field.clone()),
+                "Cannot be changed by sync.".to_string(),
+            ));
+            continue;
+        }
+
+        let client_value = match json_field(value) {
+            Ok(client_value) => sync_value(field, client_value),
+            Err(message) => {
+                errors.push((Some(field.clone()), message));
+                continue;
+            }
+        };
+
+        if client_value == sync_value(field, field_value(&task, field)) {
+            continue;
+        }
+
+        let server_version = versions.get(field).copied().unwrap_or(0);
+
+        if server_version > change.base_version {
+            conflicts.push(SyncConflictSerializer {
+                field: field.clone(),
+                client_value: value.clone(),
+                server_value: server_task.get(field).cloned().unwrap_or(Value::Null),
+                server_version,
+            });
+        } else {
+            row.insert(field.clone(), client_value);
+            changed = true;
+        }
+    }
+
+    if !errors.is_empty() {
+        return Ok(field_errors(change, errors));
+    }
+
+    let task_serializer = if changed {
+        let task_request = match row_request(user, &HashMap::new(), row.into_iter().collect()) {
+            Ok(task_request) => task_request,
+            Err(errors) => return Ok(field_errors(change, errors)),
+        };
+
+        let mut active_task: task::ActiveModel = task.clone().into();
+
+        if let Err(error) =
+            change_status(&app_state.db, &task, &mut active_task, task_request.status).await
+        {
+            return Ok(rejected(change, Some("status".to_string()), error));
+        }
+
+        active_task.title = Set(task_request.title);
+        active_task.description = Set(task_request.description);
+        active_task.estimate_minutes = Set(task_request.estimate_minutes);
+        active_task.due_date = Set(task_request.due_date);
+        active_task.priority = Set(task_request.priority);
+        active_task.labels = Set(join_labels(&task_request.labels));
+        active_task.recurrence = Set(normalize_recurrence(task_request.recurrence));
+
+        match save_task(app_state, user.clone(), task.clone(), active_task).await {
+            Ok(task_serializer) => task_serializer,
+            Err(error) => return Ok(rejected(change, None, error)),
+        }
+    } else {
+        serialize_task(&app_state.db, task.clone()).await?
+    };
+
+    Ok(SyncResultSerializer {
+        status: if conflicts.is_empty() {
+            "updated"
+        } else {
+            "conflict"
+        },
+        version: Some(task_version(&app_state.db, task.id).await?),
+        task: Some(task_serializer),
+        conflicts,
+        ..sync_result(change)
+    })
+}
+
+async fn delete_task(
+    app_state: &AppState,
+    change: &SyncChange,
+    task: task::Model,
+) -> Result<SyncResultSerializer, AppError> {
+    let versions = field_versions(&app_state.db, task.id).await?;
+
+    let server_task = serialize_task(&app_state.db, task.clone()).await?;
+    let server_value =
+        serde_json::to_value(&server_task).map_err(|e| AppError::GenericError(e.to_string()))?;
+
+    let conflicts: Vec<SyncConflictSerializer> = TRACKED_FIELDS
+        .iter()
+        .filter_map(|field| {
+            let server_version = versions.get(*field).copied()?;
+
+            (server_version > change.base_version).then(|| SyncConflictSerializer {
+                field: field.to_string(),
+                client_value: Value::Null,
+                server_value: server_value.get(*field).cloned().unwrap_or(Value::Null),
+                server_version,
+            })
+        })
+        .collect();
+
+    if !conflicts.is_empty() {
+        return Ok(SyncResultSerializer {
+            status: "conflict",
+            version: Some(task_version(&app_state.db, task.id).await?),
+            task: Some(server_task),
+            conflicts,
+            ..sync_result(change)
+        });
+    }
+
+    remove_task(app_state, task).await?;
+
+    Ok(SyncResultSerializer {
+        status: "deleted",
+        version: Some(latest_change(&app_state.db).await?),
+        ..sync_result(change)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        controller::task_controller::{assign_users, insert_tasks},
+        form::task_form::AssignTaskRequest,
+        models::task::TaskRef,
+        testing::{app_state, create_project, create_task, create_user, response_data},
+    };
+
+    async fn push(app_state: &Arc<AppState>, user: &user::Model, changes: Value) -> Value {
+        let sync_request: SyncRequest =
+            serde_json::from_value(json!({ "changes": changes })).unwrap();
+
+        let response = push_changes(
+            State(app_state.clone()),
+            Extension(user.clone()),
+            Json(sync_request),
+        )
+        .await
+        .unwrap();
+
+        response_data(response).await[0].take()
+    }
+
+    async fn pull(app_state: &Arc<AppState>, user: &user::Model, since: Option<&str>) -> Value {
+        let params = since
+            .map(|since| HashMap::from([("since".to_string(), since.to_string())]))
+            .unwrap_or_default();
+
+        let response = get_changes(
+            State(app_state.clone()),
+            Extension(user.clone()),
+            Query(params),
+        )
+        .await
+        .unwrap();
+
+        response_data(response).await
+    }
+
+    /// Changes the priority of `task` on the server.
+    async fn edit_on_server(app_state: &AppState, user: &user::Model, task: &task::Model) {
+        let mut active_task: task::ActiveModel = task.clone().into();
+        active_task.priority = Set(Some("high".to_string()));
+
+        save_task(app_state, user.clone(), task.clone(), active_task)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn field_changed_on_server_is_a_conflict() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let project = create_project(&app_state, &alice, "ops", &[]).await;
+        let task = create_task(&app_state, &alice, &project, "Deploy").await;
+        let base_version = task_version(&app_state.db, task.id).await.unwrap();
+
+        edit_on_server(&app_state, &alice, &task).await;
+
+        let result = push(
+            &app_state,
+            &alice,
+            json!([{
+                "id": task.id,
+                "base_version": base_version,
+                "fields": { "priority": "low", "title": "Deploy to production" },
+            }]),
+        )
+        .await;
+
+        assert_eq!(result["status"], "conflict");
+        assert_eq!(result["conflicts"][0]["field"], "priority");
+        assert_eq!(result["conflicts"][0]["client_value"], "low");
+        assert_eq!(result["task"]["priority"], "high");
+        assert_eq!(result["task"]["title"], "Deploy to production");
+    }
+
+    #[tokio::test]
+    async fn delete_after_server_edit_is_a_conflict() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let project = create_project(&app_state, &alice, "ops", &[]).await;
+        let task = create_task(&app_state, &alice, &project, "Deploy").await;
+        let base_version = task_version(&app_state.db, task.id).await.unwrap();
+
+        edit_on_server(&app_state, &alice, &task).await;
+
+        let change = json!([{ "id": task.id, "base_version": base_version, "deleted": true }]);
+        let result = push(&app_state, &alice, change).await;
+
+        assert_eq!(result["status"], "conflict");
+        assert_eq!(result["conflicts"][0]["field"], "priority");
+        assert!(task::Entity::find_by_id(task.id)
+            .one(&app_state.db)
+            .await
+            .unwrap()
+            .is_some());
+
+        let version = result["version"].clone();
+        let change = json!([{ "id": task.id, "base_version": version, "deleted": true }]);
+
+        assert_eq!(push(&app_state, &alice, change).await["status"], "deleted");
+    }
+
+    #[tokio::test]
+    async fn changes_are_paged() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let project = create_project(&app_state, &alice, "ops", &[&bob]).await;
+
+        let tasks = (0..=SYNC_PAGE_SIZE)
+            .map(|i| {
+                let task = task::ActiveModel {
+                    title: Set(format!("Task {}", i)),
+                    description: Set(String::new()),
+                    status: Set("pending".to_string()),
+                    user_id: Set(alice.id),
+                    project_id: Set(Some(project.id)),
+                    auto_complete: Set(false),
+                    labels: Set(String::new()),
+                    ..Default::default()
+                };
+
+                (task, Vec::new())
+            })
+            .collect();
+        let tasks = insert_tasks(&app_state, tasks).await.unwrap();
+
+        let first = pull(&app_state, &alice, None).await;
+        assert_eq!(first["has_more"], true);
+        assert_eq!(
+            first["created"].as_array().unwrap().len() as u64,
+            SYNC_PAGE_SIZE
+        );
+
+        let second = pull(&app_state, &alice, first["token"].as_str()).await;
+        assert_eq!(second["has_more"], false);
+        assert_eq!(second["created"][0]["id"], tasks.last().unwrap().id);
+
+        let third = pull(&app_state, &alice, second["token"].as_str()).await;
+        assert_eq!(third["created"], json!([]));
+        assert_eq!(third["updated"], json!([]));
+
+        // Assigning a user is a change of the task.
+        assign_users(
+            &app_state,
+            alice.clone(),
+            TaskRef::Id(tasks[0].id),
+            AssignTaskRequest {
+                user_ids: vec![bob.id],
+            },
+        )
+        .await
+        .unwrap();
+
+        let fourth = pull(&app_state, &alice, third["token"].as_str()).await;
+        assert_eq!(fourth["updated"][0]["id"], tasks[0].id);
+        assert_eq!(fourth["updated"][0]["assignees"][0]["id"], bob.id);
+
+        // Its assignees go with it, leaving only the deletion.
+        let task = task::Entity::find_by_id(tasks[0].id)
+            .one(&app_state.db)
+            .await
+            .unwrap()
+            .unwrap();
+        remove_task(&app_state, task).await.unwrap();
+
+        let fifth = pull(&app_state, &alice, fourth["token"].as_str()).await;
+        assert_eq!(fifth["updated"], json!([]));
+        assert_eq!(fifth["deleted"][0]["id"], tasks[0].id);
+    }
+}
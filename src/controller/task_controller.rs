@@ -1,47 +1,226 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
 
 use axum::{
     extract::{OriginalUri, Path, Query, State},
     response::IntoResponse,
-    routing::get,
-    Json, Router,
+    routing::{delete, get, post},
+    Extension, Json, Router,
 };
+use chrono::{NaiveDate, Utc};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter,
-    QueryOrder, Set,
+    sea_query::{Expr, OnConflict},
+    ActiveModelTrait,
+    ActiveValue::NotSet,
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, PaginatorTrait,
+    QueryFilter, QueryOrder, Select, Set, TransactionTrait, TryIntoModel,
 };
+use serde::Serialize;
+use serde_json::json;
 use validator::Validate;
 
 use crate::{
-    api_response::{JsonResponse, ResponseMetadata},
+    api_response::{DataResponse, JsonResponse, PaginatedResponse, ResponseMetadata},
+    api_version::ApiVersion,
+    controller::{
+        board_controller::{ensure_wip_limit, ensure_wip_room},
+        project_controller::find_project,
+    },
     error::AppError,
-    form::task_form::{CreateTaskRequest, UpdateTaskRequest},
-    models::_entities::task,
-    serializer::TaskSerializer,
+    form::task_form::{
+        normalize_recurrence, AssignTaskRequest, CreateTaskRequest, RepositionTaskRequest,
+        UpdateTaskRequest,
+    },
+    models::{
+        _entities::{task, task_assignee, task_checklist_item, task_event, task_watcher, user},
+        project::{allocate_task_key, has_global_permission, inbox, ProjectPermission},
+        saved_view::{find_view_by_slug, view_columns, view_filters},
+        task::{
+            assigned_to, audience, in_column, in_projects_of, join_labels, labelled, next_rank,
+            rebalance_column, watched_by, with_details, TaskRef, COMPLETED, STATUSES,
+        },
+        task_event::{apply_field, record_changes},
+        user_profile::local_now,
+    },
+    notification, rank,
+    recurrence::Recurrence,
+    serializer::{TaskEventSerializer, TaskSerializer},
+    webhook::{self, WebhookEvent},
     AppState,
 };
 
-pub async fn get_routes() -> Router<Arc<AppState>> {
+pub async fn get_routes<V: ApiVersion>() -> Router<Arc<AppState>> {
     Router::new()
-        .route("/tasks", get(get_tasks).post(create_task))
+        .route("/tasks", get(get_tasks::<V>).post(create_task::<V>))
         .route(
             "/tasks/:task_id",
-            get(get_task).put(update_task).delete(delete_task),
+            get(get_task::<V>).put(update_task::<V>).delete(delete_task),
+        )
+        .route("/tasks/:task_id/history", get(get_task_history))
+        .route(
+            "/tasks/:task_id/revert/:event_id",
+            post(revert_task_event::<V>),
+        )
+        .route("/tasks/:task_id/reposition", post(reposition_task::<V>))
+        .route("/tasks/:task_id/assignees", post(assign_task::<V>))
+        .route(
+            "/tasks/:task_id/assignees/:user_id",
+            delete(unassign_task::<V>),
+        )
+        .route(
+            "/tasks/:task_id/watch",
+            post(watch_task).delete(unwatch_task),
         )
 }
 
-#[axum::debug_handler]
-pub async fn get_tasks(
-    State(app_state): State<Arc<AppState>>,
-    Query(params): Query<HashMap<String, String>>,
-    OriginalUri(original_uri): OriginalUri,
-) -> Result<impl IntoResponse, AppError> {
-    let mut task_query = task::Entity::find();
+/// Matches the tasks selected by the filter parameters of the task list.
+pub fn task_filters(
+    user: &user::Model,
+    params: &HashMap<String, String>,
+) -> Result<Condition, AppError> {
+    let mut condition = Condition::all();
 
     if let Some(status) = params.get("status") {
-        task_query = task_query.filter(task::Column::Status.eq(status))
+        condition = condition.add(task::Column::Status.eq(status));
+    }
+
+    if let Some(project_id) = params.get("project_id") {
+        let project_id = project_id
+            .parse::<i32>()
+            .map_err(|_| AppError::BadRequest("Invalid project.".to_string()))?;
+
+        condition = condition.add(task::Column::ProjectId.eq(project_id));
+    }
+
+    if let Some(assignee) = params.get("assignee") {
+        let assignee_id = match assignee.as_str() {
+            "me" => user.id,
+            _ => assignee
+                .parse::<i32>()
+                .map_err(|_| AppError::BadRequest("Invalid assignee.".to_string()))?,
+        };
+
+        condition = condition.add(assigned_to(assignee_id));
+    }
+
+    if params.get("watching").is_some_and(|v| v == "true") {
+        condition = condition.add(watched_by(user.id));
+    }
+
+    if let Some(priority) = params.get("priority") {
+        condition = match priority.as_str() {
+            "none" => condition.add(task::Column::Priority.is_null()),
+            _ => condition.add(task::Column::Priority.eq(priority)),
+        };
+    }
+
+    if let Some(label) = params.get("label") {
+        condition = condition.add(labelled(label));
+    }
+
+    if params.get("overdue").is_some_and(|v| v == "true") {
+        condition = condition
+            .add(task::Column::DueDate.lt(Utc::now().date_naive()))
+            .add(task::Column::Status.ne(COMPLETED));
+    }
+
+    Ok(condition)
+}
+
+/// The tasks `user` lists with `params`, in order, and the columns to list
+/// them with, none meaning every field. With `view`, the filters, sort and
+/// columns of that saved view apply; parameters given explicitly take
+/// precedence over the view's.
+pub async fn task_list_query(
+    app_state: &AppState,
+    user: &user::Model,
+    params: &mut HashMap<String, String>,
+) -> Result<(Select<task::Entity>, Vec<String>), AppError> {
+    let mut columns = Vec::new();
+
+    if let Some(slug) = params.remove("view") {
+        let view = find_view_by_slug(&app_state.db, user, &slug)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        for (filter, value) in view_filters(&view) {
+            params.entry(filter).or_insert(value);
+        }
+
+        columns = view_columns(&view);
+
+        if let Some(sort) = view.sort {
+            params.entry("sort".to_string()).or_insert(sort);
+        }
     }
 
+    let mut task_query = task::Entity::find().filter(task_filters(user, params)?);
+
+    if !has_global_permission(&app_state.db, user, ProjectPermission::View).await? {
+        task_query = task_query.filter(in_projects_of(user.id));
+    }
+
+    task_query = match params.get("sort").map(String::as_str) {
+        Some("rank") => task_query
+            .order_by(task::Column::Rank, sea_orm::Order::Asc)
+            .order_by(task::Column::Id, sea_orm::Order::Asc),
+        Some("due_date") => task_query
+            .order_by(
+                Expr::col(task::Column::DueDate).is_null(),
+                sea_orm::Order::Asc,
+            )
+            .order_by(task::Column::DueDate, sea_orm::Order::Asc)
+            .order_by(task::Column::Id, sea_orm::Order::Asc),
+        _ => task_query
+            .order_by(task::Column::DateCreated, sea_orm::Order::Desc)
+            .order_by(task::Column::Id, sea_orm::Order::Desc),
+    };
+
+    Ok((task_query, columns))
+}
+
+/// Serializes `task` with only the listed `columns`, and its id, or with
+/// every field when there are none.
+pub fn pick_columns(task: impl Serialize, columns: &[String]) -> serde_json::Value {
+    let mut task = json!(task);
+
+    if !columns.is_empty() {
+        if let Some(fields) = task.as_object_mut() {
+            fields.retain(|field, _| field == "id" || columns.contains(field));
+        }
+    }
+
+    task
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks",
+    tag = "tasks",
+    params(
+        ("status" = Option<String>, Query, description = "Only tasks with this status"),
+        ("project_id" = Option<i32>, Query, description = "Only tasks of this project"),
+        ("assignee" = Option<String>, Query, description = "Only tasks assigned to this user id, or `me`"),
+        ("watching" = Option<bool>, Query, description = "Only tasks the user watches"),
+        ("priority" = Option<String>, Query, description = "Only tasks with this priority, or `none`"),
+        ("label" = Option<String>, Query, description = "Only tasks with this label"),
+        ("overdue" = Option<bool>, Query, description = "Only open tasks past their due date"),
+        ("sort" = Option<String>, Query, description = "`rank`, `due_date` or the newest first by default"),
+        ("view" = Option<String>, Query, description = "Slug of a saved view whose filters, sort and columns apply"),
+        ("page" = Option<u64>, Query, description = "Page number, from 1"),
+    ),
+    responses((status = 200, body = PaginatedResponse<Vec<TaskSerializer>>)),
+)]
+pub async fn get_tasks<V: ApiVersion>(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(mut params): Query<HashMap<String, String>>,
+    OriginalUri(original_uri): OriginalUri,
+) -> Result<impl IntoResponse, AppError> {
+    let (task_query, columns) = task_list_query(&app_state, &user, &mut params).await?;
+
     let page = params
         .get("page")
         .and_then(|s| s.parse::<u64>().ok())
@@ -57,90 +236,1525 @@ pub async fn get_tasks(
         ..Default::default()
     };
 
-    let tasks: Vec<TaskSerializer> = task_query
-        .order_by(task::Column::DateCreated, sea_orm::Order::Desc)
+    let tasks = task_query
         .paginate(&app_state.db, 10)
         .fetch_page(page - 1)
+        .await?;
+
+    let tasks: Vec<serde_json::Value> = with_details(&app_state.db, tasks)
         .await?
-        .iter()
-        .map(|task| TaskSerializer::from(task.clone()))
+        .into_iter()
+        .map(|task| pick_columns(V::Task::from(TaskSerializer::from(task)), &columns))
         .collect();
 
     Ok(JsonResponse::paginate(tasks, response_metadata, None))
 }
 
-#[axum::debug_handler]
-pub async fn create_task(
+#[utoipa::path(
+    post,
+    path = "/api/tasks",
+    tag = "tasks",
+    request_body = CreateTaskRequest,
+    responses((status = 200, body = DataResponse<TaskSerializer>)),
+)]
+pub async fn create_task<V: ApiVersion>(
     State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
     Json(task_request): Json<CreateTaskRequest>,
 ) -> Result<impl IntoResponse, AppError> {
+    let task = insert_task(&app_state, &user, task_request).await?;
+
+    Ok(JsonResponse::data(V::Task::from(task), None))
+}
+
+/// Creates a task in the requested project, or in the Inbox of `user` when
+/// none is given.
+pub async fn insert_task(
+    app_state: &AppState,
+    user: &user::Model,
+    task_request: CreateTaskRequest,
+) -> Result<TaskSerializer, AppError> {
     task_request.validate()?;
 
-    let task: TaskSerializer = task_request
-        .into_active_model()
-        .insert(&app_state.db)
+    let project = match task_request.project_id {
+        Some(project_id) => {
+            find_project(
+                &app_state.db,
+                user,
+                project_id,
+                ProjectPermission::ChangeTasks,
+            )
+            .await?
+        }
+        None => inbox(&app_state.db, user).await?,
+    };
+
+    if project.is_archived {
+        return Err(AppError::BadRequest(
+            "Tasks cannot be added to an archived project.".to_string(),
+        ));
+    }
+
+    ensure_wip_limit(&app_state.db, Some(project.id), &task_request.status, None).await?;
+
+    let rank = next_rank(&app_state.db, Some(project.id), &task_request.status).await?;
+
+    let mut task = task_request.into_active_model();
+
+    task.project_id = Set(Some(project.id));
+    task.rank = Set(rank);
+
+    let (task, audience) = app_state
+        .db
+        .transaction::<_, (TaskSerializer, Vec<i32>), DbErr>(|txn| {
+            Box::pin(async move {
+                task.key = Set(Some(allocate_task_key(txn, project.id).await?));
+
+                let task = task.insert(txn).await?;
+                let audience = audience(txn, &task).await?;
+                let task = TaskSerializer::from(task);
+
+                webhook::dispatch(txn, WebhookEvent::TaskCreated, &task, Some(&audience)).await?;
+
+                Ok((task, audience))
+            })
+        })
+        .await
+        .map_err(|e| AppError::GenericError(e.to_string()))?; // should be database error
+
+    app_state.events.task_created(&task, audience);
+
+    Ok(task)
+}
+
+/// Fails when creating `tasks` would exceed the WIP limit of a column.
+pub async fn ensure_room_for<'a, C>(
+    db: &C,
+    tasks: impl IntoIterator<Item = &'a task::ActiveModel>,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    let mut per_column: BTreeMap<(Option<i32>, String), u64> = BTreeMap::new();
+
+    for task in tasks {
+        *per_column
+            .entry((*task.project_id.as_ref(), task.status.as_ref().clone()))
+            .or_default() += 1;
+    }
+
+    for ((project_id, status), count) in per_column {
+        ensure_wip_room(db, project_id, &status, None, count).await?;
+    }
+
+    Ok(())
+}
+
+/// Creates `tasks`, each with the given checklist, in one transaction. Fails
+/// without creating any when they would exceed the WIP limit of a column.
+pub async fn insert_tasks(
+    app_state: &AppState,
+    tasks: Vec<(task::ActiveModel, Vec<String>)>,
+) -> Result<Vec<TaskSerializer>, AppError> {
+    ensure_room_for(&app_state.db, tasks.iter().map(|(task, _)| task)).await?;
+
+    let tasks = app_state
+        .db
+        .transaction::<_, Vec<(TaskSerializer, Vec<i32>)>, DbErr>(|txn| {
+            Box::pin(async move { create_tasks(txn, tasks).await })
+        })
+        .await
+        .map_err(|e| AppError::GenericError(e.to_string()))?; // should be database error
+
+    Ok(announce_created(app_state, tasks))
+}
+
+/// Creates `tasks` with their checklists and queues their webhooks, within the
+/// transaction `txn` of the caller. Returns each task with its audience, for
+/// `announce_created` once the transaction is committed.
+pub async fn create_tasks<C>(
+    txn: &C,
+    tasks: Vec<(task::ActiveModel, Vec<String>)>,
+) -> Result<Vec<(TaskSerializer, Vec<i32>)>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let mut created = Vec::new();
+
+    for (mut task, checklist) in tasks {
+        let project_id = *task.project_id.as_ref();
+
+        task.rank = Set(next_rank(txn, project_id, task.status.as_ref()).await?);
+
+        if let Some(project_id) = project_id {
+            task.key = Set(Some(allocate_task_key(txn, project_id).await?));
+        }
+
+        let task = task.insert(txn).await?;
+
+        if !checklist.is_empty() {
+            task_checklist_item::Entity::insert_many(checklist.into_iter().enumerate().map(
+                |(i, text)| task_checklist_item::ActiveModel {
+                    id: NotSet,
+                    task_id: Set(task.id),
+                    text: Set(text),
+                    is_done: Set(false),
+                    position: Set(i as i32 + 1),
+                    date_created: NotSet,
+                },
+            ))
+            .exec(txn)
+            .await?;
+        }
+
+        created.push(task);
+    }
+
+    let mut serialized = Vec::new();
+
+    for task in with_details(txn, created).await? {
+        let audience = audience(txn, &task.0).await?;
+        let task = TaskSerializer::from(task);
+
+        webhook::dispatch(txn, WebhookEvent::TaskCreated, &task, Some(&audience)).await?;
+
+        serialized.push((task, audience));
+    }
+
+    Ok(serialized)
+}
+
+/// Notifies the audience of each task created by `create_tasks`.
+pub fn announce_created(
+    app_state: &AppState,
+    tasks: Vec<(TaskSerializer, Vec<i32>)>,
+) -> Vec<TaskSerializer> {
+    let mut created = Vec::new();
+
+    for (task, audience) in tasks {
+        app_state.events.task_created(&task, audience);
+        created.push(task);
+    }
+
+    created
+}
+
+/// Checks that `user` may perform `permission` on the project of `task`.
+/// Tasks outside of any project are open to everyone.
+pub async fn authorize_task<C>(
+    db: &C,
+    user: &user::Model,
+    task: &task::Model,
+    permission: ProjectPermission,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if let Some(project_id) = task.project_id {
+        find_project(db, user, project_id, permission).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn find_task(
+    app_state: &AppState,
+    user: &user::Model,
+    task_ref: TaskRef,
+    permission: ProjectPermission,
+) -> Result<task::Model, AppError> {
+    let task = task::Entity::find()
+        .filter(task_ref.condition())
+        .one(&app_state.db)
         .await?
-        .into();
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    authorize_task(&app_state.db, user, &task, permission).await?;
+
+    Ok(task)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    responses((status = 200, body = DataResponse<TaskSerializer>)),
+)]
+pub async fn get_task<V: ApiVersion>(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+) -> Result<impl IntoResponse, AppError> {
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::View).await?;
+
+    let task = serialize_task(&app_state.db, task).await?;
+
+    Ok(JsonResponse::data(V::Task::from(task), None))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    request_body = UpdateTaskRequest,
+    responses((status = 200, body = DataResponse<TaskSerializer>)),
+)]
+pub async fn update_task<V: ApiVersion>(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+    Json(task_request): Json<UpdateTaskRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let task_serializer = modify_task(&app_state, user, task_ref, task_request).await?;
+
+    Ok(JsonResponse::data(V::Task::from(task_serializer), None))
+}
+
+/// Applies `task_request` to the task referenced by `task_ref`, which `user`
+/// must be allowed to change.
+pub async fn modify_task(
+    app_state: &AppState,
+    user: user::Model,
+    task_ref: TaskRef,
+    task_request: UpdateTaskRequest,
+) -> Result<TaskSerializer, AppError> {
+    task_request.validate()?;
+
+    let task = find_task(app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    let mut active_task: task::ActiveModel = task.clone().into();
+
+    change_status(&app_state.db, &task, &mut active_task, task_request.status).await?;
+
+    active_task.title = Set(task_request.title);
+
+    if let Some(description) = task_request.description {
+        active_task.description = Set(description);
+    }
+
+    if let Some(auto_complete) = task_request.auto_complete {
+        active_task.auto_complete = Set(auto_complete);
+    }
 
-    Ok(JsonResponse::data(task, None))
+    if let Some(estimate_minutes) = task_request.estimate_minutes {
+        active_task.estimate_minutes = Set(estimate_minutes);
+    }
+
+    if let Some(due_date) = task_request.due_date {
+        active_task.due_date = Set(due_date);
+    }
+
+    if let Some(priority) = task_request.priority {
+        active_task.priority = Set(priority);
+    }
+
+    if let Some(labels) = task_request.labels {
+        active_task.labels = Set(join_labels(&labels));
+    }
+
+    if let Some(recurrence) = task_request.recurrence {
+        active_task.recurrence = Set(normalize_recurrence(recurrence));
+    }
+
+    save_task(app_state, user, task, active_task).await
 }
 
-pub async fn get_task(
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+pub async fn delete_task(
     State(app_state): State<Arc<AppState>>,
-    Path(task_id): Path<i32>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
 ) -> Result<impl IntoResponse, AppError> {
-    let task: TaskSerializer = task::Entity::find_by_id(task_id)
+    let task = task::Entity::find()
+        .filter(task_ref.condition())
         .one(&app_state.db)
+        .await?;
+
+    if let Some(task) = task {
+        authorize_task(&app_state.db, &user, &task, ProjectPermission::ChangeTasks).await?;
+
+        remove_task(&app_state, task).await?;
+    }
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Task deleted successfully".to_string()),
+    ))
+}
+
+/// Deletes `task`, then notifies webhooks and the users who could see it.
+pub async fn remove_task(app_state: &AppState, task: task::Model) -> Result<(), AppError> {
+    let audience = audience(&app_state.db, &task).await?;
+
+    let res = task::Entity::delete_by_id(task.id)
+        .exec(&app_state.db)
+        .await?;
+
+    println!("{:?}", res);
+
+    if res.rows_affected > 0 {
+        webhook::dispatch(
+            &app_state.db,
+            WebhookEvent::TaskDeleted,
+            json!({ "id": task.id, "key": task.key }),
+            Some(&audience),
+        )
+        .await?;
+
+        app_state.events.task_deleted(task.id, audience);
+    }
+
+    Ok(())
+}
+
+/// Sets the status of `active_task`. A task moved to another column must fit
+/// within its WIP limit, and goes to its bottom.
+pub async fn change_status<C>(
+    db: &C,
+    task: &task::Model,
+    active_task: &mut task::ActiveModel,
+    status: String,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    if status != task.status {
+        ensure_wip_limit(db, task.project_id, &status, Some(task.id)).await?;
+
+        active_task.rank = Set(next_rank(db, task.project_id, &status).await?);
+    }
+
+    active_task.status = Set(status);
+
+    Ok(())
+}
+
+/// Serializes a task together with its assignees and checklist progress.
+pub async fn serialize_task<C>(db: &C, task: task::Model) -> Result<TaskSerializer, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let task = with_details(db, vec![task])
+        .await?
+        .pop()
+        .expect("One task in, one task out");
+
+    Ok(TaskSerializer::from(task))
+}
+
+/// Takes the recurrence off a repeating task as it is completed, to pass it on
+/// to the next occurrence.
+fn take_recurrence(task: &task::Model, active_task: &mut task::ActiveModel) -> Option<Recurrence> {
+    let completing = task.status != COMPLETED
+        && active_task
+            .status
+            .try_as_ref()
+            .is_some_and(|status| status == COMPLETED);
+
+    let recurrence = active_task
+        .recurrence
+        .try_as_ref()
+        .cloned()
+        .flatten()
+        .filter(|_| completing)
+        .and_then(|rule| Recurrence::parse(&rule))?;
+
+    active_task.recurrence = Set(None);
+
+    Some(recurrence)
+}
+
+/// The occurrence of the repeating `task` after the one just completed, due
+/// after `today`.
+fn next_occurrence(
+    task: task::Model,
+    recurrence: Recurrence,
+    today: NaiveDate,
+) -> task::ActiveModel {
+    task::ActiveModel {
+        title: Set(task.title),
+        description: Set(task.description),
+        status: Set(STATUSES[0].to_string()),
+        user_id: Set(task.user_id),
+        project_id: Set(task.project_id),
+        auto_complete: Set(task.auto_complete),
+        estimate_minutes: Set(task.estimate_minutes),
+        due_date: Set(Some(recurrence.next_due_date(task.due_date, today))),
+        priority: Set(task.priority),
+        labels: Set(task.labels),
+        recurrence: Set(Some(recurrence.to_string())),
+        ..Default::default()
+    }
+}
+
+/// Creates `occurrence`, the next occurrence of `task`, with the same
+/// checklist, unticked, and the same assignees.
+async fn insert_occurrence(
+    app_state: &AppState,
+    task: &task::Model,
+    occurrence: task::ActiveModel,
+) -> Result<(), AppError> {
+    let checklist = task_checklist_item::Entity::find()
+        .filter(task_checklist_item::Column::TaskId.eq(task.id))
+        .order_by_asc(task_checklist_item::Column::Position)
+        .all(&app_state.db)
+        .await?
+        .into_iter()
+        .map(|item| item.text)
+        .collect();
+
+    // Assignees removed from the project are not carried over.
+    let audience = audience(&app_state.db, task).await?;
+    let assignees: Vec<task_assignee::Model> = task_assignee::Entity::find()
+        .filter(task_assignee::Column::TaskId.eq(task.id))
+        .all(&app_state.db)
+        .await?
+        .into_iter()
+        .filter(|assignee| audience.contains(&assignee.user_id))
+        .collect();
+
+    let Some(occurrence) = insert_tasks(app_state, vec![(occurrence, checklist)])
+        .await?
+        .pop()
+    else {
+        return Ok(());
+    };
+
+    if !assignees.is_empty() {
+        task_assignee::Entity::insert_many(assignees.into_iter().map(|assignee| {
+            task_assignee::ActiveModel {
+                id: NotSet,
+                task_id: Set(occurrence.id),
+                user_id: Set(assignee.user_id),
+                date_created: NotSet,
+            }
+        }))
+        .exec(&app_state.db)
+        .await?;
+
+        let occurrence = task::Entity::find_by_id(occurrence.id)
+            .one(&app_state.db)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        publish_task_change(app_state, occurrence, vec![]).await?;
+    }
+
+    Ok(())
+}
+
+/// Saves the changes made to `task`, recording its history, webhook deliveries
+/// and notifications in one transaction before publishing the update.
+/// Completing a repeating task creates its next occurrence.
+pub async fn save_task(
+    app_state: &AppState,
+    user: user::Model,
+    task: task::Model,
+    mut active_task: task::ActiveModel,
+) -> Result<TaskSerializer, AppError> {
+    let occurrence = match take_recurrence(&task, &mut active_task) {
+        Some(recurrence) => {
+            let today = local_now(&app_state.db, user.id).await?.date();
+            let occurrence =
+                next_occurrence(active_task.clone().try_into_model()?, recurrence, today);
+
+            ensure_room_for(&app_state.db, [&occurrence]).await?;
+
+            Some((task.clone(), occurrence))
+        }
+        None => None,
+    };
+
+    let (task_serializer, audience) = app_state
+        .db
+        .transaction::<_, (TaskSerializer, Vec<i32>), DbErr>(|txn| {
+            Box::pin(async move {
+                let updated_task = active_task.update(txn).await?;
+
+                record_changes(txn, &task, &updated_task, Some(user.id)).await?;
+                notification::task_status_changed(txn, &user, &task, &updated_task).await?;
+
+                let audience = audience(txn, &updated_task).await?;
+                let task_serializer = serialize_task(txn, updated_task).await?;
+
+                dispatch_task_updated(txn, &task.status, &task_serializer, &audience).await?;
+
+                Ok((task_serializer, audience))
+            })
+        })
+        .await
+        .map_err(|e| AppError::GenericError(e.to_string()))?; // should be database error
+
+    app_state.events.task_updated(&task_serializer, audience);
+
+    if let Some((task, occurrence)) = occurrence {
+        insert_occurrence(app_state, &task, occurrence).await?;
+    }
+
+    Ok(task_serializer)
+}
+
+async fn dispatch_task_updated<C>(
+    db: &C,
+    previous_status: &str,
+    task: &TaskSerializer,
+    audience: &[i32],
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    webhook::dispatch(db, WebhookEvent::TaskUpdated, task, Some(audience)).await?;
+
+    if previous_status != task.status {
+        webhook::dispatch(
+            db,
+            WebhookEvent::TaskStatusChanged,
+            json!({ "task": task, "previous_status": previous_status }),
+            Some(audience),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Publishes a change made outside of the task row itself, such as its people
+/// or checklist. `previous_audience` lets users who were just removed learn
+/// about it too.
+pub async fn publish_task_change(
+    app_state: &AppState,
+    task: task::Model,
+    previous_audience: Vec<i32>,
+) -> Result<TaskSerializer, AppError> {
+    let current_audience = audience(&app_state.db, &task).await?;
+    let task = serialize_task(&app_state.db, task).await?;
+
+    webhook::dispatch(
+        &app_state.db,
+        WebhookEvent::TaskUpdated,
+        &task,
+        Some(&current_audience),
+    )
+    .await?;
+
+    let audience: BTreeSet<i32> = current_audience
+        .into_iter()
+        .chain(previous_audience)
+        .collect();
+
+    app_state
+        .events
+        .task_updated(&task, audience.into_iter().collect());
+
+    Ok(task)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/history",
+    tag = "tasks",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+        ("page" = Option<u64>, Query, description = "Page number, from 1"),
+    ),
+    responses((status = 200, body = PaginatedResponse<Vec<TaskEventSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_task_history(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+    Query(params): Query<HashMap<String, String>>,
+    OriginalUri(original_uri): OriginalUri,
+) -> Result<impl IntoResponse, AppError> {
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::View).await?;
+
+    let event_query = task_event::Entity::find()
+        .filter(task_event::Column::TaskId.eq(task.id))
+        .find_also_related(user::Entity);
+
+    let event_count = event_query.clone().count(&app_state.db).await?;
+
+    let response_metadata = ResponseMetadata::new(event_count, Some(original_uri.to_string()));
+
+    let page = params
+        .get("page")
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(1);
+
+    let events: Vec<TaskEventSerializer> = event_query
+        .order_by(task_event::Column::DateCreated, sea_orm::Order::Desc)
+        .order_by(task_event::Column::Id, sea_orm::Order::Desc)
+        .paginate(&app_state.db, 10)
+        .fetch_page(page - 1)
         .await?
-        .ok_or(sqlx::Error::RowNotFound)?
-        .into();
+        .into_iter()
+        .map(TaskEventSerializer::from)
+        .collect();
 
-    Ok(JsonResponse::data(task, None))
+    Ok(JsonResponse::paginate(events, response_metadata, None))
 }
 
-pub async fn update_task(
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/revert/{event_id}",
+    tag = "tasks",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+        ("event_id" = i32, Path, description = "Id of the history event to undo"),
+    ),
+    responses((status = 200, body = DataResponse<TaskSerializer>)),
+)]
+pub async fn revert_task_event<V: ApiVersion>(
     State(app_state): State<Arc<AppState>>,
-    Path(task_id): Path<i32>,
-    Json(task_request): Json<UpdateTaskRequest>,
+    Extension(user): Extension<user::Model>,
+    Path((task_ref, event_id)): Path<(TaskRef, i32)>,
 ) -> Result<impl IntoResponse, AppError> {
-    let task = task::Entity::find_by_id(task_id)
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    let event = task_event::Entity::find_by_id(event_id)
+        .filter(task_event::Column::TaskId.eq(task.id))
         .one(&app_state.db)
         .await?
-        .ok_or(sqlx::Error::RowNotFound)?;
+        .ok_or(AppError::GenericError("Task event not found.".to_string()))?;
 
-    let mut task: task::ActiveModel = task.into();
+    let mut active_task: task::ActiveModel = task.clone().into();
 
-    task.title = Set(task_request.title);
-    task.description = Set(task_request.description.unwrap());
-    task.status = Set(task_request.status);
+    apply_field(&mut active_task, &event.field, event.old_value).map_err(AppError::GenericError)?;
 
-    let task_serializer: TaskSerializer = task.update(&app_state.db).await?.into();
+    let task_serializer = save_task(&app_state, user, task, active_task).await?;
 
-    Ok(JsonResponse::data(task_serializer, None))
+    Ok(JsonResponse::data(
+        V::Task::from(task_serializer),
+        Some("Task reverted successfully".to_string()),
+    ))
 }
 
-pub async fn delete_task(
+/// Moves a task inside its board column or to another one. The task is put
+/// right before `before_id`, right after `after_id`, or at the bottom of the
+/// column when neither is given.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/reposition",
+    tag = "tasks",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    request_body = RepositionTaskRequest,
+    responses((status = 200, body = DataResponse<TaskSerializer>)),
+)]
+pub async fn reposition_task<V: ApiVersion>(
     State(app_state): State<Arc<AppState>>,
-    Path(task_id): Path<i32>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+    Json(reposition_request): Json<RepositionTaskRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let res = task::Entity::delete_by_id(task_id)
+    reposition_request.validate()?;
+
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    if reposition_request.status != task.status {
+        ensure_wip_limit(
+            &app_state.db,
+            task.project_id,
+            &reposition_request.status,
+            Some(task.id),
+        )
+        .await?;
+    }
+
+    let rank = match rank_for_position(&app_state.db, &task, &reposition_request).await? {
+        Some(rank) => rank,
+        None => {
+            rebalance_column(&app_state.db, task.project_id, &reposition_request.status).await?;
+
+            rank_for_position(&app_state.db, &task, &reposition_request)
+                .await?
+                .ok_or(AppError::GenericError("Cannot rank task.".to_string()))?
+        }
+    };
+
+    let mut active_task: task::ActiveModel = task.clone().into();
+
+    active_task.status = Set(reposition_request.status);
+    active_task.rank = Set(rank);
+
+    let task_serializer = save_task(&app_state, user, task, active_task).await?;
+
+    Ok(JsonResponse::data(V::Task::from(task_serializer), None))
+}
+
+/// Rank placing `task` at the requested position, or `None` when its new
+/// neighbours leave no room for a short enough key.
+async fn rank_for_position<C>(
+    db: &C,
+    task: &task::Model,
+    reposition_request: &RepositionTaskRequest,
+) -> Result<Option<String>, AppError>
+where
+    C: ConnectionTrait,
+{
+    let status = &reposition_request.status;
+
+    let column = task::Entity::find()
+        .filter(in_column(task.project_id, status))
+        .filter(task::Column::Id.ne(task.id));
+
+    let find_anchor = |anchor_id: i32| {
+        let anchor_query = column.clone().filter(task::Column::Id.eq(anchor_id));
+
+        async move {
+            anchor_query
+                .one(db)
+                .await?
+                .ok_or(AppError::BadRequest(format!(
+                    "Task {} is not in the {} column.",
+                    anchor_id, status
+                )))
+        }
+    };
+
+    let (lower, upper) = match (reposition_request.before_id, reposition_request.after_id) {
+        (Some(before_id), _) => {
+            let anchor = find_anchor(before_id).await?;
+
+            let lower = column
+                .clone()
+                .filter(task::Column::Rank.lt(anchor.rank.clone()))
+                .order_by(task::Column::Rank, sea_orm::Order::Desc)
+                .one(db)
+                .await?
+                .map(|task| task.rank);
+
+            (lower, Some(anchor.rank))
+        }
+        (None, Some(after_id)) => {
+            let anchor = find_anchor(after_id).await?;
+
+            let upper = column
+                .clone()
+                .filter(task::Column::Rank.gt(anchor.rank.clone()))
+                .order_by(task::Column::Rank, sea_orm::Order::Asc)
+                .one(db)
+                .await?
+                .map(|task| task.rank);
+
+            (Some(anchor.rank), upper)
+        }
+        (None, None) => {
+            let lower = column
+                .clone()
+                .order_by(task::Column::Rank, sea_orm::Order::Desc)
+                .one(db)
+                .await?
+                .map(|task| task.rank);
+
+            (lower, None)
+        }
+    };
+
+    Ok(rank::between(lower.as_deref(), upper.as_deref())
+        .filter(|rank| rank.len() <= rank::MAX_LENGTH))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/assignees",
+    tag = "tasks",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    request_body = AssignTaskRequest,
+    responses((status = 200, body = DataResponse<TaskSerializer>)),
+)]
+pub async fn assign_task<V: ApiVersion>(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+    Json(assign_request): Json<AssignTaskRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let task = assign_users(&app_state, user, task_ref, assign_request).await?;
+
+    Ok(JsonResponse::data(V::Task::from(task), None))
+}
+
+/// Assigns the task referenced by `task_ref` to the requested users, who must
+/// all exist.
+pub async fn assign_users(
+    app_state: &AppState,
+    user: user::Model,
+    task_ref: TaskRef,
+    assign_request: AssignTaskRequest,
+) -> Result<TaskSerializer, AppError> {
+    assign_request.validate()?;
+
+    let task = find_task(app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    let user_ids: HashSet<i32> = user::Entity::find()
+        .filter(user::Column::Id.is_in(assign_request.user_ids.clone()))
+        .all(&app_state.db)
+        .await?
+        .into_iter()
+        .map(|user| user.id)
+        .collect();
+
+    if let Some(user_id) = assign_request
+        .user_ids
+        .iter()
+        .find(|user_id| !user_ids.contains(user_id))
+    {
+        return Err(AppError::BadRequest(format!("User {} not found.", user_id)));
+    }
+
+    add_assignees(app_state, user, &task, user_ids).await?;
+
+    publish_task_change(app_state, task, vec![]).await
+}
+
+/// Fails unless every user of `user_ids` can see `task`, as only they may be
+/// assigned to it or watch it.
+async fn ensure_in_audience<'a, C>(
+    db: &C,
+    task: &task::Model,
+    user_ids: impl IntoIterator<Item = &'a i32>,
+) -> Result<(), AppError>
+where
+    C: ConnectionTrait,
+{
+    let audience = audience(db, task).await?;
+
+    if let Some(user_id) = user_ids
+        .into_iter()
+        .find(|user_id| !audience.contains(user_id))
+    {
+        return Err(AppError::BadRequest(format!(
+            "User {} is not a member of the project of the task.",
+            user_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Assigns `task` to the users of `user_ids` it is not assigned to yet, who
+/// are notified by `user`.
+pub async fn add_assignees(
+    app_state: &AppState,
+    user: user::Model,
+    task: &task::Model,
+    user_ids: HashSet<i32>,
+) -> Result<(), AppError> {
+    ensure_in_audience(&app_state.db, task, &user_ids).await?;
+
+    let current_assignees: HashSet<i32> = task_assignee::Entity::find()
+        .filter(task_assignee::Column::TaskId.eq(task.id))
+        .all(&app_state.db)
+        .await?
+        .into_iter()
+        .map(|assignee| assignee.user_id)
+        .collect();
+
+    let new_assignees: Vec<i32> = user_ids.difference(&current_assignees).copied().collect();
+
+    if !new_assignees.is_empty() {
+        let txn_task = task.clone();
+
+        app_state
+            .db
+            .transaction::<_, (), DbErr>(|txn| {
+                Box::pin(async move {
+                    task_assignee::Entity::insert_many(new_assignees.iter().map(|user_id| {
+                        task_assignee::ActiveModel {
+                            id: NotSet,
+                            task_id: Set(txn_task.id),
+                            user_id: Set(*user_id),
+                            date_created: NotSet,
+                        }
+                    }))
+                    .exec(txn)
+                    .await?;
+
+                    notification::task_assigned(txn, &user, &txn_task, &new_assignees).await?;
+
+                    Ok(())
+                })
+            })
+            .await
+            .map_err(|e| AppError::GenericError(e.to_string()))?; // should be database error
+    }
+
+    Ok(())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/assignees/{user_id}",
+    tag = "tasks",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    responses((status = 200, body = DataResponse<TaskSerializer>)),
+)]
+pub async fn unassign_task<V: ApiVersion>(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path((task_ref, user_id)): Path<(TaskRef, i32)>,
+) -> Result<impl IntoResponse, AppError> {
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    let previous_audience = audience(&app_state.db, &task).await?;
+
+    let res = task_assignee::Entity::delete_many()
+        .filter(task_assignee::Column::TaskId.eq(task.id))
+        .filter(task_assignee::Column::UserId.eq(user_id))
         .exec(&app_state.db)
         .await?;
 
-    println!("{:?}", res);
+    if res.rows_affected == 0 {
+        return Err(sqlx::Error::RowNotFound.into());
+    }
+
+    let task = publish_task_change(&app_state, task, previous_audience).await?;
+
+    Ok(JsonResponse::data(V::Task::from(task), None))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/watch",
+    tag = "tasks",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+pub async fn watch_task(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+) -> Result<impl IntoResponse, AppError> {
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::View).await?;
+
+    ensure_in_audience(&app_state.db, &task, [&user.id]).await?;
+
+    task_watcher::Entity::insert(task_watcher::ActiveModel {
+        id: NotSet,
+        task_id: Set(task.id),
+        user_id: Set(user.id),
+        date_created: NotSet,
+    })
+    .on_conflict(
+        OnConflict::columns([task_watcher::Column::TaskId, task_watcher::Column::UserId])
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(&app_state.db)
+    .await?;
 
     Ok(JsonResponse::data(
         None::<String>,
-        Some("Task deleted successfully".to_string()),
+        Some("Watching task".to_string()),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/watch",
+    tag = "tasks",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+pub async fn unwatch_task(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+) -> Result<impl IntoResponse, AppError> {
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::View).await?;
+
+    task_watcher::Entity::delete_many()
+        .filter(task_watcher::Column::TaskId.eq(task.id))
+        .filter(task_watcher::Column::UserId.eq(user.id))
+        .exec(&app_state.db)
+        .await?;
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Stopped watching task".to_string()),
     ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        api_version::V1,
+        models::{
+            _entities::{notification as notification_entity, project_member},
+            project::{add_member, ProjectRole},
+            task_event::{field_value, TRACKED_FIELDS},
+        },
+        testing::{create_project, create_task, create_user, grant, response_data},
+    };
 
     #[tokio::test]
     async fn hello_world() {
         assert_eq!(1, 1);
     }
+
+    #[tokio::test]
+    async fn audience_follows_project_membership() {
+        let app_state = crate::testing::app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let carol = create_user(&app_state, "carol").await;
+        let dave = create_user(&app_state, "dave").await;
+        grant(&app_state, &dave, ProjectPermission::View).await;
+
+        let project = create_project(&app_state, &alice, "ops", &[&bob]).await;
+        let task = create_task(&app_state, &alice, &project, "Deploy").await;
+
+        assert_eq!(
+            audience(&app_state.db, &task).await.unwrap(),
+            vec![alice.id, bob.id, dave.id]
+        );
+
+        let assign = |user_ids| AssignTaskRequest { user_ids };
+        let refused = assign_users(
+            &app_state,
+            alice.clone(),
+            TaskRef::Id(task.id),
+            assign(vec![bob.id, carol.id]),
+        )
+        .await;
+        assert!(matches!(refused, Err(AppError::BadRequest(_))));
+
+        let assigned = assign_users(
+            &app_state,
+            alice.clone(),
+            TaskRef::Id(task.id),
+            assign(vec![bob.id]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(assigned.assignees.len(), 1);
+
+        project_member::Entity::delete_many()
+            .filter(project_member::Column::ProjectId.eq(project.id))
+            .filter(project_member::Column::UserId.eq(bob.id))
+            .exec(&app_state.db)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            audience(&app_state.db, &task).await.unwrap(),
+            vec![alice.id, dave.id]
+        );
+    }
+
+    /// Changes one field of an update request.
+    type Change = fn(&mut UpdateTaskRequest);
+
+    fn unchanged(task: &task::Model) -> UpdateTaskRequest {
+        UpdateTaskRequest {
+            title: task.title.clone(),
+            description: None,
+            status: task.status.clone(),
+            auto_complete: None,
+            estimate_minutes: None,
+            due_date: None,
+            priority: None,
+            labels: None,
+            recurrence: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_description_is_kept() {
+        let app_state = crate::testing::app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let project = create_project(&app_state, &alice, "ops", &[]).await;
+        let task = create_task(&app_state, &alice, &project, "Deploy").await;
+
+        let mut request = unchanged(&task);
+        request.description = Some("Roll out the release".to_string());
+        modify_task(&app_state, alice.clone(), TaskRef::Id(task.id), request)
+            .await
+            .unwrap();
+
+        let mut request = unchanged(&task);
+        request.title = "Deploy today".to_string();
+        let updated = modify_task(&app_state, alice, TaskRef::Id(task.id), request)
+            .await
+            .unwrap();
+
+        assert_eq!(updated.title, "Deploy today");
+        assert_eq!(updated.description, "Roll out the release");
+    }
+
+    #[tokio::test]
+    async fn each_tracked_field_round_trips_through_history() {
+        let app_state = crate::testing::app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let project = create_project(&app_state, &alice, "ops", &[]).await;
+        let task = create_task(&app_state, &alice, &project, "Deploy").await;
+
+        let changes: [(&str, Change); 8] = [
+            ("title", |request| {
+                request.title = "Deploy today".to_string()
+            }),
+            ("description", |request| {
+                request.description = Some("Roll out the release".to_string())
+            }),
+            ("status", |request| {
+                request.status = "in_progress".to_string()
+            }),
+            ("estimate_minutes", |request| {
+                request.estimate_minutes = Some(Some(90))
+            }),
+            ("due_date", |request| {
+                request.due_date = Some(NaiveDate::from_ymd_opt(2025, 3, 14))
+            }),
+            ("priority", |request| {
+                request.priority = Some(Some("high".to_string()))
+            }),
+            ("labels", |request| {
+                request.labels = Some(vec!["ops".to_string(), "release".to_string()])
+            }),
+            ("recurrence", |request| {
+                request.recurrence = Some(Some("FREQ=WEEKLY;INTERVAL=2".to_string()))
+            }),
+        ];
+        assert_eq!(changes.map(|(field, _)| field), TRACKED_FIELDS);
+
+        for (field, change) in changes {
+            let before = task::Entity::find_by_id(task.id)
+                .one(&app_state.db)
+                .await
+                .unwrap()
+                .unwrap();
+            let original = field_value(&before, field);
+
+            let mut request = unchanged(&before);
+            change(&mut request);
+            modify_task(&app_state, alice.clone(), TaskRef::Id(task.id), request)
+                .await
+                .unwrap();
+
+            let event = task_event::Entity::find()
+                .filter(task_event::Column::TaskId.eq(task.id))
+                .order_by_desc(task_event::Column::Id)
+                .one(&app_state.db)
+                .await
+                .unwrap()
+                .unwrap();
+            let changed = task::Entity::find_by_id(task.id)
+                .one(&app_state.db)
+                .await
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(event.field, field);
+            assert_eq!(event.old_value, original, "old value of {}", field);
+            assert_eq!(event.new_value, field_value(&changed, field));
+            assert_ne!(event.new_value, original, "new value of {}", field);
+
+            revert_task_event::<V1>(
+                State(app_state.clone()),
+                Extension(alice.clone()),
+                Path((TaskRef::Id(task.id), event.id)),
+            )
+            .await
+            .unwrap();
+
+            let reverted = task::Entity::find_by_id(task.id)
+                .one(&app_state.db)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(field_value(&reverted, field), original, "{}", field);
+
+            let undo = task_event::Entity::find()
+                .filter(task_event::Column::TaskId.eq(task.id))
+                .order_by_desc(task_event::Column::Id)
+                .one(&app_state.db)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(undo.field, field);
+            assert_eq!(undo.old_value, event.new_value);
+            assert_eq!(undo.new_value, original);
+        }
+    }
+
+    async fn assign(
+        app_state: &Arc<AppState>,
+        user: &user::Model,
+        task: &task::Model,
+        user_ids: Vec<i32>,
+    ) -> Result<serde_json::Value, AppError> {
+        let response = assign_task::<V1>(
+            State(app_state.clone()),
+            Extension(user.clone()),
+            Path(TaskRef::Id(task.id)),
+            Json(AssignTaskRequest { user_ids }),
+        )
+        .await?;
+
+        Ok(response_data(response).await)
+    }
+
+    async fn watch(
+        app_state: &Arc<AppState>,
+        user: &user::Model,
+        task: &task::Model,
+    ) -> Result<(), AppError> {
+        watch_task(
+            State(app_state.clone()),
+            Extension(user.clone()),
+            Path(TaskRef::Id(task.id)),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn assigning_notifies_members_and_refuses_others() {
+        let app_state = crate::testing::app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let carol = create_user(&app_state, "carol").await;
+        let project = create_project(&app_state, &alice, "ops", &[&bob]).await;
+        let task = create_task(&app_state, &alice, &project, "Deploy").await;
+
+        let assigned = assign(&app_state, &alice, &task, vec![bob.id])
+            .await
+            .unwrap();
+        let assignees: Vec<i64> = assigned["assignees"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|assignee| assignee["id"].as_i64().unwrap())
+            .collect();
+        assert_eq!(assignees, vec![bob.id as i64]);
+
+        let notifications = notification_entity::Entity::find()
+            .filter(notification_entity::Column::UserId.eq(bob.id))
+            .all(&app_state.db)
+            .await
+            .unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].kind, "task_assigned");
+        assert_eq!(notifications[0].task_id, Some(task.id));
+
+        let unknown = assign(&app_state, &alice, &task, vec![bob.id, 9999]).await;
+        assert!(matches!(unknown, Err(AppError::BadRequest(_))));
+
+        let outsider = assign(&app_state, &alice, &task, vec![carol.id]).await;
+        assert!(matches!(outsider, Err(AppError::BadRequest(_))));
+
+        let by_outsider = assign(&app_state, &carol, &task, vec![bob.id]).await;
+        assert!(matches!(by_outsider, Err(AppError::Unauthorized(_))));
+
+        let empty = assign(&app_state, &alice, &task, vec![]).await;
+        assert!(matches!(empty, Err(AppError::Validation(_))));
+
+        let assignees = task_assignee::Entity::find()
+            .filter(task_assignee::Column::TaskId.eq(task.id))
+            .count(&app_state.db)
+            .await
+            .unwrap();
+        assert_eq!(assignees, 1);
+    }
+
+    #[tokio::test]
+    async fn assigning_twice_keeps_one_assignment() {
+        let app_state = crate::testing::app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let carol = create_user(&app_state, "carol").await;
+        let project = create_project(&app_state, &alice, "ops", &[&bob, &carol]).await;
+        let task = create_task(&app_state, &alice, &project, "Deploy").await;
+
+        assign(&app_state, &alice, &task, vec![bob.id, bob.id])
+            .await
+            .unwrap();
+        let assigned = assign(&app_state, &alice, &task, vec![bob.id, carol.id])
+            .await
+            .unwrap();
+        assert_eq!(assigned["assignees"].as_array().unwrap().len(), 2);
+
+        for user in [&bob, &carol] {
+            let assignments = task_assignee::Entity::find()
+                .filter(task_assignee::Column::TaskId.eq(task.id))
+                .filter(task_assignee::Column::UserId.eq(user.id))
+                .count(&app_state.db)
+                .await
+                .unwrap();
+            assert_eq!(assignments, 1);
+
+            let notifications = notification_entity::Entity::find()
+                .filter(notification_entity::Column::UserId.eq(user.id))
+                .count(&app_state.db)
+                .await
+                .unwrap();
+            assert_eq!(notifications, 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn only_users_who_see_the_task_can_watch_it() {
+        let app_state = crate::testing::app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let carol = create_user(&app_state, "carol").await;
+        let dave = create_user(&app_state, "dave").await;
+        let erin = create_user(&app_state, "erin").await;
+        grant(&app_state, &dave, ProjectPermission::View).await;
+
+        let project = create_project(&app_state, &alice, "ops", &[&bob]).await;
+        add_member(&app_state.db, project.id, erin.id, ProjectRole::Viewer)
+            .await
+            .unwrap();
+        let task = create_task(&app_state, &alice, &project, "Deploy").await;
+
+        for user in [&bob, &dave, &erin] {
+            watch(&app_state, user, &task).await.unwrap();
+            // Watching again is not an error.
+            watch(&app_state, user, &task).await.unwrap();
+        }
+
+        let refused = watch(&app_state, &carol, &task).await;
+        assert!(matches!(refused, Err(AppError::Unauthorized(_))));
+
+        let watcher_ids: Vec<i32> = task_watcher::Entity::find()
+            .filter(task_watcher::Column::TaskId.eq(task.id))
+            .order_by_asc(task_watcher::Column::UserId)
+            .all(&app_state.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|watcher| watcher.user_id)
+            .collect();
+        assert_eq!(watcher_ids, vec![bob.id, dave.id, erin.id]);
+
+        unwatch_task(
+            State(app_state.clone()),
+            Extension(erin.clone()),
+            Path(TaskRef::Id(task.id)),
+        )
+        .await
+        .unwrap();
+
+        let watchers = task_watcher::Entity::find()
+            .filter(task_watcher::Column::TaskId.eq(task.id))
+            .filter(task_watcher::Column::UserId.eq(erin.id))
+            .count(&app_state.db)
+            .await
+            .unwrap();
+        assert_eq!(watchers, 0);
+    }
+
+    #[tokio::test]
+    async fn update_with_unknown_status_is_rejected() {
+        let app_state = crate::testing::app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let project = create_project(&app_state, &alice, "ops", &[]).await;
+        let task = create_task(&app_state, &alice, &project, "Deploy").await;
+
+        let mut request = unchanged(&task);
+        request.title = "Deploy today".to_string();
+        request.status = "archived".to_string();
+
+        let updated = update_task::<V1>(
+            State(app_state.clone()),
+            Extension(alice),
+            Path(TaskRef::Id(task.id)),
+            Json(request),
+        )
+        .await;
+        assert!(matches!(updated, Err(AppError::Validation(_))));
+
+        let stored = task::Entity::find_by_id(task.id)
+            .one(&app_state.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored, task);
+    }
+
+    #[tokio::test]
+    async fn tasks_are_found_by_id_or_key() {
+        use axum::{
+            body::{to_bytes, Body},
+            http::{header, Request, StatusCode},
+        };
+        use tower::ServiceExt;
+
+        let app_state = crate::testing::app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let project = create_project(&app_state, &alice, "ops", &[]).await;
+        let task = create_task(&app_state, &alice, &project, "Deploy").await;
+        create_task(&app_state, &alice, &project, "Release").await;
+
+        let app = crate::create_router(app_state).await;
+        let token = crate::testing::token_of(&alice).await;
+
+        let get = |task_ref: String| {
+            let app = app.clone();
+            let token = token.clone();
+
+            async move {
+                let response = app
+                    .oneshot(
+                        Request::builder()
+                            .uri(format!("/api/tasks/{}", task_ref))
+                            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                let status = response.status();
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body).unwrap_or_default(),
+                )
+            }
+        };
+
+        let (status, by_id) = get(task.id.to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(by_id["data"]["key"], "OPS-1");
+
+        for key in ["OPS-1", "ops-1"] {
+            let (status, by_key) = get(key.to_string()).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(by_key["data"], by_id["data"]);
+        }
+
+        let (status, _) = get("OPS-99".to_string()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = get("WEB-1".to_string()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
 }
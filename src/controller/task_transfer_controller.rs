@@ -0,0 +1,934 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel, PaginatorTrait,
+    QueryFilter, Select, Set,
+};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use validator::Validate;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    controller::{
+        project_controller::find_project,
+        task_controller::{ensure_room_for, insert_tasks, pick_columns, task_list_query},
+    },
+    csv,
+    error::AppError,
+    form::task_form::{CreateTaskRequest, ImportTasksRequest},
+    models::{
+        _entities::{project, task, user},
+        project::{inbox, ProjectPermission},
+        task::{with_details, COLUMNS, IMPORT_FIELDS, STATUSES},
+    },
+    plain_text::{self, PlainTask},
+    serializer::{ImportErrorSerializer, ImportReportSerializer, TaskSerializer},
+    AppState,
+};
+
+/// Tasks loaded from the database at a time while exporting.
+const EXPORT_BATCH_SIZE: u64 = 200;
+
+/// Most rows a single import can contain.
+const MAX_IMPORT_ROWS: usize = 5000;
+
+/// The field (or column) and value pairs of one imported row, or why it
+/// could not be read.
+pub type ImportRow = Result<Vec<(String, Option<String>)>, String>;
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/tasks/export", get(export_tasks))
+        .route("/tasks/import", post(import_tasks))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Csv,
+    Json,
+    Ndjson,
+    TodoTxt,
+    Markdown,
+}
+
+impl ExportFormat {
+    fn parse(format: &str) -> Option<Self> {
+        match format {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            "ndjson" => Some(ExportFormat::Ndjson),
+            "todotxt" => Some(ExportFormat::TodoTxt),
+            "markdown" => Some(ExportFormat::Markdown),
+            _ => None,
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Ndjson => "application/x-ndjson",
+            ExportFormat::TodoTxt => "text/plain; charset=utf-8",
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::TodoTxt => "txt",
+            ExportFormat::Markdown => "md",
+        }
+    }
+
+    fn is_plain_text(&self) -> bool {
+        matches!(self, ExportFormat::TodoTxt | ExportFormat::Markdown)
+    }
+}
+
+/// Key prefixes of the projects of `tasks`, by project id.
+async fn project_prefixes(
+    db: &DatabaseConnection,
+    tasks: &[task::Model],
+) -> Result<HashMap<i32, String>, DbErr> {
+    let project_ids: Vec<i32> = tasks.iter().filter_map(|task| task.project_id).collect();
+
+    Ok(project::Entity::find()
+        .filter(project::Column::Id.is_in(project_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|project| (project.id, project.key_prefix))
+        .collect())
+}
+
+/// Writes `tasks` as todo.txt lines or Markdown checklist items.
+async fn write_plain_text(
+    db: &DatabaseConnection,
+    chunk: &mut String,
+    tasks: Vec<task::Model>,
+    format: ExportFormat,
+) -> Result<(), DbErr> {
+    let prefixes = project_prefixes(db, &tasks).await?;
+
+    for task in &tasks {
+        let prefix = task
+            .project_id
+            .and_then(|project_id| prefixes.get(&project_id));
+        let task = PlainTask::from_task(task, prefix.map(String::as_str));
+
+        chunk.push_str(&match format {
+            ExportFormat::Markdown => plain_text::to_markdown(&task),
+            _ => plain_text::to_todo_txt(&task),
+        });
+        chunk.push('\n');
+    }
+
+    Ok(())
+}
+
+/// Flattens a serialized task field into a CSV cell. Lists are joined with
+/// commas, assignees by username, and checklists written as `done/total`.
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| match item.get("username") {
+                Some(username) => csv_cell(username),
+                None => csv_cell(item),
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        Value::Object(fields) if fields.contains_key("done") => {
+            format!("{}/{}", fields["done"], fields["total"])
+        }
+        value => value.to_string(),
+    }
+}
+
+/// Writes the tasks of `task_query` to `sender` one batch at a time. Stops
+/// early when the client goes away.
+async fn write_export(
+    db: DatabaseConnection,
+    task_query: Select<task::Entity>,
+    format: ExportFormat,
+    columns: Vec<String>,
+    sender: &mpsc::Sender<Result<String, DbErr>>,
+) -> Result<(), DbErr> {
+    let mut chunk = String::new();
+
+    match format {
+        ExportFormat::Csv => csv::write_record(&mut chunk, &columns),
+        ExportFormat::Json => chunk.push('['),
+        ExportFormat::Ndjson | ExportFormat::TodoTxt | ExportFormat::Markdown => {}
+    }
+
+    let mut pages = task_query.paginate(&db, EXPORT_BATCH_SIZE);
+    let mut first = true;
+
+    while let Some(tasks) = pages.fetch_and_next().await? {
+        if format.is_plain_text() {
+            write_plain_text(&db, &mut chunk, tasks, format).await?;
+
+            if sender.send(Ok(std::mem::take(&mut chunk))).await.is_err() {
+                return Ok(());
+            }
+
+            continue;
+        }
+
+        for task in with_details(&db, tasks).await? {
+            let task = pick_columns(TaskSerializer::from(task), &columns);
+
+            match format {
+                ExportFormat::Csv => csv::write_record(
+                    &mut chunk,
+                    columns.iter().map(|column| csv_cell(&task[column])),
+                ),
+                ExportFormat::Json => {
+                    if !first {
+                        chunk.push(',');
+                    }
+                    chunk.push_str(&task.to_string());
+                }
+                _ => {
+                    chunk.push_str(&task.to_string());
+                    chunk.push('\n');
+                }
+            }
+
+            first = false;
+        }
+
+        if sender.send(Ok(std::mem::take(&mut chunk))).await.is_err() {
+            return Ok(());
+        }
+    }
+
+    if format == ExportFormat::Json {
+        chunk.push(']');
+    }
+
+    let _ = sender.send(Ok(chunk)).await;
+
+    Ok(())
+}
+
+/// Streams the tasks `get_tasks` would list with the same parameters, every
+/// page of them, as CSV, a JSON array, newline delimited JSON, todo.txt or a
+/// Markdown checklist.
+#[utoipa::path(
+    get,
+    path = "/api/tasks/export",
+    tag = "tasks",
+    params(
+        ("format" = Option<String>, Query, description = "`json` (default), `ndjson`, `csv`, `todotxt` or `markdown`. The other parameters of the task list apply"),
+    ),
+    responses((status = 200, description = "The tasks, as a file download", content((Vec<TaskSerializer> = "application/json"), (String = "application/x-ndjson"), (String = "text/csv"), (String = "text/plain"), (String = "text/markdown")))),
+)]
+#[axum::debug_handler]
+pub async fn export_tasks(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(mut params): Query<HashMap<String, String>>,
+) -> Result<Response, AppError> {
+    let format = params.remove("format").unwrap_or("json".to_string());
+    let format = ExportFormat::parse(&format).ok_or(AppError::BadRequest(format!(
+        "Unknown export format {}.",
+        format
+    )))?;
+
+    let (task_query, mut columns) = task_list_query(&app_state, &user, &mut params).await?;
+
+    if format == ExportFormat::Csv && columns.is_empty() {
+        columns = COLUMNS.iter().map(|column| column.to_string()).collect();
+    }
+
+    let (sender, receiver) = mpsc::channel(4);
+    let db = app_state.db.clone();
+
+    tokio::spawn(async move {
+        if let Err(e) = write_export(db, task_query, format, columns, &sender).await {
+            tracing::error!("Task export failed: {}", e);
+            let _ = sender.send(Err(e)).await;
+        }
+    });
+
+    let disposition = format!("attachment; filename=\"tasks.{}\"", format.extension());
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(ReceiverStream::new(receiver)),
+    )
+        .into_response())
+}
+
+/// Turns a JSON value into the text of a field. Lists of strings, such as
+/// labels, are joined with commas.
+pub fn json_field(value: &Value) -> Result<Option<String>, String> {
+    match value {
+        Value::Null => Ok(None),
+        Value::String(text) => Ok(Some(text.clone())),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| {
+                item.as_str()
+                    .map(str::to_string)
+                    .ok_or("Lists can only contain strings.".to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|items| Some(items.join(","))),
+        Value::Object(_) => Err("Nested objects cannot be imported.".to_string()),
+        value => Ok(Some(value.to_string())),
+    }
+}
+
+fn json_row(value: Value) -> Result<Vec<(String, Option<String>)>, String> {
+    let Value::Object(fields) = value else {
+        return Err("Each row must be a JSON object.".to_string());
+    };
+
+    fields
+        .into_iter()
+        .map(|(key, value)| Ok((key, json_field(&value)?)))
+        .collect()
+}
+
+/// Splits `data` into rows of column (or key) and value pairs. Rows that
+/// cannot be read are returned as errors.
+fn parse_rows(format: &str, data: &str) -> Result<Vec<ImportRow>, AppError> {
+    match format {
+        "csv" => {
+            let mut records = csv::parse(data).map_err(AppError::BadRequest)?.into_iter();
+            let header = records.next().unwrap_or_default();
+
+            Ok(records
+                .map(|record| {
+                    if record.len() != header.len() {
+                        return Err(format!(
+                            "Expected {} columns, found {}.",
+                            header.len(),
+                            record.len()
+                        ));
+                    }
+
+                    Ok(header
+                        .iter()
+                        .cloned()
+                        .zip(record.into_iter().map(Some))
+                        .collect())
+                })
+                .collect())
+        }
+        "json" => {
+            let rows: Vec<Value> = serde_json::from_str(data)
+                .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+
+            Ok(rows.into_iter().map(json_row).collect())
+        }
+        _ => Ok(data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line)
+                    .map_err(|e| format!("Invalid JSON: {}", e))
+                    .and_then(json_row)
+            })
+            .collect()),
+    }
+}
+
+/// The import row of a task read from todo.txt or Markdown.
+fn plain_row(task: PlainTask, project_id: Option<i32>) -> Vec<(String, Option<String>)> {
+    vec![
+        ("title".to_string(), Some(task.title)),
+        ("status".to_string(), Some(task.status)),
+        ("priority".to_string(), task.priority),
+        ("labels".to_string(), Some(task.labels.join(","))),
+        (
+            "due_date".to_string(),
+            task.due_date.map(|due_date| due_date.to_string()),
+        ),
+        (
+            "estimate_minutes".to_string(),
+            task.estimate_minutes.map(|minutes| minutes.to_string()),
+        ),
+        (
+            "project_id".to_string(),
+            project_id.map(|project_id| project_id.to_string()),
+        ),
+    ]
+}
+
+/// Reads the todo.txt lines or Markdown checklist items of `data`. Projects
+/// are named by key prefix in todo.txt, and unknown ones make their row
+/// invalid.
+async fn parse_plain_rows(
+    app_state: &AppState,
+    format: &str,
+    data: &str,
+) -> Result<Vec<ImportRow>, AppError> {
+    if format == "markdown" {
+        return Ok(data
+            .lines()
+            .filter_map(plain_text::parse_markdown)
+            .map(|task| Ok(plain_row(task, None)))
+            .collect());
+    }
+
+    let tasks: Vec<Result<PlainTask, String>> = data
+        .lines()
+        .filter_map(plain_text::parse_todo_txt)
+        .collect();
+
+    let prefixes: Vec<&String> = tasks
+        .iter()
+        .filter_map(|task| task.as_ref().ok()?.project.as_ref())
+        .collect();
+
+    let project_ids: HashMap<String, i32> = project::Entity::find()
+        .filter(project::Column::KeyPrefix.is_in(prefixes))
+        .all(&app_state.db)
+        .await?
+        .into_iter()
+        .map(|project| (project.key_prefix, project.id))
+        .collect();
+
+    Ok(tasks
+        .into_iter()
+        .map(|task| {
+            let task = task?;
+            let project_id = match &task.project {
+                Some(prefix) => Some(
+                    *project_ids
+                        .get(prefix)
+                        .ok_or(format!("Unknown project +{}.", prefix))?,
+                ),
+                None => None,
+            };
+
+            Ok(plain_row(task, project_id))
+        })
+        .collect())
+}
+
+/// Parses the value of `field` in `row`, recording an error when it is
+/// invalid.
+fn parse_field<T: FromStr>(
+    row: &HashMap<String, String>,
+    field: &str,
+    errors: &mut Vec<(Option<String>, String)>,
+) -> Option<T> {
+    let value = row.get(field)?;
+
+    match value.parse() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            errors.push((Some(field.to_string()), format!("Invalid value {}", value)));
+            None
+        }
+    }
+}
+
+/// Builds the creation request of one row, checked with the same rules as
+/// `create_task`. Empty values count as missing.
+pub fn row_request(
+    user: &user::Model,
+    mapping: &HashMap<String, String>,
+    row: Vec<(String, Option<String>)>,
+) -> Result<CreateTaskRequest, Vec<(Option<String>, String)>> {
+    let mut row: HashMap<String, String> = row
+        .into_iter()
+        .filter_map(|(column, value)| {
+            let field = mapping.get(&column).cloned().unwrap_or(column);
+
+            (IMPORT_FIELDS.contains(&field.as_str()))
+                .then_some(value)
+                .flatten()
+                .filter(|value| !value.trim().is_empty())
+                .map(|value| (field, value))
+        })
+        .collect();
+
+    let mut errors = Vec::new();
+
+    let task_request = CreateTaskRequest {
+        project_id: parse_field(&row, "project_id", &mut errors),
+        auto_complete: parse_field(&row, "auto_complete", &mut errors).unwrap_or(false),
+        estimate_minutes: parse_field(&row, "estimate_minutes", &mut errors),
+        due_date: parse_field(&row, "due_date", &mut errors),
+        labels: row
+            .get("labels")
+            .map(|labels| {
+                labels
+                    .split(',')
+                    .map(|label| label.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        title: row.remove("title").unwrap_or_default(),
+        description: row.remove("description").unwrap_or_default(),
+        status: row.remove("status").unwrap_or(STATUSES[0].to_string()),
+        priority: row.remove("priority"),
+        recurrence: row.remove("recurrence"),
+        user_id: user.id,
+    };
+
+    if let Err(validation_errors) = task_request.validate() {
+        for (field, field_errors) in validation_errors.field_errors() {
+            for error in field_errors {
+                errors.push((
+                    Some(field.to_string()),
+                    error
+                        .message
+                        .as_ref()
+                        .map_or(error.code.to_string(), |message| message.to_string()),
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(task_request)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Finds the project imported rows go to, `None` standing for the Inbox.
+async fn import_project(
+    app_state: &AppState,
+    user: &user::Model,
+    project_id: Option<i32>,
+) -> Result<project::Model, String> {
+    let project = match project_id {
+        Some(project_id) => find_project(
+            &app_state.db,
+            user,
+            project_id,
+            ProjectPermission::ChangeTasks,
+        )
+        .await
+        .map_err(|_| format!("Tasks cannot be added to project {}.", project_id))?,
+        None => inbox(&app_state.db, user)
+            .await
+            .map_err(|e| e.to_string())?,
+    };
+
+    if project.is_archived {
+        return Err("Tasks cannot be added to an archived project.".to_string());
+    }
+
+    Ok(project)
+}
+
+/// Imports tasks from CSV, JSON, newline delimited JSON, todo.txt or a
+/// Markdown checklist. Every row is
+/// validated first, and tasks are only created when all of them are valid,
+/// in a single transaction. With `dry_run` nothing is created.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/import",
+    tag = "tasks",
+    request_body = ImportTasksRequest,
+    responses((status = 200, body = DataResponse<ImportReportSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn import_tasks(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Json(import_request): Json<ImportTasksRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    import_request.validate()?;
+
+    let rows = match import_request.format.as_str() {
+        "todotxt" | "markdown" => {
+            parse_plain_rows(&app_state, &import_request.format, &import_request.data).await?
+        }
+        format => parse_rows(format, &import_request.data)?,
+    };
+
+    let (report, message) = import_rows(
+        &app_state,
+        &user,
+        rows,
+        &import_request.mapping,
+        import_request.project_id,
+        import_request.dry_run,
+    )
+    .await?;
+
+    Ok(JsonResponse::data(report, message))
+}
+
+/// Validates every row and, unless it is a dry run or some row is invalid,
+/// creates their tasks in a single transaction. Rows go to `project_id`, or
+/// the Inbox, unless they name a project themselves.
+pub async fn import_rows(
+    app_state: &AppState,
+    user: &user::Model,
+    rows: Vec<ImportRow>,
+    mapping: &HashMap<String, String>,
+    project_id: Option<i32>,
+    dry_run: bool,
+) -> Result<(ImportReportSerializer, Option<String>), AppError> {
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(AppError::BadRequest(format!(
+            "An import can contain at most {} rows.",
+            MAX_IMPORT_ROWS
+        )));
+    }
+
+    let total_rows = rows.len();
+
+    let mut projects: HashMap<Option<i32>, Result<project::Model, String>> = HashMap::new();
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, row) in rows.into_iter().enumerate() {
+        let row_errors = |row_errors: Vec<(Option<String>, String)>| {
+            row_errors
+                .into_iter()
+                .map(move |(field, message)| ImportErrorSerializer {
+                    row: i + 1,
+                    field,
+                    message,
+                })
+        };
+
+        let task_request = match row
+            .map_err(|message| vec![(None, message)])
+            .and_then(|row| row_request(user, mapping, row))
+        {
+            Ok(task_request) => task_request,
+            Err(invalid) => {
+                errors.extend(row_errors(invalid));
+                continue;
+            }
+        };
+
+        let project_id = task_request.project_id.or(project_id);
+
+        let project = match projects.get(&project_id) {
+            Some(project) => project.clone(),
+            None => {
+                let project = import_project(app_state, user, project_id).await;
+                projects.insert(project_id, project.clone());
+                project
+            }
+        };
+
+        match project {
+            Ok(project) => {
+                let mut task = task_request.into_active_model();
+
+                task.project_id = Set(Some(project.id));
+                tasks.push((task, Vec::new()));
+            }
+            Err(message) => {
+                errors.extend(row_errors(vec![(Some("project_id".to_string()), message)]));
+            }
+        }
+    }
+
+    let mut report = ImportReportSerializer {
+        dry_run,
+        total_rows,
+        valid_rows: tasks.len(),
+        imported: 0,
+        task_ids: Vec::new(),
+        errors,
+    };
+
+    if !report.errors.is_empty() {
+        let message =
+            (!dry_run).then(|| "No tasks were imported because some rows are invalid.".to_string());
+
+        return Ok((report, message));
+    }
+
+    if dry_run {
+        ensure_room_for(&app_state.db, tasks.iter().map(|(task, _)| task)).await?;
+
+        return Ok((report, None));
+    }
+
+    let tasks = insert_tasks(app_state, tasks).await?;
+
+    report.imported = tasks.len();
+    report.task_ids = tasks.iter().map(|task| task.id).collect();
+
+    Ok((report, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use axum::body::to_bytes;
+
+    use super::*;
+    use crate::testing::{app_state, create_project, create_user, response_data};
+
+    fn import_request(
+        format: &str,
+        data: String,
+        mapping: &[(&str, &str)],
+        project_id: Option<i32>,
+        dry_run: bool,
+    ) -> ImportTasksRequest {
+        ImportTasksRequest {
+            format: format.to_string(),
+            data,
+            mapping: mapping
+                .iter()
+                .map(|(column, field)| (column.to_string(), field.to_string()))
+                .collect(),
+            dry_run,
+            project_id,
+        }
+    }
+
+    async fn import(
+        app_state: &Arc<AppState>,
+        user: &user::Model,
+        request: ImportTasksRequest,
+    ) -> Value {
+        response_data(
+            import_tasks(
+                State(app_state.clone()),
+                Extension(user.clone()),
+                Json(request),
+            )
+            .await
+            .unwrap(),
+        )
+        .await
+    }
+
+    async fn task_count(app_state: &AppState) -> u64 {
+        task::Entity::find().count(&app_state.db).await.unwrap()
+    }
+
+    async fn export(
+        app_state: &Arc<AppState>,
+        user: &user::Model,
+        params: &[(&str, &str)],
+    ) -> String {
+        let params = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        let response = export_tasks(
+            State(app_state.clone()),
+            Extension(user.clone()),
+            Query(params),
+        )
+        .await
+        .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    /// Newline delimited JSON rows of `count` tasks with `status`.
+    fn ndjson_rows(prefix: &str, count: usize, status: &str) -> String {
+        (1..=count)
+            .map(|i| format!(r#"{{"title":"{} {}","status":"{}"}}"#, prefix, i, status))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[tokio::test]
+    async fn dry_run_creates_no_tasks() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+
+        let data = "title,status\nDeploy,pending\nRelease,in_progress\n".to_string();
+        let report = import(
+            &app_state,
+            &alice,
+            import_request("csv", data, &[], None, true),
+        )
+        .await;
+
+        assert_eq!(report["dry_run"], true);
+        assert_eq!(report["total_rows"], 2);
+        assert_eq!(report["valid_rows"], 2);
+        assert_eq!(report["imported"], 0);
+        assert!(report["errors"].as_array().unwrap().is_empty());
+        assert_eq!(task_count(&app_state).await, 0);
+    }
+
+    #[tokio::test]
+    async fn mapping_names_the_fields_of_columns() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+
+        let data = "Name,State,Due,Ignored\nDeploy,in_progress,2025-03-01,x\n".to_string();
+        let mapping = [("Name", "title"), ("State", "status"), ("Due", "due_date")];
+        let report = import(
+            &app_state,
+            &alice,
+            import_request("csv", data, &mapping, None, false),
+        )
+        .await;
+        assert_eq!(report["imported"], 1);
+
+        let task_id = report["task_ids"][0].as_i64().unwrap() as i32;
+        let task = task::Entity::find_by_id(task_id)
+            .one(&app_state.db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(task.title, "Deploy");
+        assert_eq!(task.status, "in_progress");
+        assert_eq!(task.due_date, chrono::NaiveDate::from_ymd_opt(2025, 3, 1));
+        assert_eq!(task.user_id, alice.id);
+        assert_eq!(
+            task.project_id,
+            Some(inbox(&app_state.db, &alice).await.unwrap().id)
+        );
+    }
+
+    #[tokio::test]
+    async fn one_invalid_row_imports_nothing() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+
+        let data = [
+            r#"{"title":"Deploy","status":"pending"}"#,
+            r#"{"title":"Release","status":"archived","due_date":"tomorrow"}"#,
+            r#"{"title":"Announce"}"#,
+        ]
+        .join("\n");
+        let report = import(
+            &app_state,
+            &alice,
+            import_request("ndjson", data, &[], None, false),
+        )
+        .await;
+
+        assert_eq!(report["total_rows"], 3);
+        assert_eq!(report["valid_rows"], 2);
+        assert_eq!(report["imported"], 0);
+
+        let mut errors: Vec<(i64, &str)> = report["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| {
+                (
+                    error["row"].as_i64().unwrap(),
+                    error["field"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        errors.sort();
+        assert_eq!(errors, vec![(2, "due_date"), (2, "status")]);
+
+        assert_eq!(task_count(&app_state).await, 0);
+    }
+
+    #[tokio::test]
+    async fn export_streams_every_batch_of_the_visible_tasks() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let ops = create_project(&app_state, &alice, "ops", &[]).await;
+        let secret = create_project(&app_state, &bob, "secret", &[]).await;
+
+        let pending = EXPORT_BATCH_SIZE as usize + 50;
+        let data = [
+            ndjson_rows("Pending", pending, "pending"),
+            ndjson_rows("Started", 20, "in_progress"),
+        ]
+        .join("\n");
+        let report = import(
+            &app_state,
+            &alice,
+            import_request("ndjson", data, &[], Some(ops.id), false),
+        )
+        .await;
+        assert_eq!(report["imported"], pending + 20);
+
+        let report = import(
+            &app_state,
+            &bob,
+            import_request(
+                "ndjson",
+                ndjson_rows("Hidden", 5, "pending"),
+                &[],
+                Some(secret.id),
+                false,
+            ),
+        )
+        .await;
+        assert_eq!(report["imported"], 5);
+
+        let csv = export(
+            &app_state,
+            &alice,
+            &[("format", "csv"), ("status", "pending")],
+        )
+        .await;
+        let mut records = csv::parse(&csv).unwrap().into_iter();
+        let header = records.next().unwrap();
+        let column = |name: &str| header.iter().position(|column| column == name).unwrap();
+        let (id, project_id, status) = (column("id"), column("project_id"), column("status"));
+
+        let records: Vec<Vec<String>> = records.collect();
+        let ids: HashSet<&String> = records.iter().map(|record| &record[id]).collect();
+        assert_eq!(records.len(), pending);
+        assert_eq!(ids.len(), pending);
+        assert!(records
+            .iter()
+            .all(|record| record[project_id] == ops.id.to_string() && record[status] == "pending"));
+
+        let ndjson = export(
+            &app_state,
+            &alice,
+            &[("format", "ndjson"), ("status", "pending")],
+        )
+        .await;
+        let tasks: Vec<Value> = ndjson
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let ids: HashSet<i64> = tasks
+            .iter()
+            .map(|task| task["id"].as_i64().unwrap())
+            .collect();
+        assert_eq!(tasks.len(), pending);
+        assert_eq!(ids.len(), pending);
+        assert!(tasks
+            .iter()
+            .all(|task| task["project_id"] == ops.id && task["status"] == "pending"));
+
+        let hidden = export(
+            &app_state,
+            &alice,
+            &[("format", "ndjson"), ("project_id", &secret.id.to_string())],
+        )
+        .await;
+        assert!(hidden.is_empty());
+    }
+}
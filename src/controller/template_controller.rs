@@ -0,0 +1,412 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::{Days, Utc};
+use sea_orm::{ActiveModelTrait, ActiveValue::NotSet, EntityTrait, QueryFilter, QueryOrder, Set};
+use validator::Validate;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    api_version::ApiVersion,
+    controller::{project_controller::find_project, task_controller::insert_tasks},
+    error::AppError,
+    form::task_form::{InstantiateTemplateRequest, SaveTemplateRequest, TemplateTaskRequest},
+    models::{
+        _entities::{task, task_template, user},
+        project::{inbox, ProjectPermission},
+        task::join_labels,
+        task_template::visible_to,
+    },
+    placeholder,
+    serializer::{TaskSerializer, TaskTemplateSerializer},
+    AppState,
+};
+
+pub async fn get_routes<V: ApiVersion>() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/templates", get(get_templates).post(create_template))
+        .route(
+            "/templates/:template_id",
+            get(get_template)
+                .put(update_template)
+                .delete(delete_template),
+        )
+        .route(
+            "/templates/:template_id/instantiate",
+            post(instantiate_template::<V>),
+        )
+}
+
+/// Finds a template `user` can use, or only one they created with `owned`.
+async fn find_template(
+    app_state: &AppState,
+    user: &user::Model,
+    template_id: i32,
+    owned: bool,
+) -> Result<task_template::Model, AppError> {
+    let template = task_template::Entity::find_by_id(template_id)
+        .filter(visible_to(user.id))
+        .one(&app_state.db)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    if owned && template.user_id != user.id {
+        return Err(AppError::Unauthorized(format!(
+            "Template {} belongs to user {}",
+            template.id, template.user_id
+        )));
+    }
+
+    Ok(template)
+}
+
+fn template_tasks(template: &task_template::Model) -> Result<Vec<TemplateTaskRequest>, AppError> {
+    serde_json::from_str(&template.tasks).map_err(|e| AppError::GenericError(e.to_string()))
+}
+
+/// Names of the placeholders used in `tasks`.
+fn placeholders_of(tasks: &[TemplateTaskRequest]) -> BTreeSet<String> {
+    tasks
+        .iter()
+        .flat_map(|task| {
+            std::iter::once(&task.title)
+                .chain(std::iter::once(&task.description))
+                .chain(&task.checklist)
+        })
+        .flat_map(|text| placeholder::names(text))
+        .collect()
+}
+
+fn serialize_template(template: task_template::Model) -> Result<TaskTemplateSerializer, AppError> {
+    let placeholders = placeholders_of(&template_tasks(&template)?);
+
+    Ok(TaskTemplateSerializer::from((template, placeholders)))
+}
+
+/// Validates `template_request` and stores it in `template`.
+async fn save_template(
+    app_state: &AppState,
+    user: &user::Model,
+    mut template: task_template::ActiveModel,
+    template_request: SaveTemplateRequest,
+) -> Result<TaskTemplateSerializer, AppError> {
+    template_request.validate()?;
+
+    if let Some(project_id) = template_request.project_id {
+        find_project(
+            &app_state.db,
+            user,
+            project_id,
+            ProjectPermission::ChangeTasks,
+        )
+        .await?;
+    }
+
+    template.name = Set(template_request.name);
+    template.description = Set(template_request.description);
+    template.project_id = Set(template_request.project_id);
+    template.tasks = Set(serde_json::to_string(&template_request.tasks)
+        .map_err(|e| AppError::GenericError(e.to_string()))?);
+
+    let template = template.save(&app_state.db).await?.try_into()?;
+
+    serialize_template(template)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/templates",
+    tag = "templates",
+    responses((status = 200, body = DataResponse<Vec<TaskTemplateSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_templates(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let templates = task_template::Entity::find()
+        .filter(visible_to(user.id))
+        .order_by_asc(task_template::Column::Name)
+        .order_by_asc(task_template::Column::Id)
+        .all(&app_state.db)
+        .await?
+        .into_iter()
+        .map(serialize_template)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(JsonResponse::data(templates, None))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/templates",
+    tag = "templates",
+    request_body = SaveTemplateRequest,
+    responses((status = 200, body = DataResponse<TaskTemplateSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn create_template(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Json(template_request): Json<SaveTemplateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let template = task_template::ActiveModel {
+        id: NotSet,
+        user_id: Set(user.id),
+        date_created: NotSet,
+        ..Default::default()
+    };
+
+    let template = save_template(&app_state, &user, template, template_request).await?;
+
+    Ok(JsonResponse::data(template, None))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/templates/{template_id}",
+    tag = "templates",
+    params(
+        ("template_id" = i32, Path, description = "Id of the template"),
+    ),
+    responses((status = 200, body = DataResponse<TaskTemplateSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn get_template(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(template_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let template = find_template(&app_state, &user, template_id, false).await?;
+
+    Ok(JsonResponse::data(serialize_template(template)?, None))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/templates/{template_id}",
+    tag = "templates",
+    params(
+        ("template_id" = i32, Path, description = "Id of the template"),
+    ),
+    request_body = SaveTemplateRequest,
+    responses((status = 200, body = DataResponse<TaskTemplateSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn update_template(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(template_id): Path<i32>,
+    Json(template_request): Json<SaveTemplateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let template = find_template(&app_state, &user, template_id, true).await?;
+
+    let template = save_template(&app_state, &user, template.into(), template_request).await?;
+
+    Ok(JsonResponse::data(template, None))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/templates/{template_id}",
+    tag = "templates",
+    params(
+        ("template_id" = i32, Path, description = "Id of the template"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+#[axum::debug_handler]
+pub async fn delete_template(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(template_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let template = find_template(&app_state, &user, template_id, true).await?;
+
+    task_template::Entity::delete_by_id(template.id)
+        .exec(&app_state.db)
+        .await?;
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Template deleted successfully".to_string()),
+    ))
+}
+
+/// Creates every task of a template, with its checklist, in one transaction.
+/// Placeholders are replaced by the given values, which must cover all of
+/// them.
+#[utoipa::path(
+    post,
+    path = "/api/templates/{template_id}/instantiate",
+    tag = "templates",
+    params(
+        ("template_id" = i32, Path, description = "Id of the template"),
+    ),
+    request_body(content = Option<InstantiateTemplateRequest>),
+    responses((status = 200, body = DataResponse<Vec<TaskSerializer>>)),
+)]
+pub async fn instantiate_template<V: ApiVersion>(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(template_id): Path<i32>,
+    instantiate_request: Option<Json<InstantiateTemplateRequest>>,
+) -> Result<impl IntoResponse, AppError> {
+    let Json(instantiate_request) = instantiate_request.unwrap_or_default();
+
+    instantiate_request.validate()?;
+
+    let template = find_template(&app_state, &user, template_id, false).await?;
+    let template_tasks = template_tasks(&template)?;
+
+    let missing: Vec<String> = placeholders_of(&template_tasks)
+        .into_iter()
+        .filter(|name| !instantiate_request.values.contains_key(name))
+        .collect();
+
+    if !missing.is_empty() {
+        return Err(AppError::BadRequest(format!(
+            "Missing values for placeholders: {}.",
+            missing.join(", ")
+        )));
+    }
+
+    let project = match instantiate_request.project_id.or(template.project_id) {
+        Some(project_id) => {
+            find_project(
+                &app_state.db,
+                &user,
+                project_id,
+                ProjectPermission::ChangeTasks,
+            )
+            .await?
+        }
+        None => inbox(&app_state.db, &user).await?,
+    };
+
+    if project.is_archived {
+        return Err(AppError::BadRequest(
+            "Tasks cannot be added to an archived project.".to_string(),
+        ));
+    }
+
+    let start_date = instantiate_request
+        .start_date
+        .unwrap_or(Utc::now().date_naive());
+    let values = instantiate_request.values;
+
+    let mut tasks = Vec::new();
+
+    for template_task in template_tasks {
+        let title = placeholder::render(&template_task.title, &values);
+
+        if title.trim().chars().count() < 3 {
+            return Err(AppError::BadRequest(format!(
+                "The title \"{}\" must have at least 3 characters.",
+                title
+            )));
+        }
+
+        let due_date = template_task
+            .due_offset_days
+            .map(|offset| {
+                start_date
+                    .checked_add_days(Days::new(offset as u64))
+                    .ok_or(AppError::BadRequest(
+                        "The due date of a task would be out of range.".to_string(),
+                    ))
+            })
+            .transpose()?;
+
+        let task = task::ActiveModel {
+            title: Set(title),
+            description: Set(placeholder::render(&template_task.description, &values)),
+            status: Set(template_task.status),
+            user_id: Set(user.id),
+            project_id: Set(Some(project.id)),
+            auto_complete: Set(false),
+            estimate_minutes: Set(template_task.estimate_minutes),
+            due_date: Set(due_date),
+            priority: Set(template_task.priority),
+            labels: Set(join_labels(&template_task.labels)),
+            ..Default::default()
+        };
+
+        let checklist: Vec<String> = template_task
+            .checklist
+            .iter()
+            .map(|text| placeholder::render(text, &values))
+            .collect();
+
+        tasks.push((task, checklist));
+    }
+
+    let tasks: Vec<V::Task> = insert_tasks(&app_state, tasks)
+        .await?
+        .into_iter()
+        .map(V::Task::from)
+        .collect();
+
+    Ok(JsonResponse::data(tasks, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        api_version::V2,
+        testing::{app_state, create_user},
+    };
+
+    #[tokio::test]
+    async fn due_date_out_of_range_is_rejected() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+
+        let template_request: SaveTemplateRequest = serde_json::from_value(json!({
+            "name": "Release",
+            "tasks": [{ "title": "Publish", "due_offset_days": 1 }],
+        }))
+        .unwrap();
+
+        let template = save_template(
+            &app_state,
+            &alice,
+            task_template::ActiveModel {
+                id: NotSet,
+                user_id: Set(alice.id),
+                date_created: NotSet,
+                ..Default::default()
+            },
+            template_request,
+        )
+        .await
+        .unwrap();
+
+        let instantiate_request = InstantiateTemplateRequest {
+            start_date: chrono::NaiveDate::MAX.into(),
+            ..Default::default()
+        };
+
+        let tasks = instantiate_template::<V2>(
+            State(app_state.clone()),
+            Extension(alice),
+            Path(template.id),
+            Some(Json(instantiate_request)),
+        )
+        .await;
+
+        assert!(matches!(tasks, Err(AppError::BadRequest(_))));
+        assert_eq!(
+            task::Entity::find().all(&app_state.db).await.unwrap().len(),
+            0
+        );
+    }
+}
@@ -0,0 +1,453 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    Extension, Json, Router,
+};
+use chrono::Days;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, Condition, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use validator::Validate;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse},
+    controller::task_controller::find_task,
+    csv,
+    error::AppError,
+    form::time_entry_form::{CreateTimeEntryRequest, StartTimerRequest, UpdateTimeEntryRequest},
+    models::{
+        _entities::{task, time_entry, user},
+        project::{has_global_permission, ProjectPermission},
+        task::{in_projects_of, TaskRef},
+        time_entry::{now, report, running_timer, ReportDimension},
+    },
+    serializer::{TimeEntrySerializer, TimeReportRowSerializer, TimeReportSerializer},
+    utils::{parse_date_range, parse_id},
+    AppState,
+};
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/timer", get(get_timer))
+        .route("/timer/stop", post(stop_timer))
+        .route("/tasks/:task_id/timer/start", post(start_timer))
+        .route(
+            "/tasks/:task_id/time_entries",
+            get(get_task_time_entries).post(create_time_entry),
+        )
+        .route(
+            "/time_entries/:entry_id",
+            put(update_time_entry).delete(delete_time_entry),
+        )
+        .route("/time_entries/report", get(get_time_report))
+}
+
+/// Finds a time entry of `user`. Entries can only be changed by their owner.
+async fn find_own_entry(
+    app_state: &AppState,
+    user: &user::Model,
+    entry_id: i32,
+) -> Result<time_entry::Model, AppError> {
+    let entry = time_entry::Entity::find_by_id(entry_id)
+        .one(&app_state.db)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    if entry.user_id != user.id {
+        return Err(AppError::Unauthorized(format!(
+            "Time entry {} belongs to user {}",
+            entry.id, entry.user_id
+        )));
+    }
+
+    Ok(entry)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/timer",
+    tag = "time",
+    responses((status = 200, body = DataResponse<Option<TimeEntrySerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_timer(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let timer = running_timer(&app_state.db, user.id)
+        .await?
+        .map(TimeEntrySerializer::from);
+
+    Ok(JsonResponse::data(timer, None))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/timer/start",
+    tag = "time",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    request_body(content = Option<StartTimerRequest>),
+    responses((status = 200, body = DataResponse<TimeEntrySerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn start_timer(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+    timer_request: Option<Json<StartTimerRequest>>,
+) -> Result<impl IntoResponse, AppError> {
+    let Json(timer_request) = timer_request.unwrap_or_default();
+
+    timer_request.validate()?;
+
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    if let Some(timer) = running_timer(&app_state.db, user.id).await? {
+        return Err(AppError::BadRequest(format!(
+            "A timer is already running on task {}. Stop it first.",
+            timer.task_id
+        )));
+    }
+
+    let entry = time_entry::ActiveModel {
+        id: NotSet,
+        user_id: Set(user.id),
+        task_id: Set(task.id),
+        started_at: Set(now()),
+        ended_at: Set(None),
+        note: Set(timer_request.note),
+        date_created: NotSet,
+    }
+    .insert(&app_state.db)
+    .await?;
+
+    Ok(JsonResponse::data(TimeEntrySerializer::from(entry), None))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/timer/stop",
+    tag = "time",
+    responses((status = 200, body = DataResponse<TimeEntrySerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn stop_timer(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let timer = running_timer(&app_state.db, user.id)
+        .await?
+        .ok_or(AppError::BadRequest("No timer is running.".to_string()))?;
+
+    let mut timer: time_entry::ActiveModel = timer.into();
+
+    timer.ended_at = Set(Some(now()));
+
+    let entry = timer.update(&app_state.db).await?;
+
+    Ok(JsonResponse::data(TimeEntrySerializer::from(entry), None))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/time_entries",
+    tag = "time",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    responses((status = 200, body = DataResponse<Vec<TimeEntrySerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_task_time_entries(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+) -> Result<impl IntoResponse, AppError> {
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::View).await?;
+
+    let entries: Vec<TimeEntrySerializer> = time_entry::Entity::find()
+        .filter(time_entry::Column::TaskId.eq(task.id))
+        .order_by_desc(time_entry::Column::StartedAt)
+        .all(&app_state.db)
+        .await?
+        .into_iter()
+        .map(TimeEntrySerializer::from)
+        .collect();
+
+    Ok(JsonResponse::data(entries, None))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/time_entries",
+    tag = "time",
+    params(
+        ("task_id" = String, Path, description = "Id or key of the task, e.g. `42` or `OPS-42`"),
+    ),
+    request_body = CreateTimeEntryRequest,
+    responses((status = 200, body = DataResponse<TimeEntrySerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn create_time_entry(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(task_ref): Path<TaskRef>,
+    Json(entry_request): Json<CreateTimeEntryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    entry_request.validate()?;
+
+    let task = find_task(&app_state, &user, task_ref, ProjectPermission::ChangeTasks).await?;
+
+    let entry = time_entry::ActiveModel {
+        id: NotSet,
+        user_id: Set(user.id),
+        task_id: Set(task.id),
+        started_at: Set(entry_request.started_at),
+        ended_at: Set(Some(entry_request.ended_at)),
+        note: Set(entry_request.note),
+        date_created: NotSet,
+    }
+    .insert(&app_state.db)
+    .await?;
+
+    Ok(JsonResponse::data(TimeEntrySerializer::from(entry), None))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/time_entries/{entry_id}",
+    tag = "time",
+    params(
+        ("entry_id" = i32, Path, description = "Id of the time entry"),
+    ),
+    request_body = UpdateTimeEntryRequest,
+    responses((status = 200, body = DataResponse<TimeEntrySerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn update_time_entry(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(entry_id): Path<i32>,
+    Json(entry_request): Json<UpdateTimeEntryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    entry_request.validate()?;
+
+    let entry = find_own_entry(&app_state, &user, entry_id).await?;
+
+    let started_at = entry_request.started_at.unwrap_or(entry.started_at);
+    let ended_at = entry_request.ended_at.or(entry.ended_at);
+
+    if ended_at.is_some_and(|ended_at| ended_at <= started_at) {
+        return Err(AppError::BadRequest(
+            "ended_at must be later than started_at.".to_string(),
+        ));
+    }
+
+    let mut entry: time_entry::ActiveModel = entry.into();
+
+    entry.started_at = Set(started_at);
+    entry.ended_at = Set(ended_at);
+
+    if let Some(note) = entry_request.note {
+        entry.note = Set(Some(note));
+    }
+
+    let entry = entry.update(&app_state.db).await?;
+
+    Ok(JsonResponse::data(TimeEntrySerializer::from(entry), None))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/time_entries/{entry_id}",
+    tag = "time",
+    params(
+        ("entry_id" = i32, Path, description = "Id of the time entry"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+#[axum::debug_handler]
+pub async fn delete_time_entry(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(entry_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let entry = find_own_entry(&app_state, &user, entry_id).await?;
+
+    time_entry::Entity::delete_by_id(entry.id)
+        .exec(&app_state.db)
+        .await?;
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Time entry deleted successfully".to_string()),
+    ))
+}
+
+/// Sums the hours logged between `from` and `to` (both inclusive), grouped by
+/// the comma separated dimensions in `group_by`. Returns CSV with
+/// `format=csv`.
+#[utoipa::path(
+    get,
+    path = "/api/time_entries/report",
+    tag = "time",
+    params(
+        ("from" = Option<chrono::NaiveDate>, Query, description = "First day, e.g. 2025-01-01"),
+        ("to" = Option<chrono::NaiveDate>, Query, description = "Last day, inclusive"),
+        ("group_by" = Option<String>, Query, description = "Comma separated dimensions to group by"),
+        ("format" = Option<String>, Query, description = "`csv` for a CSV file"),
+    ),
+    responses((status = 200, content((DataResponse<TimeReportSerializer> = "application/json"), (String = "text/csv")))),
+)]
+#[axum::debug_handler]
+pub async fn get_time_report(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, AppError> {
+    let (from, to) = parse_date_range(&params)?;
+
+    let dimensions = match params.get("group_by") {
+        Some(group_by) => group_by
+            .split(',')
+            .map(|dimension| {
+                ReportDimension::parse(dimension.trim()).ok_or(AppError::BadRequest(format!(
+                    "Unknown group_by dimension {}.",
+                    dimension
+                )))
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => ReportDimension::ALL.to_vec(),
+    };
+
+    let mut condition = Condition::all();
+
+    if !has_global_permission(&app_state.db, &user, ProjectPermission::View).await? {
+        condition = condition.add(in_projects_of(user.id));
+    }
+
+    if let Some(user_id) = parse_id(&params, "user_id")? {
+        condition = condition.add(time_entry::Column::UserId.eq(user_id));
+    }
+
+    if let Some(project_id) = parse_id(&params, "project_id")? {
+        condition = condition.add(task::Column::ProjectId.eq(project_id));
+    }
+
+    if let Some(task_id) = parse_id(&params, "task_id")? {
+        condition = condition.add(time_entry::Column::TaskId.eq(task_id));
+    }
+
+    let day_after = to
+        .checked_add_days(Days::new(1))
+        .ok_or(AppError::BadRequest("to is out of range.".to_string()))?;
+
+    let rows = report(
+        &app_state.db,
+        &dimensions,
+        from.and_hms_opt(0, 0, 0).expect("Midnight is valid"),
+        day_after.and_hms_opt(0, 0, 0).expect("Midnight is valid"),
+        condition,
+    )
+    .await?;
+
+    let total_seconds: i64 = rows.iter().map(|row| row.seconds).sum();
+
+    let rows: Vec<TimeReportRowSerializer> = rows
+        .into_iter()
+        .map(TimeReportRowSerializer::from)
+        .collect();
+
+    if params.get("format").is_some_and(|format| format == "csv") {
+        let mut body = String::new();
+
+        let mut header = Vec::new();
+
+        for dimension in &dimensions {
+            header.extend(match dimension {
+                ReportDimension::User => vec!["user_id", "username"],
+                ReportDimension::Task => vec!["task_id", "task_key", "task_title"],
+                ReportDimension::Project => vec!["project_id", "project"],
+                ReportDimension::Day => vec!["day"],
+            });
+        }
+
+        header.push("hours");
+        csv::write_record(&mut body, &header);
+
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        let id = |value: Option<i32>| value.map(|id| id.to_string()).unwrap_or_default();
+
+        for row in rows {
+            let mut record = Vec::new();
+
+            for dimension in &dimensions {
+                record.extend(match dimension {
+                    ReportDimension::User => vec![id(row.user_id), text(&row.username)],
+                    ReportDimension::Task => {
+                        vec![id(row.task_id), text(&row.task_key), text(&row.task_title)]
+                    }
+                    ReportDimension::Project => {
+                        vec![id(row.project_id), text(&row.project_name)]
+                    }
+                    ReportDimension::Day => vec![text(&row.day)],
+                });
+            }
+
+            record.push(format!("{:.2}", row.hours));
+            csv::write_record(&mut body, &record);
+        }
+
+        let disposition = format!("attachment; filename=\"time-report-{}-{}.csv\"", from, to);
+
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (header::CONTENT_DISPOSITION, disposition),
+            ],
+            body,
+        )
+            .into_response());
+    }
+
+    let report = TimeReportSerializer {
+        from,
+        to,
+        group_by: dimensions
+            .iter()
+            .map(|dimension| dimension.as_str().to_string())
+            .collect(),
+        total_hours: (total_seconds as f64 / 36.0).round() / 100.0,
+        rows,
+    };
+
+    Ok(JsonResponse::data(report, None).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{app_state, create_user};
+
+    #[tokio::test]
+    async fn last_representable_day_is_rejected() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+
+        let params = HashMap::from([
+            ("from".to_string(), "+262142-12-31".to_string()),
+            ("to".to_string(), "+262142-12-31".to_string()),
+        ]);
+
+        let report = get_time_report(State(app_state), Extension(alice), Query(params)).await;
+
+        assert!(matches!(report, Err(AppError::BadRequest(_))));
+    }
+}
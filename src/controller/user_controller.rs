@@ -5,37 +5,47 @@ use axum::routing::delete;
 use axum::{
     extract::{OriginalUri, Path, Query, State},
     response::IntoResponse,
-    routing::{get, post},
-    Json, Router,
+    routing::{get, post, put},
+    Extension, Json, Router,
 };
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, DbErr, EntityTrait, ModelTrait,
-    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait, TryIntoModel,
 };
 use validator::Validate;
 
-use crate::api_response::{JsonResponse, ResponseMetadata};
+use crate::api_response::{DataResponse, JsonResponse, PaginatedResponse, ResponseMetadata};
+use crate::api_version::ApiVersion;
 use crate::error::AppError;
 use crate::form::{
     role_form::{UpdateUserPermissionRequest, UpdateUserRolesRequest},
-    user_form::{CreateUserRequest, UpdateUserRequest},
+    user_form::{
+        CreateUserRequest, UpdateCapacityRequest, UpdateTimezoneRequest, UpdateUserRequest,
+    },
 };
 use crate::models::_entities::{
     permission, role, task, user, user_permission, user_profile, user_role,
 };
+use crate::models::{
+    project::{self, ProjectPermission},
+    task::{in_projects_of, with_details},
+};
 use crate::serializer::{
     PermissionSerializer, RoleSerializer, TaskSerializer, UserSerializer, UserWithProfileSerializer,
 };
+use crate::webhook::{self, WebhookEvent};
 use crate::AppState;
 
-pub async fn get_routes() -> Router<Arc<AppState>> {
+pub async fn get_routes<V: ApiVersion>() -> Router<Arc<AppState>> {
     Router::new()
         .route("/users", get(get_users).post(create_user))
         .route(
             "/users/:user_id",
             get(get_user).put(update_user).delete(delete_user),
         )
-        .route("/users/:user_id/tasks", get(get_user_tasks))
+        .route("/users/:user_id/tasks", get(get_user_tasks::<V>))
+        .route("/users/:user_id/capacity", put(update_user_capacity))
+        .route("/users/:user_id/timezone", put(update_user_timezone))
         .route(
             "/users/:user_id/roles",
             get(get_user_roles).post(assign_roles),
@@ -49,6 +59,18 @@ pub async fn get_routes() -> Router<Arc<AppState>> {
         .route("/users/:user_id/permissions/sync", post(sync_permissions))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    tag = "users",
+    params(
+        ("name" = Option<String>, Query, description = "Only users whose name contains this"),
+        ("username" = Option<String>, Query, description = "Only users whose username contains this"),
+        ("email" = Option<String>, Query, description = "Only users whose email contains this"),
+        ("page" = Option<u64>, Query, description = "Page number, from 1"),
+    ),
+    responses((status = 200, body = PaginatedResponse<Vec<UserWithProfileSerializer>>)),
+)]
 #[axum::debug_handler()]
 pub async fn get_users(
     State(app_state): State<Arc<AppState>>,
@@ -96,6 +118,15 @@ pub async fn get_users(
     Ok(JsonResponse::paginate(users, response_metadata, None))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    responses((status = 200, body = DataResponse<UserWithProfileSerializer>)),
+)]
 #[axum::debug_handler()]
 pub async fn get_user(
     State(app_state): State<Arc<AppState>>,
@@ -111,6 +142,13 @@ pub async fn get_user(
     Ok(JsonResponse::data(user, None))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users",
+    tag = "users",
+    request_body = CreateUserRequest,
+    responses((status = 200, body = DataResponse<UserWithProfileSerializer>)),
+)]
 #[axum::debug_handler]
 pub async fn create_user(
     State(app_state): State<Arc<AppState>>,
@@ -131,10 +169,22 @@ pub async fn create_user(
                     user_id: Set(user.id),
                     address: Set(Some(user_request.address)),
                     mobile_number: Set(Some(user_request.mobile_number)),
+                    weekly_capacity_minutes: NotSet,
+                    timezone: NotSet,
                 }
                 .insert(txn)
                 .await?;
 
+                project::inbox(txn, &user).await?;
+
+                webhook::dispatch(
+                    txn,
+                    WebhookEvent::UserCreated,
+                    UserSerializer::from(user.clone()),
+                    None,
+                )
+                .await?;
+
                 Ok((user, Some(user_profile)))
             })
         })
@@ -146,6 +196,16 @@ pub async fn create_user(
     Ok(JsonResponse::data(user_serializer, None))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    request_body = UpdateUserRequest,
+    responses((status = 200, body = DataResponse<UserWithProfileSerializer>)),
+)]
 #[axum::debug_handler()]
 pub async fn update_user(
     State(app_state): State<Arc<AppState>>,
@@ -176,6 +236,103 @@ pub async fn update_user(
     Ok(JsonResponse::data(user_serializer, None))
 }
 
+/// Finds a user with their profile, which is created with the defaults when
+/// they have none yet.
+async fn find_with_profile(
+    app_state: &AppState,
+    user_id: i32,
+) -> Result<(user::Model, user_profile::ActiveModel), AppError> {
+    let (user, profile) = user::Entity::find_by_id(user_id)
+        .find_also_related(user_profile::Entity)
+        .one(&app_state.db)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    let profile = match profile {
+        Some(profile) => profile.into(),
+        None => user_profile::ActiveModel {
+            id: NotSet,
+            user_id: Set(user.id),
+            address: Set(None),
+            mobile_number: Set(None),
+            weekly_capacity_minutes: NotSet,
+            timezone: NotSet,
+        },
+    };
+
+    Ok((user, profile))
+}
+
+/// Sets the weekly capacity used by the workload report, creating the profile
+/// of the user if needed.
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}/capacity",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    request_body = UpdateCapacityRequest,
+    responses((status = 200, body = DataResponse<UserWithProfileSerializer>)),
+)]
+#[axum::debug_handler()]
+pub async fn update_user_capacity(
+    State(app_state): State<Arc<AppState>>,
+    Path(user_id): Path<i32>,
+    Json(capacity_request): Json<UpdateCapacityRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    capacity_request.validate()?;
+
+    let (user, mut profile) = find_with_profile(&app_state, user_id).await?;
+
+    profile.weekly_capacity_minutes = Set(capacity_request.weekly_capacity_minutes);
+
+    let profile = profile.save(&app_state.db).await?.try_into_model()?;
+
+    let user_serializer = UserWithProfileSerializer::from((user, Some(profile)));
+
+    Ok(JsonResponse::data(user_serializer, None))
+}
+
+/// Sets the time zone dates are read in, such as "tomorrow" in quick add.
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}/timezone",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    request_body = UpdateTimezoneRequest,
+    responses((status = 200, body = DataResponse<UserWithProfileSerializer>)),
+)]
+#[axum::debug_handler()]
+pub async fn update_user_timezone(
+    State(app_state): State<Arc<AppState>>,
+    Path(user_id): Path<i32>,
+    Json(timezone_request): Json<UpdateTimezoneRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    timezone_request.validate()?;
+
+    let (user, mut profile) = find_with_profile(&app_state, user_id).await?;
+
+    profile.timezone = Set(timezone_request.timezone);
+
+    let profile = profile.save(&app_state.db).await?.try_into_model()?;
+
+    let user_serializer = UserWithProfileSerializer::from((user, Some(profile)));
+
+    Ok(JsonResponse::data(user_serializer, None))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
 #[axum::debug_handler()]
 pub async fn delete_user(
     State(app_state): State<Arc<AppState>>,
@@ -193,9 +350,19 @@ pub async fn delete_user(
     ))
 }
 
-#[axum::debug_handler()]
-pub async fn get_user_tasks(
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/tasks",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+        ("page" = Option<u64>, Query, description = "Page number, from 1"),
+    ),
+    responses((status = 200, body = PaginatedResponse<Vec<TaskSerializer>>)),
+)]
+pub async fn get_user_tasks<V: ApiVersion>(
     State(app_state): State<Arc<AppState>>,
+    Extension(caller): Extension<user::Model>,
     Path(user_id): Path<i32>,
     Query(params): Query<HashMap<String, String>>,
     OriginalUri(original_uri): OriginalUri,
@@ -205,7 +372,11 @@ pub async fn get_user_tasks(
         .await?
         .ok_or(sqlx::Error::RowNotFound)?;
 
-    let task_query = user.find_related(task::Entity);
+    let mut task_query = user.find_related(task::Entity);
+
+    if !project::has_global_permission(&app_state.db, &caller, ProjectPermission::View).await? {
+        task_query = task_query.filter(in_projects_of(caller.id));
+    }
 
     let task_count = task_query.clone().count(&app_state.db).await?;
 
@@ -221,12 +392,15 @@ pub async fn get_user_tasks(
         .and_then(|v| v.parse::<u64>().ok())
         .unwrap_or(10);
 
-    let task_serializer: Vec<TaskSerializer> = task_query
+    let tasks = task_query
         .paginate(&app_state.db, per_page)
         .fetch_page(page - 1)
+        .await?;
+
+    let task_serializer: Vec<V::Task> = with_details(&app_state.db, tasks)
         .await?
-        .iter()
-        .map(|task| TaskSerializer::from(task.clone()))
+        .into_iter()
+        .map(|task| V::Task::from(TaskSerializer::from(task)))
         .collect();
 
     Ok(JsonResponse::paginate(
@@ -236,6 +410,15 @@ pub async fn get_user_tasks(
     ))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/roles",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    responses((status = 200, body = DataResponse<Vec<RoleSerializer>>)),
+)]
 #[axum::debug_handler()]
 pub async fn get_user_roles(
     State(app_state): State<Arc<AppState>>,
@@ -255,14 +438,46 @@ pub async fn get_user_roles(
     Ok(JsonResponse::data(role_serializer, None))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/roles",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    request_body = UpdateUserRolesRequest,
+    responses((status = 200, body = DataResponse<Vec<String>>)),
+)]
 #[axum::debug_handler()]
 pub async fn assign_roles(
     State(app_state): State<Arc<AppState>>,
     Path(user_id): Path<i32>,
     Json(user_roles_request): Json<UpdateUserRolesRequest>,
 ) -> Result<impl IntoResponse, AppError> {
+    let roles_to_add = add_user_roles(&app_state.db, user_id, user_roles_request).await?;
+
+    if roles_to_add.is_empty() {
+        return Ok(JsonResponse::data(
+            None::<String>,
+            Some("All roles already assigned.".to_string()),
+        ));
+    }
+
+    Ok(JsonResponse::data(
+        roles_to_add,
+        Some("Roles added successfully.".to_string()),
+    ))
+}
+
+/// Gives `user_id` the requested roles they do not have yet, and returns the
+/// names of the roles added.
+pub async fn add_user_roles(
+    db: &DatabaseConnection,
+    user_id: i32,
+    user_roles_request: UpdateUserRolesRequest,
+) -> Result<Vec<String>, AppError> {
     let _user = user::Entity::find_by_id(user_id)
-        .one(&app_state.db)
+        .one(db)
         .await?
         .ok_or(sqlx::Error::RowNotFound)?;
 
@@ -273,7 +488,7 @@ pub async fn assign_roles(
     let existing_roles: HashSet<String> = user::Entity::find_by_id(user_id)
         .find_with_related(role::Entity)
         .filter(role::Column::Name.is_in(&user_roles_request.roles))
-        .all(&app_state.db)
+        .all(db)
         .await?
         .into_iter()
         .flat_map(|(_, roles)| roles.into_iter().map(|role| role.name))
@@ -286,16 +501,13 @@ pub async fn assign_roles(
         .collect();
 
     if roles_to_add.is_empty() {
-        return Ok(JsonResponse::data(
-            None::<String>,
-            Some("All roles already assigned.".to_string()),
-        ));
+        return Ok(roles_to_add);
     }
 
     // Fetch role details for the new roles
     let roles_to_add_models = role::Entity::find()
         .filter(role::Column::Name.is_in(roles_to_add.clone()))
-        .all(&app_state.db)
+        .all(db)
         .await?;
 
     // Prepare user_role ActiveModels for insertion
@@ -309,17 +521,21 @@ pub async fn assign_roles(
         .collect();
 
     if !user_roles.is_empty() {
-        user_role::Entity::insert_many(user_roles)
-            .exec(&app_state.db)
-            .await?;
+        user_role::Entity::insert_many(user_roles).exec(db).await?;
     }
 
-    Ok(JsonResponse::data(
-        roles_to_add,
-        Some("Roles added successfully.".to_string()),
-    ))
+    Ok(roles_to_add)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/permissions",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    responses((status = 200, body = DataResponse<Vec<PermissionSerializer>>)),
+)]
 #[axum::debug_handler()]
 pub async fn get_user_permissions(
     State(app_state): State<Arc<AppState>>,
@@ -339,14 +555,47 @@ pub async fn get_user_permissions(
     Ok(JsonResponse::data(permission_serializer, None))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/permissions",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    request_body = UpdateUserPermissionRequest,
+    responses((status = 200, body = DataResponse<Vec<String>>)),
+)]
 #[axum::debug_handler()]
 pub async fn assign_permissions(
     State(app_state): State<Arc<AppState>>,
     Path(user_id): Path<i32>,
     Json(user_permission_request): Json<UpdateUserPermissionRequest>,
 ) -> Result<impl IntoResponse, AppError> {
+    let new_permissions =
+        add_user_permissions(&app_state.db, user_id, user_permission_request).await?;
+
+    if new_permissions.is_empty() {
+        return Ok(JsonResponse::data(
+            None::<String>,
+            Some("Already added.".to_string()),
+        ));
+    }
+
+    Ok(JsonResponse::data(
+        new_permissions,
+        Some("Roles added successfully".to_string()),
+    ))
+}
+
+/// Gives `user_id` the requested permissions they do not have yet, and
+/// returns the permissions added.
+pub async fn add_user_permissions(
+    db: &DatabaseConnection,
+    user_id: i32,
+    user_permission_request: UpdateUserPermissionRequest,
+) -> Result<Vec<permission::Model>, AppError> {
     let _user = user::Entity::find_by_id(user_id)
-        .one(&app_state.db)
+        .one(db)
         .await?
         .ok_or(sqlx::Error::RowNotFound)?;
 
@@ -357,7 +606,7 @@ pub async fn assign_permissions(
     let user_permissions: Vec<String> = user::Entity::find_by_id(user_id)
         .find_with_related(permission::Entity)
         .filter(permission::Column::CodeName.is_in(user_permission_request.permissions.clone()))
-        .all(&app_state.db)
+        .all(db)
         .await?
         .iter()
         .flat_map(|(_, permissions)| permissions.iter().map(|value| value.code_name.clone()))
@@ -370,15 +619,12 @@ pub async fn assign_permissions(
         .collect();
 
     if new_permissions.is_empty() {
-        return Ok(JsonResponse::data(
-            None::<String>,
-            Some("Already added.".to_string()),
-        ));
+        return Ok(Vec::new());
     }
 
     let new_permissions = permission::Entity::find()
         .filter(permission::Column::CodeName.is_in(new_permissions))
-        .all(&app_state.db)
+        .all(db)
         .await?;
 
     let user_permissions: Vec<user_permission::ActiveModel> = new_permissions
@@ -392,16 +638,23 @@ pub async fn assign_permissions(
 
     if !user_permissions.is_empty() {
         user_permission::Entity::insert_many(user_permissions)
-            .exec(&app_state.db)
+            .exec(db)
             .await?;
     }
 
-    Ok(JsonResponse::data(
-        new_permissions,
-        Some("Roles added successfully".to_string()),
-    ))
+    Ok(new_permissions)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/permissions/sync",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    request_body = UpdateUserPermissionRequest,
+    responses((status = 200, body = DataResponse<serde_json::Value>)),
+)]
 #[axum::debug_handler()]
 pub async fn sync_permissions(
     State(app_state): State<Arc<AppState>>,
@@ -511,6 +764,16 @@ pub async fn sync_permissions(
     ))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/roles/sync",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+    ),
+    request_body = UpdateUserRolesRequest,
+    responses((status = 200, body = DataResponse<serde_json::Value>)),
+)]
 #[axum::debug_handler()]
 pub async fn sync_roles(
     State(app_state): State<Arc<AppState>>,
@@ -617,6 +880,17 @@ pub async fn sync_roles(
     ))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/roles/{role_id}",
+    operation_id = "remove_user_role",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "Id of the user"),
+        ("role_id" = i32, Path, description = "Id of the role"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
 #[axum::debug_handler()]
 pub async fn delete_role(
     State(app_state): State<Arc<AppState>>,
@@ -645,3 +919,69 @@ pub async fn delete_role(
         Some("Role removed from the user".to_string()),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Uri;
+
+    use super::*;
+    use crate::{
+        api_version::V1,
+        testing::{app_state, create_project, create_task, create_user, grant, response_data},
+    };
+
+    async fn task_ids_of(
+        app_state: &Arc<AppState>,
+        caller: &user::Model,
+        user: &user::Model,
+    ) -> Vec<i64> {
+        let tasks = response_data(
+            get_user_tasks::<V1>(
+                State(app_state.clone()),
+                Extension(caller.clone()),
+                Path(user.id),
+                Query(HashMap::new()),
+                OriginalUri(Uri::from_static("/api/users/1/tasks")),
+            )
+            .await
+            .unwrap(),
+        )
+        .await;
+
+        tasks
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|task| task["id"].as_i64().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn user_tasks_follow_project_membership() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let carol = create_user(&app_state, "carol").await;
+        let dave = create_user(&app_state, "dave").await;
+        grant(&app_state, &dave, ProjectPermission::View).await;
+
+        let ops = create_project(&app_state, &alice, "ops", &[&bob]).await;
+        let shared = create_project(&app_state, &alice, "web", &[&bob, &carol]).await;
+        let deploy = create_task(&app_state, &alice, &ops, "Deploy").await;
+        let design = create_task(&app_state, &alice, &shared, "Design").await;
+
+        let mut both = vec![deploy.id as i64, design.id as i64];
+        both.sort();
+
+        for caller in [&alice, &bob, &dave] {
+            let mut ids = task_ids_of(&app_state, caller, &alice).await;
+            ids.sort();
+            assert_eq!(ids, both);
+        }
+
+        assert_eq!(
+            task_ids_of(&app_state, &carol, &alice).await,
+            vec![design.id as i64]
+        );
+    }
+}
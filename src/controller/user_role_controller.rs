@@ -4,7 +4,7 @@ use axum::{extract::State, response::IntoResponse, routing::get, Router};
 use sea_orm::EntityTrait;
 
 use crate::{
-    api_response::JsonResponse,
+    api_response::{DataResponse, JsonResponse},
     error::AppError,
     models::_entities::{role, user},
     AppState,
@@ -14,6 +14,13 @@ pub async fn get_routes() -> Router<Arc<AppState>> {
     Router::new().route("/auth/user_roles", get(get_user_roles))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/auth/user_roles",
+    operation_id = "get_users_with_roles",
+    tag = "users",
+    responses((status = 200, body = DataResponse<serde_json::Value>)),
+)]
 pub async fn get_user_roles(
     State(app_state): State<Arc<AppState>>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -0,0 +1,247 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use validator::Validate;
+
+use crate::{
+    api_response::{DataResponse, JsonResponse, PaginatedResponse, ResponseMetadata},
+    error::AppError,
+    form::webhook_form::{CreateWebhookRequest, UpdateWebhookRequest},
+    models::_entities::{user, webhook, webhook_delivery},
+    serializer::{WebhookDeliverySerializer, WebhookSerializer},
+    AppState,
+};
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/webhooks", get(get_webhooks).post(create_webhook))
+        .route(
+            "/webhooks/:webhook_id",
+            get(get_webhook).put(update_webhook).delete(delete_webhook),
+        )
+        .route(
+            "/webhooks/:webhook_id/deliveries",
+            get(get_webhook_deliveries),
+        )
+}
+
+async fn find_user_webhook(
+    app_state: &AppState,
+    user: &user::Model,
+    webhook_id: i32,
+) -> Result<webhook::Model, AppError> {
+    let webhook = webhook::Entity::find_by_id(webhook_id)
+        .one(&app_state.db)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    if webhook.user_id != user.id {
+        return Err(AppError::Unauthorized(
+            "Webhook belongs to another user.".to_string(),
+        ));
+    }
+
+    Ok(webhook)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    responses((status = 200, body = DataResponse<Vec<WebhookSerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_webhooks(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let webhooks: Vec<WebhookSerializer> = webhook::Entity::find()
+        .filter(webhook::Column::UserId.eq(user.id))
+        .order_by(webhook::Column::DateCreated, sea_orm::Order::Desc)
+        .all(&app_state.db)
+        .await?
+        .into_iter()
+        .map(WebhookSerializer::from)
+        .collect();
+
+    Ok(JsonResponse::data(webhooks, None))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookRequest,
+    responses((status = 200, body = DataResponse<WebhookSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn create_webhook(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Json(webhook_request): Json<CreateWebhookRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    webhook_request.validate()?;
+
+    let webhook: WebhookSerializer = webhook::ActiveModel {
+        id: NotSet,
+        user_id: Set(user.id),
+        url: Set(webhook_request.url),
+        secret: Set(webhook_request.secret),
+        event_types: Set(webhook_request.event_types.join(",")),
+        is_active: Set(webhook_request.is_active.unwrap_or(true)),
+        date_created: NotSet,
+    }
+    .insert(&app_state.db)
+    .await?
+    .into();
+
+    Ok(JsonResponse::data(webhook, None))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(
+        ("webhook_id" = i32, Path, description = "Id of the webhook"),
+    ),
+    responses((status = 200, body = DataResponse<WebhookSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn get_webhook(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(webhook_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let webhook: WebhookSerializer = find_user_webhook(&app_state, &user, webhook_id)
+        .await?
+        .into();
+
+    Ok(JsonResponse::data(webhook, None))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(
+        ("webhook_id" = i32, Path, description = "Id of the webhook"),
+    ),
+    request_body = UpdateWebhookRequest,
+    responses((status = 200, body = DataResponse<WebhookSerializer>)),
+)]
+#[axum::debug_handler]
+pub async fn update_webhook(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(webhook_id): Path<i32>,
+    Json(webhook_request): Json<UpdateWebhookRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let webhook = find_user_webhook(&app_state, &user, webhook_id).await?;
+
+    webhook_request.validate()?;
+
+    let mut webhook: webhook::ActiveModel = webhook.into();
+
+    webhook.url = Set(webhook_request.url);
+    webhook.event_types = Set(webhook_request.event_types.join(","));
+    webhook.is_active = Set(webhook_request.is_active);
+
+    if let Some(secret) = webhook_request.secret {
+        webhook.secret = Set(secret);
+    }
+
+    let webhook_serializer: WebhookSerializer = webhook.update(&app_state.db).await?.into();
+
+    Ok(JsonResponse::data(webhook_serializer, None))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(
+        ("webhook_id" = i32, Path, description = "Id of the webhook"),
+    ),
+    responses((status = 200, description = "Done, with `data` null", body = DataResponse<Option<String>>)),
+)]
+#[axum::debug_handler]
+pub async fn delete_webhook(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(webhook_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let webhook = find_user_webhook(&app_state, &user, webhook_id).await?;
+
+    webhook::Entity::delete_by_id(webhook.id)
+        .exec(&app_state.db)
+        .await?;
+
+    Ok(JsonResponse::data(
+        None::<String>,
+        Some("Webhook deleted successfully".to_string()),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{webhook_id}/deliveries",
+    tag = "webhooks",
+    params(
+        ("webhook_id" = i32, Path, description = "Id of the webhook"),
+        ("status" = Option<String>, Query, description = "Only deliveries with this status"),
+        ("event_type" = Option<String>, Query, description = "Only deliveries of this event"),
+        ("page" = Option<u64>, Query, description = "Page number, from 1"),
+    ),
+    responses((status = 200, body = PaginatedResponse<Vec<WebhookDeliverySerializer>>)),
+)]
+#[axum::debug_handler]
+pub async fn get_webhook_deliveries(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user): Extension<user::Model>,
+    Path(webhook_id): Path<i32>,
+    Query(params): Query<HashMap<String, String>>,
+    OriginalUri(original_uri): OriginalUri,
+) -> Result<impl IntoResponse, AppError> {
+    let webhook = find_user_webhook(&app_state, &user, webhook_id).await?;
+
+    let mut delivery_query =
+        webhook_delivery::Entity::find().filter(webhook_delivery::Column::WebhookId.eq(webhook.id));
+
+    if let Some(status) = params.get("status") {
+        delivery_query = delivery_query.filter(webhook_delivery::Column::Status.eq(status));
+    }
+
+    if let Some(event_type) = params.get("event_type") {
+        delivery_query = delivery_query.filter(webhook_delivery::Column::EventType.eq(event_type));
+    }
+
+    let page = params
+        .get("page")
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(1);
+
+    let delivery_count = delivery_query.clone().count(&app_state.db).await?;
+
+    let response_metadata = ResponseMetadata::new(delivery_count, Some(original_uri.to_string()));
+
+    let deliveries: Vec<WebhookDeliverySerializer> = delivery_query
+        .order_by(webhook_delivery::Column::Id, sea_orm::Order::Desc)
+        .paginate(&app_state.db, 10)
+        .fetch_page(page - 1)
+        .await?
+        .into_iter()
+        .map(WebhookDeliverySerializer::from)
+        .collect();
+
+    Ok(JsonResponse::paginate(deliveries, response_metadata, None))
+}
@@ -0,0 +1,236 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    http::{header, HeaderMap},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    collaboration::{ClientMessage, ConnectionGuard, ServerMessage, Viewer},
+    error::AppError,
+    models::{
+        _entities::{task, user},
+        project::{has_global_permission, ProjectPermission},
+        task::in_projects_of,
+    },
+    utils::verify_token,
+    AppState,
+};
+
+pub async fn get_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/ws", get(websocket))
+}
+
+/// Upgrades to the collaboration channel.
+///
+/// Browsers cannot set headers on websocket requests, so the token may also be
+/// passed as the `token` query parameter.
+#[utoipa::path(
+    get,
+    path = "/api/ws",
+    tag = "events",
+    params(
+        ("token" = Option<String>, Query, description = "Access token, for clients that cannot set headers"),
+    ),
+    responses((status = 101, description = "Switched to the collaboration websocket")),
+    security(()),
+)]
+#[axum::debug_handler]
+pub async fn websocket(
+    State(app_state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Result<impl IntoResponse, AppError> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .or(params.get("token").map(String::as_str))
+        .ok_or(AppError::Unauthorized("No token found.".to_string()))?;
+
+    let user = verify_token(app_state.clone(), token).await?;
+
+    let guard = app_state
+        .collaboration
+        .connect(&user)
+        .ok_or(AppError::TooManyRequests(
+            "Too many open connections.".to_string(),
+        ))?;
+
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, app_state, user, guard)))
+}
+
+async fn send(socket: &mut WebSocket, message: &ServerMessage) -> bool {
+    let text = serde_json::to_string(message).expect("Cannot serialize websocket message");
+
+    socket.send(Message::Text(text)).await.is_ok()
+}
+
+async fn handle_socket(
+    mut socket: WebSocket,
+    app_state: Arc<AppState>,
+    user: user::Model,
+    _guard: ConnectionGuard,
+) {
+    let hub = &app_state.collaboration;
+    let viewer = Viewer::from(&user);
+
+    let mut room_messages = hub.subscribe();
+    let mut task_changes = app_state.events.subscribe(None).receiver;
+    let mut shutdown = hub.shutdown_receiver();
+
+    let mut subscribed: HashSet<i32> = HashSet::new();
+
+    loop {
+        tokio::select! {
+            _ = async { shutdown.wait_for(|is_shutdown| *is_shutdown).await.map(|_| ()) } => {
+                let _ = socket
+                    .send(Message::Close(Some(CloseFrame {
+                        code: close_code::AWAY,
+                        reason: "Server is shutting down.".into(),
+                    })))
+                    .await;
+                break;
+            }
+            message = socket.recv() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+
+                let reply = match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(client_message) => {
+                        handle_client_message(&app_state, &user, &viewer, &mut subscribed, client_message)
+                            .await
+                    }
+                    Err(err) => Some(ServerMessage::Error { message: err.to_string() }),
+                };
+
+                if let Some(reply) = reply {
+                    if !send(&mut socket, &reply).await {
+                        break;
+                    }
+                }
+            }
+            room_message = room_messages.recv() => {
+                let room_message = match room_message {
+                    Ok(room_message) => room_message,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+
+                if room_message.is_for(user.id, &subscribed)
+                    && !send(&mut socket, &room_message.message).await
+                {
+                    break;
+                }
+            }
+            change = task_changes.recv() => {
+                let change = match change {
+                    Ok(change) => change,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+
+                if !subscribed.contains(&change.task_id) || !change.is_visible_to(&user) {
+                    continue;
+                }
+
+                let message = ServerMessage::Change {
+                    id: change.id,
+                    event: change.kind.as_str(),
+                    task_id: change.task_id,
+                    data: change.data,
+                };
+
+                if !send(&mut socket, &message).await {
+                    break;
+                }
+            }
+        }
+    }
+
+    for task_id in subscribed {
+        hub.leave(task_id, user.id);
+    }
+}
+
+async fn handle_client_message(
+    app_state: &AppState,
+    user: &user::Model,
+    viewer: &Viewer,
+    subscribed: &mut HashSet<i32>,
+    message: ClientMessage,
+) -> Option<ServerMessage> {
+    let hub = &app_state.collaboration;
+
+    match message {
+        ClientMessage::Subscribe { task_ids } => {
+            let mut task_query = task::Entity::find().filter(task::Column::Id.is_in(task_ids));
+
+            match has_global_permission(&app_state.db, user, ProjectPermission::View).await {
+                Ok(true) => {}
+                Ok(false) => task_query = task_query.filter(in_projects_of(user.id)),
+                Err(err) => {
+                    return Some(ServerMessage::Error {
+                        message: err.to_string(),
+                    })
+                }
+            }
+
+            let tasks = match task_query.all(&app_state.db).await {
+                Ok(tasks) => tasks,
+                Err(err) => {
+                    return Some(ServerMessage::Error {
+                        message: err.to_string(),
+                    })
+                }
+            };
+
+            for task in tasks {
+                if subscribed.insert(task.id) {
+                    hub.join(task.id, viewer);
+                }
+            }
+
+            Some(ServerMessage::Subscribed {
+                task_ids: subscribed.iter().copied().collect(),
+            })
+        }
+        ClientMessage::Unsubscribe { task_ids } => {
+            for task_id in task_ids {
+                if subscribed.remove(&task_id) {
+                    hub.leave(task_id, user.id);
+                }
+            }
+
+            Some(ServerMessage::Subscribed {
+                task_ids: subscribed.iter().copied().collect(),
+            })
+        }
+        ClientMessage::Typing { task_id, is_typing } => {
+            if !subscribed.contains(&task_id) {
+                return Some(ServerMessage::Error {
+                    message: format!("Not subscribed to task {}.", task_id),
+                });
+            }
+
+            hub.typing(task_id, viewer.clone(), is_typing);
+
+            None
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Minimal CSV support (RFC 4180) for reports, exports and imports.
+
+/// Appends one record to `out`, quoting the fields that need it.
+pub fn write_record<I, S>(out: &mut String, fields: I)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+
+        let field = field.as_ref();
+
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+
+    out.push_str("\r\n");
+}
+
+/// Splits `text` into records of fields. Quoted fields may contain commas,
+/// doubled quotes and line breaks; records end with LF or CRLF. Blank lines
+/// are skipped.
+pub fn parse(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if field.is_empty() => loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => {
+                        if c == '\n' {
+                            line += 1;
+                        }
+                        field.push(c);
+                    }
+                    None => return Err(format!("Unterminated quoted field on line {}.", line)),
+                }
+            },
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+
+                if record.len() > 1 || !record[0].is_empty() {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+
+                line += 1;
+            }
+            c => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_fields_when_needed() {
+        let mut out = String::new();
+
+        write_record(&mut out, ["plain", "a,b", "say \"hi\"", ""]);
+
+        assert_eq!(out, "plain,\"a,b\",\"say \"\"hi\"\"\",\r\n");
+    }
+
+    #[test]
+    fn parses_quoted_fields() {
+        let text = "title,notes\r\nplain,\"a,b\"\n\n\"say \"\"hi\"\"\",\"two\nlines\"\nlast,";
+
+        assert_eq!(
+            parse(text).unwrap(),
+            vec![
+                vec!["title", "notes"],
+                vec!["plain", "a,b"],
+                vec!["say \"hi\"", "two\nlines"],
+                vec!["last", ""],
+            ]
+        );
+        assert!(parse("\"open").is_err());
+    }
+}
@@ -0,0 +1,278 @@
+//! Minimal WebDAV XML (RFC 4918) with the CalDAV (RFC 4791) and collection
+//! sync (RFC 6578) requests the CalDAV server answers.
+
+pub const DAV: &str = "DAV:";
+pub const CALDAV: &str = "urn:ietf:params:xml:ns:caldav";
+pub const CALENDAR_SERVER: &str = "http://calendarserver.org/ns/";
+
+/// Qualified name of a property.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PropName {
+    pub namespace: String,
+    pub name: String,
+}
+
+impl PropName {
+    pub fn new(namespace: &str, name: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        }
+    }
+}
+
+/// A PROPFIND or REPORT request. `props` is `None` when the client asked for
+/// every property.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Request {
+    PropFind {
+        props: Option<Vec<PropName>>,
+    },
+    CalendarQuery {
+        props: Option<Vec<PropName>>,
+        /// Whether the filter can match `VTODO` components at all.
+        todos: bool,
+    },
+    CalendarMultiget {
+        props: Option<Vec<PropName>>,
+        hrefs: Vec<String>,
+    },
+    SyncCollection {
+        props: Option<Vec<PropName>>,
+        sync_token: String,
+    },
+}
+
+fn is(node: &roxmltree::Node, namespace: &str, name: &str) -> bool {
+    node.is_element()
+        && node.tag_name().namespace() == Some(namespace)
+        && node.tag_name().name() == name
+}
+
+fn child<'a, 'input>(
+    node: &roxmltree::Node<'a, 'input>,
+    namespace: &str,
+    name: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children().find(|child| is(child, namespace, name))
+}
+
+fn requested_props(node: &roxmltree::Node) -> Option<Vec<PropName>> {
+    child(node, DAV, "prop").map(|prop| {
+        prop.children()
+            .filter(|child| child.is_element())
+            .map(|child| {
+                PropName::new(
+                    child.tag_name().namespace().unwrap_or_default(),
+                    child.tag_name().name(),
+                )
+            })
+            .collect()
+    })
+}
+
+/// Parses the body of a PROPFIND or REPORT request. An empty PROPFIND asks for
+/// every property.
+pub fn parse_request(body: &str) -> Result<Request, String> {
+    if body.trim().is_empty() {
+        return Ok(Request::PropFind { props: None });
+    }
+
+    let document = roxmltree::Document::parse(body).map_err(|e| e.to_string())?;
+    let root = document.root_element();
+    let props = requested_props(&root);
+
+    if is(&root, DAV, "propfind") {
+        Ok(Request::PropFind { props })
+    } else if is(&root, CALDAV, "calendar-query") {
+        let todos = root
+            .descendants()
+            .filter(|node| is(node, CALDAV, "comp-filter"))
+            .filter_map(|node| node.attribute("name"))
+            .all(|name| name == "VCALENDAR" || name == "VTODO");
+
+        Ok(Request::CalendarQuery { props, todos })
+    } else if is(&root, CALDAV, "calendar-multiget") {
+        let hrefs = root
+            .children()
+            .filter(|node| is(node, DAV, "href"))
+            .filter_map(|node| node.text())
+            .map(|href| href.trim().to_string())
+            .collect();
+
+        Ok(Request::CalendarMultiget { props, hrefs })
+    } else if is(&root, DAV, "sync-collection") {
+        let sync_token = child(&root, DAV, "sync-token")
+            .and_then(|node| node.text())
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+
+        Ok(Request::SyncCollection { props, sync_token })
+    } else {
+        Err(format!("Unsupported request {}.", root.tag_name().name()))
+    }
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// One `response` of a multistatus. Properties without a value are reported
+/// as not found; a `status` replaces the properties, e.g. 404 for a resource
+/// deleted since the last sync.
+#[derive(Debug, Default)]
+pub struct Response {
+    pub href: String,
+    pub props: Vec<(PropName, Option<String>)>,
+    pub status: Option<u16>,
+}
+
+fn status_line(status: u16) -> &'static str {
+    match status {
+        200 => "HTTP/1.1 200 OK",
+        403 => "HTTP/1.1 403 Forbidden",
+        _ => "HTTP/1.1 404 Not Found",
+    }
+}
+
+/// Writes `<prop>value</prop>` with the prefix of its namespace.
+fn write_prop(out: &mut String, prop: &PropName, value: &str) {
+    let (prefix, declaration) = match prop.namespace.as_str() {
+        DAV => ("d", String::new()),
+        CALDAV => ("c", String::new()),
+        CALENDAR_SERVER => ("cs", String::new()),
+        namespace => ("x", format!(" xmlns:x=\"{}\"", escape(namespace))),
+    };
+
+    if value.is_empty() {
+        out.push_str(&format!("<{}:{}{}/>", prefix, prop.name, declaration));
+    } else {
+        out.push_str(&format!(
+            "<{0}:{1}{2}>{3}</{0}:{1}>",
+            prefix, prop.name, declaration, value
+        ));
+    }
+}
+
+/// A `207 Multi-Status` body. Property values must already be XML.
+pub fn multistatus(responses: &[Response], sync_token: Option<&str>) -> String {
+    let mut out = format!(
+        r#"<?xml version="1.0" encoding="utf-8"?><d:multistatus xmlns:d="{}" xmlns:c="{}" xmlns:cs="{}">"#,
+        DAV, CALDAV, CALENDAR_SERVER
+    );
+
+    for response in responses {
+        out.push_str(&format!(
+            "<d:response><d:href>{}</d:href>",
+            escape(&response.href)
+        ));
+
+        if let Some(status) = response.status {
+            out.push_str(&format!("<d:status>{}</d:status>", status_line(status)));
+        }
+
+        for found in [true, false] {
+            let props: Vec<_> = response
+                .props
+                .iter()
+                .filter(|(_, value)| value.is_some() == found)
+                .collect();
+
+            if props.is_empty() {
+                continue;
+            }
+
+            out.push_str("<d:propstat><d:prop>");
+
+            for (prop, value) in props {
+                write_prop(&mut out, prop, value.as_deref().unwrap_or_default());
+            }
+
+            out.push_str(&format!(
+                "</d:prop><d:status>{}</d:status></d:propstat>",
+                status_line(if found { 200 } else { 404 })
+            ));
+        }
+
+        out.push_str("</d:response>");
+    }
+
+    if let Some(sync_token) = sync_token {
+        out.push_str(&format!(
+            "<d:sync-token>{}</d:sync-token>",
+            escape(sync_token)
+        ));
+    }
+
+    out.push_str("</d:multistatus>");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sync_collection_and_writes_multistatus() {
+        let body = r#"<?xml version="1.0"?>
+            <d:sync-collection xmlns:d="DAV:" xmlns:x="urn:x">
+              <d:sync-token>urn:task_app:sync:7</d:sync-token>
+              <d:sync-level>1</d:sync-level>
+              <d:prop><d:getetag/><x:color/></d:prop>
+            </d:sync-collection>"#;
+
+        let request = parse_request(body).unwrap();
+
+        assert_eq!(
+            request,
+            Request::SyncCollection {
+                props: Some(vec![
+                    PropName::new(DAV, "getetag"),
+                    PropName::new("urn:x", "color"),
+                ]),
+                sync_token: "urn:task_app:sync:7".to_string(),
+            }
+        );
+
+        let out = multistatus(
+            &[
+                Response {
+                    href: "/a&b.ics".to_string(),
+                    props: vec![
+                        (PropName::new(DAV, "getetag"), Some("\"1\"".to_string())),
+                        (PropName::new("urn:x", "color"), None),
+                    ],
+                    status: None,
+                },
+                Response {
+                    href: "/gone.ics".to_string(),
+                    status: Some(404),
+                    ..Default::default()
+                },
+            ],
+            Some("urn:task_app:sync:9"),
+        );
+
+        let document = roxmltree::Document::parse(&out).unwrap();
+        let statuses: Vec<_> = document
+            .descendants()
+            .filter(|node| node.has_tag_name((DAV, "status")))
+            .filter_map(|node| node.text())
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                "HTTP/1.1 200 OK",
+                "HTTP/1.1 404 Not Found",
+                "HTTP/1.1 404 Not Found"
+            ]
+        );
+        assert!(out.contains("<d:href>/a&amp;b.ics</d:href>"));
+        assert!(out.contains("<x:color xmlns:x=\"urn:x\"/>"));
+    }
+}
@@ -9,9 +9,11 @@ use crate::api_response::JsonResponse;
 pub enum AppError {
     DatabaseError(sqlx::Error),
     GenericError(String),
+    BadRequest(String),
     SeaOrm(sea_orm::DbErr),
     Validation(validator::ValidationErrors),
     Unauthorized(String),
+    TooManyRequests(String),
 }
 
 impl From<sqlx::Error> for AppError {
@@ -32,9 +34,10 @@ impl From<validator::ValidationErrors> for AppError {
     }
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let (status_code, error_message) = match self {
+impl AppError {
+    /// The status code and message the error is reported to clients with.
+    pub fn status_and_message(self) -> (StatusCode, String) {
+        match self {
             AppError::DatabaseError(sqlx_error) => match sqlx_error {
                 sqlx::Error::Database(database_error) => {
                     (StatusCode::NOT_FOUND, database_error.to_string())
@@ -46,15 +49,27 @@ impl IntoResponse for AppError {
                 ),
             },
             AppError::GenericError(e) => (StatusCode::INTERNAL_SERVER_ERROR, e),
+            AppError::BadRequest(e) => (StatusCode::BAD_REQUEST, e),
             AppError::SeaOrm(db_err) => (StatusCode::NOT_FOUND, db_err.to_string()),
             AppError::Validation(validation_errors) => {
                 (StatusCode::BAD_REQUEST, validation_errors.to_string())
             }
-            AppError::Unauthorized(_) => (
-                StatusCode::UNAUTHORIZED,
-                "You are not authorized.".to_string(),
-            ),
-        };
+            AppError::Unauthorized(reason) => {
+                tracing::debug!("Unauthorized: {}", reason);
+
+                (
+                    StatusCode::UNAUTHORIZED,
+                    "You are not authorized.".to_string(),
+                )
+            }
+            AppError::TooManyRequests(e) => (StatusCode::TOO_MANY_REQUESTS, e),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status_code, error_message) = self.status_and_message();
 
         (
             status_code,
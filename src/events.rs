@@ -0,0 +1,194 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+
+use crate::{models::_entities::user, serializer::TaskSerializer};
+
+/// Number of recent changes kept for `Last-Event-ID` resumption.
+const REPLAY_BUFFER_SIZE: usize = 256;
+const CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TaskChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl TaskChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskChangeKind::Created => "task.created",
+            TaskChangeKind::Updated => "task.updated",
+            TaskChangeKind::Deleted => "task.deleted",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskChange {
+    pub id: u64,
+    pub kind: TaskChangeKind,
+    pub task_id: i32,
+    /// Users who could see the task when it changed.
+    pub audience: Vec<i32>,
+    pub data: Value,
+}
+
+impl TaskChange {
+    pub fn is_visible_to(&self, user: &user::Model) -> bool {
+        self.audience.contains(&user.id)
+    }
+}
+
+/// Changes to replay to a resuming subscriber, followed by the live receiver.
+pub struct Subscription {
+    pub replay: Vec<TaskChange>,
+    /// The requested `Last-Event-ID` is older than the replay buffer (or was issued
+    /// before a restart), so some changes were missed and the client should reload
+    /// its tasks.
+    pub missed: bool,
+    pub receiver: broadcast::Receiver<TaskChange>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    next_id: u64,
+    buffer: VecDeque<TaskChange>,
+}
+
+/// In-process fan-out of task changes to the `/events` stream subscribers.
+#[derive(Debug, Clone)]
+pub struct EventHub {
+    sender: broadcast::Sender<TaskChange>,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+
+        Self {
+            sender,
+            inner: Arc::new(Mutex::new(Inner {
+                next_id: 1,
+                buffer: VecDeque::with_capacity(REPLAY_BUFFER_SIZE),
+            })),
+        }
+    }
+}
+
+impl EventHub {
+    fn publish(&self, kind: TaskChangeKind, task_id: i32, audience: Vec<i32>, data: Value) {
+        let mut inner = self.inner.lock().expect("Event hub lock poisoned");
+
+        let change = TaskChange {
+            id: inner.next_id,
+            kind,
+            task_id,
+            audience,
+            data,
+        };
+
+        inner.next_id += 1;
+
+        if inner.buffer.len() == REPLAY_BUFFER_SIZE {
+            inner.buffer.pop_front();
+        }
+        inner.buffer.push_back(change.clone());
+
+        // Sending only fails when nobody is subscribed.
+        let _ = self.sender.send(change);
+    }
+
+    pub fn task_created(&self, task: &TaskSerializer, audience: Vec<i32>) {
+        self.publish(TaskChangeKind::Created, task.id, audience, json!(task));
+    }
+
+    pub fn task_updated(&self, task: &TaskSerializer, audience: Vec<i32>) {
+        self.publish(TaskChangeKind::Updated, task.id, audience, json!(task));
+    }
+
+    pub fn task_deleted(&self, task_id: i32, audience: Vec<i32>) {
+        self.publish(
+            TaskChangeKind::Deleted,
+            task_id,
+            audience,
+            json!({ "id": task_id }),
+        );
+    }
+
+    pub fn subscribe(&self, last_event_id: Option<u64>) -> Subscription {
+        // Holding the lock keeps publishers from slipping a change in between
+        // the replay snapshot and the live receiver.
+        let inner = self.inner.lock().expect("Event hub lock poisoned");
+
+        let receiver = self.sender.subscribe();
+
+        let Some(last_event_id) = last_event_id else {
+            return Subscription {
+                replay: Vec::new(),
+                missed: false,
+                receiver,
+            };
+        };
+
+        let oldest_id = inner
+            .buffer
+            .front()
+            .map(|change| change.id)
+            .unwrap_or(inner.next_id);
+
+        Subscription {
+            replay: inner
+                .buffer
+                .iter()
+                .filter(|change| change.id > last_event_id)
+                .cloned()
+                .collect(),
+            missed: last_event_id.saturating_add(1) < oldest_id || last_event_id >= inner.next_id,
+            receiver,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscribe_replays_changes_after_last_event_id() {
+        let hub = EventHub::default();
+
+        for task_id in 1..=3 {
+            hub.task_deleted(task_id, vec![1]);
+        }
+
+        let subscription = hub.subscribe(Some(1));
+        let replayed: Vec<u64> = subscription.replay.iter().map(|change| change.id).collect();
+
+        assert_eq!(replayed, vec![2, 3]);
+        assert!(!subscription.missed);
+    }
+
+    #[test]
+    fn subscribe_with_last_event_id_out_of_range_is_missed() {
+        let hub = EventHub::default();
+
+        hub.task_deleted(1, vec![1]);
+
+        let subscription = hub.subscribe(Some(u64::MAX));
+
+        assert!(subscription.replay.is_empty());
+        assert!(subscription.missed);
+
+        // The hub is still usable afterwards.
+        hub.task_deleted(2, vec![1]);
+        assert!(!hub.subscribe(Some(1)).missed);
+    }
+}
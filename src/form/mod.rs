@@ -1,4 +1,9 @@
+pub mod notification_form;
 pub mod permission_form;
+pub mod project_form;
 pub mod role_form;
+pub mod saved_view_form;
 pub mod task_form;
+pub mod time_entry_form;
 pub mod user_form;
+pub mod webhook_form;
@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::notification::NotificationKind;
+
+fn validate_kinds(preferences: &HashMap<String, bool>) -> Result<(), ValidationError> {
+    if !preferences
+        .keys()
+        .all(|kind| NotificationKind::is_valid(kind))
+    {
+        return Err(
+            ValidationError::new("preferences").with_message("Unknown notification kind".into())
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateNotificationPreferencesRequest {
+    #[validate(custom(function = "validate_kinds"))]
+    pub preferences: HashMap<String, bool>,
+}
@@ -1,10 +1,13 @@
+use async_graphql::InputObject;
 use sea_orm::{DeriveIntoActiveModel, Set};
 use serde::Deserialize;
+use utoipa::ToSchema;
 use validator::Validate;
 
 use crate::models::_entities::permission::ActiveModel;
 
-#[derive(Debug, Deserialize, Validate, Clone, DeriveIntoActiveModel)]
+#[derive(Debug, Deserialize, Validate, Clone, DeriveIntoActiveModel, ToSchema, InputObject)]
+#[graphql(name = "PermissionInput")]
 pub struct CreatePermissionRequest {
     #[validate(length(min = 3, message = "Must have at least 3 characters"))]
     pub name: String,
@@ -21,7 +24,7 @@ impl From<CreatePermissionRequest> for ActiveModel {
     }
 }
 
-#[derive(Debug, Deserialize, Validate, Clone, DeriveIntoActiveModel)]
+#[derive(Debug, Deserialize, Validate, Clone, DeriveIntoActiveModel, ToSchema)]
 pub struct UpdatePermissionRequest {
     #[validate(length(min = 3, message = "Must have at least 3 characters"))]
     pub name: String,
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::models::{project::ProjectRole, task::STATUSES};
+
+fn validate_role(role: &str) -> Result<(), ValidationError> {
+    if !ProjectRole::is_valid(role) {
+        return Err(ValidationError::new("role").with_message("Unknown project role".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_wip_limits(limits: &HashMap<String, Option<u32>>) -> Result<(), ValidationError> {
+    if !limits
+        .keys()
+        .all(|status| STATUSES.contains(&status.as_str()))
+    {
+        return Err(ValidationError::new("limits").with_message("Unknown status".into()));
+    }
+
+    if limits.values().any(|limit| *limit == Some(0)) {
+        return Err(ValidationError::new("limits").with_message("Limits must be positive".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_key_prefix(prefix: &str) -> Result<(), ValidationError> {
+    let mut chars = prefix.chars();
+
+    let is_valid = chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        && prefix.len() <= 10;
+
+    if !is_valid {
+        return Err(ValidationError::new("key_prefix").with_message(
+            "Must be up to 10 uppercase letters or digits, starting with a letter".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateProjectRequest {
+    #[validate(length(min = 1, max = 100, message = "Must have 1 to 100 characters"))]
+    pub name: String,
+    pub description: Option<String>,
+    #[validate(custom(function = "validate_key_prefix"))]
+    pub key_prefix: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateProjectRequest {
+    #[validate(length(min = 1, max = 100, message = "Must have 1 to 100 characters"))]
+    pub name: String,
+    pub description: Option<String>,
+    pub is_archived: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateProjectMemberRequest {
+    #[validate(custom(function = "validate_role"))]
+    pub role: String,
+}
+
+/// WIP limits by status; `null` removes the limit of a column.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateWipLimitsRequest {
+    #[validate(custom(function = "validate_wip_limits"))]
+    pub limits: HashMap<String, Option<u32>>,
+}
@@ -1,10 +1,13 @@
+use async_graphql::InputObject;
 use sea_orm::{DeriveIntoActiveModel, Set};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
 use crate::models::_entities::role::ActiveModel;
 
-#[derive(Debug, Deserialize, Validate, DeriveIntoActiveModel)]
+#[derive(Debug, Deserialize, Validate, DeriveIntoActiveModel, ToSchema, InputObject)]
+#[graphql(name = "CreateRoleInput")]
 pub struct CreateRoleRequest {
     #[validate(length(min = 3, message = "Must have at least 3 characters"))]
     pub name: String,
@@ -19,18 +22,21 @@ impl From<CreateRoleRequest> for ActiveModel {
     }
 }
 
-#[derive(Debug, Deserialize, Validate, DeriveIntoActiveModel)]
+#[derive(Debug, Deserialize, Validate, DeriveIntoActiveModel, ToSchema, InputObject)]
+#[graphql(name = "UpdateRoleInput")]
 pub struct UpdateRoleRequest {
     #[validate(length(min = 3, message = "Must have at least 3 characters"))]
     pub name: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema, InputObject)]
+#[graphql(name = "AssignRolesInput")]
 pub struct UpdateUserRolesRequest {
     pub roles: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema, InputObject)]
+#[graphql(name = "AssignPermissionsInput")]
 pub struct UpdateUserPermissionRequest {
     pub permissions: Vec<String>,
 }
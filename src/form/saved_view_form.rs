@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::models::{
+    saved_view::VISIBILITIES,
+    task::{COLUMNS, FILTERS, PRIORITIES, SORTS, STATUSES},
+};
+
+fn validate_slug(slug: &str) -> Result<(), ValidationError> {
+    let is_valid = !slug.is_empty()
+        && slug.len() <= 50
+        && !slug.starts_with('-')
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+
+    if !is_valid {
+        return Err(ValidationError::new("slug").with_message(
+            "Must be up to 50 lowercase letters, digits or dashes, not starting with a dash".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks the filters against the ones the task list supports. Ids are
+/// checked when the filters are applied.
+fn validate_filters(filters: &HashMap<String, String>) -> Result<(), ValidationError> {
+    for (filter, value) in filters {
+        let is_valid = match filter.as_str() {
+            "status" => STATUSES.contains(&value.as_str()),
+            "priority" => value == "none" || PRIORITIES.contains(&value.as_str()),
+            "label" => !value.is_empty() && !value.contains(','),
+            "watching" | "overdue" => value == "true" || value == "false",
+            _ => FILTERS.contains(&filter.as_str()),
+        };
+
+        if !is_valid {
+            return Err(ValidationError::new("filters")
+                .with_message(format!("Invalid filter {}={}", filter, value).into()));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_sort(sort: &str) -> Result<(), ValidationError> {
+    if !SORTS.contains(&sort) {
+        return Err(ValidationError::new("sort").with_message("Unknown sort order".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_columns(columns: &[String]) -> Result<(), ValidationError> {
+    if !columns
+        .iter()
+        .all(|column| COLUMNS.contains(&column.as_str()))
+    {
+        return Err(ValidationError::new("columns").with_message("Unknown column".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_visibility(visibility: &str) -> Result<(), ValidationError> {
+    if !VISIBILITIES.contains(&visibility) {
+        return Err(ValidationError::new("visibility").with_message("Unknown visibility".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_sharing(request: &SaveViewRequest) -> Result<(), ValidationError> {
+    if (request.visibility == "roles") == request.role_ids.is_empty() {
+        return Err(ValidationError::new("role_ids")
+            .with_message("Roles are required exactly when the visibility is roles".into()));
+    }
+
+    Ok(())
+}
+
+fn default_visibility() -> String {
+    "private".to_string()
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_sharing"))]
+pub struct SaveViewRequest {
+    #[validate(length(min = 1, max = 100, message = "Must have 1 to 100 characters"))]
+    pub name: String,
+    #[validate(custom(function = "validate_slug"))]
+    pub slug: String,
+    #[serde(default)]
+    #[validate(custom(function = "validate_filters"))]
+    pub filters: HashMap<String, String>,
+    #[validate(custom(function = "validate_sort"))]
+    pub sort: Option<String>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_columns"))]
+    pub columns: Vec<String>,
+    #[serde(default = "default_visibility")]
+    #[validate(custom(function = "validate_visibility"))]
+    pub visibility: String,
+    #[serde(default)]
+    pub role_ids: Vec<i32>,
+}
@@ -1,22 +1,339 @@
-use crate::models::_entities::task::ActiveModel;
-use sea_orm::DeriveIntoActiveModel;
+use crate::{
+    models::{
+        _entities::task::ActiveModel,
+        task::{join_labels, IMPORT_FIELDS, PRIORITIES, STATUSES},
+    },
+    recurrence::Recurrence,
+};
+use async_graphql::InputObject;
+use chrono::NaiveDate;
+use sea_orm::{IntoActiveModel, Set};
+use std::collections::HashMap;
 
-use serde::{Deserialize, Serialize};
-use validator::Validate;
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
 
-#[derive(Debug, Deserialize, Validate, DeriveIntoActiveModel)]
+fn validate_status(status: &str) -> Result<(), ValidationError> {
+    if !STATUSES.contains(&status) {
+        return Err(ValidationError::new("status").with_message("Unknown status".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_priority(priority: &str) -> Result<(), ValidationError> {
+    if !PRIORITIES.contains(&priority) {
+        return Err(ValidationError::new("priority").with_message("Unknown priority".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_recurrence(recurrence: &str) -> Result<(), ValidationError> {
+    if Recurrence::parse(recurrence).is_none() {
+        return Err(ValidationError::new("recurrence").with_message(
+            "Must be FREQ=DAILY, WEEKLY, MONTHLY or YEARLY, with an optional INTERVAL".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Stores `recurrence` in its canonical form, e.g. without `INTERVAL=1`.
+pub fn normalize_recurrence(recurrence: Option<String>) -> Option<String> {
+    recurrence
+        .map(|rule| Recurrence::parse(&rule).map_or(rule, |recurrence| recurrence.to_string()))
+}
+
+fn validate_estimate(estimate_minutes: i32) -> Result<(), ValidationError> {
+    if estimate_minutes < 0 {
+        return Err(
+            ValidationError::new("estimate_minutes").with_message("Must not be negative".into())
+        );
+    }
+
+    Ok(())
+}
+
+fn validate_labels(labels: &[String]) -> Result<(), ValidationError> {
+    if labels.len() > 20 {
+        return Err(ValidationError::new("labels").with_message("At most 20 labels".into()));
+    }
+
+    let is_valid = labels.iter().all(|label| {
+        (1..=50).contains(&label.chars().count())
+            && !label.chars().any(|c| c == ',' || c.is_whitespace())
+    });
+
+    if !is_valid {
+        return Err(ValidationError::new("labels")
+            .with_message("Labels must have 1 to 50 characters without commas or spaces".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_checklist(checklist: &[String]) -> Result<(), ValidationError> {
+    if !checklist
+        .iter()
+        .all(|text| (1..=500).contains(&text.chars().count()))
+    {
+        return Err(ValidationError::new("checklist")
+            .with_message("Checklist items must have 1 to 500 characters".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_import_format(format: &str) -> Result<(), ValidationError> {
+    if !["csv", "json", "ndjson", "todotxt", "markdown"].contains(&format) {
+        return Err(ValidationError::new("format")
+            .with_message("Must be one of csv, json, ndjson, todotxt or markdown".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_mapping(mapping: &HashMap<String, String>) -> Result<(), ValidationError> {
+    if let Some(field) = mapping
+        .values()
+        .find(|field| !IMPORT_FIELDS.contains(&field.as_str()))
+    {
+        return Err(ValidationError::new("mapping")
+            .with_message(format!("Tasks cannot be imported with a {} field", field).into()));
+    }
+
+    Ok(())
+}
+
+fn default_status() -> String {
+    STATUSES[0].to_string()
+}
+
+/// Tells an explicit `null` (`Some(None)`) apart from a missing field (`None`).
+fn deserialize_nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
+
+fn validate_reposition(request: &RepositionTaskRequest) -> Result<(), ValidationError> {
+    if request.before_id.is_some() && request.after_id.is_some() {
+        return Err(ValidationError::new("reposition")
+            .with_message("Only one of before_id and after_id can be given".into()));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema, InputObject)]
+#[graphql(name = "CreateTaskInput")]
 pub struct CreateTaskRequest {
     #[validate(length(min = 3, message = "Must have at least 3 characters"))]
     pub title: String,
     pub description: String,
+    #[validate(custom(function = "validate_status"))]
     pub status: String,
     pub user_id: i32,
+    pub project_id: Option<i32>,
+    #[serde(default)]
+    #[graphql(default)]
+    pub auto_complete: bool,
+    #[validate(custom(function = "validate_estimate"))]
+    pub estimate_minutes: Option<i32>,
+    pub due_date: Option<NaiveDate>,
+    #[validate(custom(function = "validate_priority"))]
+    pub priority: Option<String>,
+    #[serde(default)]
+    #[graphql(default)]
+    #[validate(custom(function = "validate_labels"))]
+    pub labels: Vec<String>,
+    /// Rule the task repeats by, e.g. `FREQ=WEEKLY;INTERVAL=2`.
+    #[serde(default)]
+    #[validate(custom(function = "validate_recurrence"))]
+    pub recurrence: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+impl IntoActiveModel<ActiveModel> for CreateTaskRequest {
+    fn into_active_model(self) -> ActiveModel {
+        ActiveModel {
+            title: Set(self.title),
+            description: Set(self.description),
+            status: Set(self.status),
+            user_id: Set(self.user_id),
+            project_id: Set(self.project_id),
+            auto_complete: Set(self.auto_complete),
+            estimate_minutes: Set(self.estimate_minutes),
+            due_date: Set(self.due_date),
+            priority: Set(self.priority),
+            labels: Set(join_labels(&self.labels)),
+            recurrence: Set(normalize_recurrence(self.recurrence)),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, ToSchema)]
 pub struct UpdateTaskRequest {
     #[validate(length(min = 3, message = "Must have at least 3 characters"))]
     pub title: String,
     pub description: Option<String>,
+    #[validate(custom(function = "validate_status"))]
+    pub status: String,
+    pub auto_complete: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(custom(function = "validate_estimate"))]
+    pub estimate_minutes: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub due_date: Option<Option<NaiveDate>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(custom(function = "validate_priority"))]
+    pub priority: Option<Option<String>>,
+    #[validate(custom(function = "validate_labels"))]
+    pub labels: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(custom(function = "validate_recurrence"))]
+    pub recurrence: Option<Option<String>>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema, InputObject)]
+#[graphql(name = "AssignTaskInput")]
+pub struct AssignTaskRequest {
+    #[validate(length(min = 1, message = "At least one user is required"))]
+    pub user_ids: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_reposition"))]
+pub struct RepositionTaskRequest {
+    #[validate(custom(function = "validate_status"))]
     pub status: String,
+    pub before_id: Option<i32>,
+    pub after_id: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateChecklistItemRequest {
+    #[validate(length(min = 1, max = 500, message = "Must have 1 to 500 characters"))]
+    pub text: String,
+    #[serde(default)]
+    pub is_done: bool,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateChecklistItemRequest {
+    #[validate(length(min = 1, max = 500, message = "Must have 1 to 500 characters"))]
+    pub text: Option<String>,
+    pub is_done: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReorderChecklistRequest {
+    pub item_ids: Vec<i32>,
+}
+
+/// One task of a template. The title, description and checklist may contain
+/// `{{name}}` placeholders.
+#[derive(Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct TemplateTaskRequest {
+    #[validate(length(min = 3, max = 200, message = "Must have 3 to 200 characters"))]
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_status")]
+    #[validate(custom(function = "validate_status"))]
+    pub status: String,
+    #[validate(custom(function = "validate_priority"))]
+    pub priority: Option<String>,
+    #[validate(custom(function = "validate_estimate"))]
+    pub estimate_minutes: Option<i32>,
+    /// Days between the start date given when instantiating and the due date.
+    #[validate(range(min = 0, max = 3650, message = "Must be 0 to 3650 days"))]
+    pub due_offset_days: Option<i32>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_labels"))]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    #[validate(
+        length(max = 100, message = "At most 100 checklist items"),
+        custom(function = "validate_checklist")
+    )]
+    pub checklist: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SaveTemplateRequest {
+    #[validate(length(min = 1, max = 100, message = "Must have 1 to 100 characters"))]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub project_id: Option<i32>,
+    #[validate(
+        length(min = 1, max = 100, message = "Must have 1 to 100 tasks"),
+        nested
+    )]
+    pub tasks: Vec<TemplateTaskRequest>,
+}
+
+#[derive(Debug, Default, Deserialize, Validate, ToSchema)]
+pub struct InstantiateTemplateRequest {
+    /// Overrides the project of the template, or the Inbox when it has none.
+    pub project_id: Option<i32>,
+    /// Due dates are offset from this date, today by default.
+    pub start_date: Option<NaiveDate>,
+    /// Values of the placeholders.
+    #[serde(default)]
+    pub values: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ImportTasksRequest {
+    #[validate(custom(function = "validate_import_format"))]
+    pub format: String,
+    /// The CSV, JSON array, newline delimited JSON, todo.txt or Markdown
+    /// checklist to import.
+    pub data: String,
+    /// Task field each column or key of `data` is imported as, for the ones
+    /// not already named after a field.
+    #[serde(default)]
+    #[validate(custom(function = "validate_mapping"))]
+    pub mapping: HashMap<String, String>,
+    /// Validates every row without creating any task.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Project of the rows without a project_id, the Inbox by default.
+    pub project_id: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct QuickAddRequest {
+    /// The task written as one line, e.g. `Pay rent tomorrow !high #finance`.
+    #[validate(length(min = 1, message = "Must not be empty"))]
+    pub text: String,
+    /// Project of the task, the Inbox by default.
+    pub project_id: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SyncRequest {
+    pub changes: Vec<SyncChange>,
+}
+
+/// A change made by a client while offline. Changes without an `id` create a
+/// task, the others update or delete the task as it was at `base_version`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SyncChange {
+    /// Identifies a task created offline in the results, until it has an id.
+    pub client_id: Option<String>,
+    pub id: Option<i32>,
+    #[serde(default)]
+    pub base_version: i32,
+    #[serde(default)]
+    pub deleted: bool,
+    /// The fields that changed, as in the JSON import.
+    #[serde(default)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
 }
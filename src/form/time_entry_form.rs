@@ -0,0 +1,37 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+fn validate_period(request: &CreateTimeEntryRequest) -> Result<(), ValidationError> {
+    if request.ended_at <= request.started_at {
+        return Err(
+            ValidationError::new("ended_at").with_message("Must be later than started_at".into())
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Default, Deserialize, Validate, ToSchema)]
+pub struct StartTimerRequest {
+    #[validate(length(max = 1000, message = "Must have at most 1000 characters"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_period"))]
+pub struct CreateTimeEntryRequest {
+    pub started_at: NaiveDateTime,
+    pub ended_at: NaiveDateTime,
+    #[validate(length(max = 1000, message = "Must have at most 1000 characters"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateTimeEntryRequest {
+    pub started_at: Option<NaiveDateTime>,
+    pub ended_at: Option<NaiveDateTime>,
+    #[validate(length(max = 1000, message = "Must have at most 1000 characters"))]
+    pub note: Option<String>,
+}
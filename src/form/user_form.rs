@@ -2,9 +2,10 @@ use crate::{models::_entities::user::ActiveModel, utils::hash};
 use sea_orm::Set;
 
 use serde::Deserialize;
-use validator::Validate;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
 
-#[derive(Debug, Deserialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Validate, Clone, ToSchema)]
 pub struct CreateUserRequest {
     #[validate(length(min = 3, message = "Must have at least 3 characters"))]
     pub name: String,
@@ -27,7 +28,7 @@ impl From<CreateUserRequest> for ActiveModel {
     }
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateUserRequest {
     #[validate(length(min = 3, message = "Must have at least 3 characters"))]
     pub name: String,
@@ -37,10 +38,31 @@ pub struct UpdateUserRequest {
     pub password: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UserLogin {
     #[validate(length(min = 3, message = "Must have at least 3 characters"))]
     pub username: String,
     #[validate(length(min = 8, message = "Must have at least 8 characters"))]
     pub password: String,
 }
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateCapacityRequest {
+    #[validate(range(min = 0, max = 10080, message = "Must be between 0 and 10080 minutes"))]
+    pub weekly_capacity_minutes: i32,
+}
+
+fn validate_timezone(timezone: &str) -> Result<(), ValidationError> {
+    if timezone.parse::<chrono_tz::Tz>().is_err() {
+        return Err(ValidationError::new("timezone")
+            .with_message("Must be an IANA time zone, such as Europe/Paris".into()));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateTimezoneRequest {
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: String,
+}
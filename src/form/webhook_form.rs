@@ -0,0 +1,55 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::webhook::{check_url, WebhookEvent};
+
+fn validate_event_types(event_types: &[String]) -> Result<(), ValidationError> {
+    if event_types.is_empty() {
+        return Err(ValidationError::new("event_types")
+            .with_message("At least one event type is required".into()));
+    }
+
+    if !event_types
+        .iter()
+        .all(|event_type| WebhookEvent::is_valid(event_type))
+    {
+        return Err(ValidationError::new("event_types").with_message("Unknown event type".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_target(url: &str) -> Result<(), ValidationError> {
+    check_url(url)
+        .map(|_| ())
+        .map_err(|err| ValidationError::new("url").with_message(err.into()))
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateWebhookRequest {
+    #[validate(
+        url(message = "Must be a valid URL"),
+        custom(function = "validate_target")
+    )]
+    pub url: String,
+    #[validate(length(min = 16, message = "Must have at least 16 characters"))]
+    pub secret: String,
+    #[validate(custom(function = "validate_event_types"))]
+    pub event_types: Vec<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateWebhookRequest {
+    #[validate(
+        url(message = "Must be a valid URL"),
+        custom(function = "validate_target")
+    )]
+    pub url: String,
+    #[validate(length(min = 16, message = "Must have at least 16 characters"))]
+    pub secret: Option<String>,
+    #[validate(custom(function = "validate_event_types"))]
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+}
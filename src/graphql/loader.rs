@@ -0,0 +1,260 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::dataloader::Loader;
+use sea_orm::{
+    sea_query::{Alias, Expr, Query},
+    ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Related,
+};
+
+use crate::{
+    models::{
+        _entities::{permission, role, task, user, user_profile},
+        task::{in_projects_of, with_details},
+    },
+    serializer::TaskSerializer,
+};
+
+/// Key of a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UserId(pub i32);
+
+/// Key of the profile of a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProfileOf(pub i32);
+
+/// Key of the roles of a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RolesOf(pub i32);
+
+/// Key of the direct permissions of a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PermissionsOf(pub i32);
+
+/// Key of the newest tasks a user reported, at most `limit` of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RecentTasksOf {
+    pub user_id: i32,
+    pub limit: u64,
+}
+
+/// Loads the relations of the users a query selects in one query per
+/// relation, rather than one per user.
+pub struct AppLoader {
+    db: DatabaseConnection,
+    /// The user whose projects tasks are limited to, if not every project.
+    member_id: Option<i32>,
+}
+
+impl AppLoader {
+    pub fn new(db: DatabaseConnection, member_id: Option<i32>) -> Self {
+        Self { db, member_id }
+    }
+
+    /// The users of `user_ids` with their models of `R`.
+    async fn with_related<R>(
+        &self,
+        user_ids: impl Iterator<Item = i32>,
+    ) -> Result<Vec<(user::Model, Vec<R::Model>)>, DbErr>
+    where
+        R: EntityTrait,
+        user::Entity: Related<R>,
+    {
+        user::Entity::find()
+            .filter(user::Column::Id.is_in(user_ids))
+            .find_with_related(R::default())
+            .all(&self.db)
+            .await
+    }
+}
+
+impl Loader<UserId> for AppLoader {
+    type Value = user::Model;
+    type Error = Arc<DbErr>;
+
+    async fn load(&self, keys: &[UserId]) -> Result<HashMap<UserId, Self::Value>, Self::Error> {
+        Ok(user::Entity::find()
+            .filter(user::Column::Id.is_in(keys.iter().map(|key| key.0)))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|user| (UserId(user.id), user))
+            .collect())
+    }
+}
+
+impl Loader<ProfileOf> for AppLoader {
+    type Value = user_profile::Model;
+    type Error = Arc<DbErr>;
+
+    async fn load(
+        &self,
+        keys: &[ProfileOf],
+    ) -> Result<HashMap<ProfileOf, Self::Value>, Self::Error> {
+        Ok(self
+            .with_related::<user_profile::Entity>(keys.iter().map(|key| key.0))
+            .await?
+            .into_iter()
+            .filter_map(|(user, profiles)| Some((ProfileOf(user.id), profiles.into_iter().next()?)))
+            .collect())
+    }
+}
+
+impl Loader<RolesOf> for AppLoader {
+    type Value = Vec<role::Model>;
+    type Error = Arc<DbErr>;
+
+    async fn load(&self, keys: &[RolesOf]) -> Result<HashMap<RolesOf, Self::Value>, Self::Error> {
+        Ok(self
+            .with_related::<role::Entity>(keys.iter().map(|key| key.0))
+            .await?
+            .into_iter()
+            .map(|(user, roles)| (RolesOf(user.id), roles))
+            .collect())
+    }
+}
+
+impl Loader<PermissionsOf> for AppLoader {
+    type Value = Vec<permission::Model>;
+    type Error = Arc<DbErr>;
+
+    async fn load(
+        &self,
+        keys: &[PermissionsOf],
+    ) -> Result<HashMap<PermissionsOf, Self::Value>, Self::Error> {
+        Ok(self
+            .with_related::<permission::Entity>(keys.iter().map(|key| key.0))
+            .await?
+            .into_iter()
+            .map(|(user, permissions)| (PermissionsOf(user.id), permissions))
+            .collect())
+    }
+}
+
+impl Loader<RecentTasksOf> for AppLoader {
+    type Value = Vec<TaskSerializer>;
+    type Error = Arc<DbErr>;
+
+    async fn load(
+        &self,
+        keys: &[RecentTasksOf],
+    ) -> Result<HashMap<RecentTasksOf, Self::Value>, Self::Error> {
+        // Numbers the tasks of each user from the newest, to load at most the
+        // largest limit of them.
+        let mut scope =
+            Condition::all().add(task::Column::UserId.is_in(keys.iter().map(|key| key.user_id)));
+
+        if let Some(member_id) = self.member_id {
+            scope = scope.add(in_projects_of(member_id));
+        }
+
+        let ranked = Query::select()
+            .column(task::Column::Id)
+            .expr_as(
+                Expr::cust(
+                    r#"ROW_NUMBER() OVER (PARTITION BY "task"."user_id" ORDER BY "task"."date_created" DESC, "task"."id" DESC)"#,
+                ),
+                Alias::new("position"),
+            )
+            .from(task::Entity)
+            .cond_where(scope)
+            .to_owned();
+
+        let limit = keys.iter().map(|key| key.limit).max().unwrap_or(0);
+
+        let mut tasks_of: HashMap<i32, Vec<task::Model>> = HashMap::new();
+
+        for task in task::Entity::find()
+            .filter(
+                task::Column::Id.in_subquery(
+                    Query::select()
+                        .column(Alias::new("id"))
+                        .from_subquery(ranked, Alias::new("ranked"))
+                        .and_where(Expr::col(Alias::new("position")).lte(limit))
+                        .to_owned(),
+                ),
+            )
+            .order_by_desc(task::Column::DateCreated)
+            .order_by_desc(task::Column::Id)
+            .all(&self.db)
+            .await?
+        {
+            tasks_of.entry(task.user_id).or_default().push(task);
+        }
+
+        // Only the tasks some key returns need their assignees and checklists.
+        let recent = |key: &RecentTasksOf| {
+            tasks_of
+                .get(&key.user_id)
+                .into_iter()
+                .flatten()
+                .take(key.limit as usize)
+        };
+
+        let needed: HashMap<i32, task::Model> = keys
+            .iter()
+            .flat_map(recent)
+            .map(|task| (task.id, task.clone()))
+            .collect();
+
+        let tasks: HashMap<i32, TaskSerializer> =
+            with_details(&self.db, needed.into_values().collect())
+                .await?
+                .into_iter()
+                .map(|task| (task.0.id, TaskSerializer::from(task)))
+                .collect();
+
+        Ok(keys
+            .iter()
+            .map(|key| {
+                let serializers = recent(key).map(|task| tasks[&task.id].clone()).collect();
+
+                (*key, serializers)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{app_state, create_project, create_task, create_user};
+
+    #[tokio::test]
+    async fn recent_tasks_are_limited_per_user() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let carol = create_user(&app_state, "carol").await;
+        let project = create_project(&app_state, &alice, "ops", &[&bob]).await;
+
+        for title in ["First", "Second", "Third"] {
+            create_task(&app_state, &alice, &project, title).await;
+        }
+        create_task(&app_state, &bob, &project, "Only").await;
+
+        let key = |user: &user::Model, limit| RecentTasksOf {
+            user_id: user.id,
+            limit,
+        };
+        let keys = [key(&alice, 2), key(&alice, 1), key(&bob, 2)];
+
+        let loader = AppLoader::new(app_state.db.clone(), None);
+        let recent = loader.load(&keys).await.unwrap();
+        let titles = |key| -> Vec<String> {
+            recent[&key]
+                .iter()
+                .map(|task: &TaskSerializer| task.title.clone())
+                .collect()
+        };
+
+        assert_eq!(titles(keys[0]), vec!["Third", "Second"]);
+        assert_eq!(titles(keys[1]), vec!["Third"]);
+        assert_eq!(titles(keys[2]), vec!["Only"]);
+
+        let loader = AppLoader::new(app_state.db.clone(), Some(carol.id));
+        let recent = loader.load(&keys).await.unwrap();
+
+        assert!(recent.values().all(|tasks| tasks.is_empty()));
+    }
+}
@@ -0,0 +1,99 @@
+//! GraphQL API over the same models, permission checks and validation as the
+//! REST controllers, for clients that want related records in one request.
+
+use std::sync::{Arc, OnceLock};
+
+use async_graphql::{
+    dataloader::DataLoader, EmptySubscription, ErrorExtensions, Request, Response,
+};
+
+use crate::{
+    error::AppError,
+    models::{
+        _entities::user,
+        project::{has_global_permission, ProjectPermission},
+    },
+    AppState,
+};
+
+mod loader;
+mod mutation;
+mod query;
+mod types;
+
+use loader::AppLoader;
+use mutation::MutationRoot;
+use query::QueryRoot;
+
+pub type AppSchema = async_graphql::Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// How deeply selections may nest, introspection included.
+const MAX_DEPTH: usize = 16;
+
+/// Highest cost of a query. Each field costs one, and lists cost as many
+/// times their fields as they return items at most.
+const MAX_COMPLEXITY: usize = 1000;
+
+pub fn schema() -> &'static AppSchema {
+    static SCHEMA: OnceLock<AppSchema> = OnceLock::new();
+
+    SCHEMA.get_or_init(|| {
+        AppSchema::build(QueryRoot, MutationRoot, EmptySubscription)
+            .limit_depth(MAX_DEPTH)
+            .limit_complexity(MAX_COMPLEXITY)
+            .finish()
+    })
+}
+
+/// Runs `request` on behalf of `user`, batching the relations it selects.
+pub async fn execute(
+    app_state: Arc<AppState>,
+    user: user::Model,
+    request: Request,
+) -> Result<Response, AppError> {
+    // Tasks outside of the projects of `user` stay hidden, as in the task list.
+    let member_id = if has_global_permission(&app_state.db, &user, ProjectPermission::View).await? {
+        None
+    } else {
+        Some(user.id)
+    };
+
+    let loader = DataLoader::new(
+        AppLoader::new(app_state.db.clone(), member_id),
+        tokio::spawn,
+    );
+
+    Ok(schema()
+        .execute(request.data(app_state).data(user).data(loader))
+        .await)
+}
+
+impl From<AppError> for async_graphql::Error {
+    fn from(value: AppError) -> Self {
+        let (status_code, message) = value.status_and_message();
+
+        async_graphql::Error::new(message).extend_with(|_, extensions| {
+            extensions.set("status", status_code.as_u16());
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rejects_deep_and_costly_queries() {
+        let deep = format!(
+            "{{ me {}{{ id }}{} }}",
+            "{ tasks(limit: 1) { reporter ".repeat(9),
+            " } }".repeat(9)
+        );
+        let response = schema().execute(deep.as_str()).await;
+        assert_eq!(response.errors[0].message, "Query is nested too deep.");
+
+        let costly = "{ users { tasks(limit: 50) { id title assignees { id name } } } }";
+        let response = schema().execute(costly).await;
+        assert_eq!(response.errors[0].message, "Query is too complex.");
+    }
+}
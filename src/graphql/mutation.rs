@@ -0,0 +1,246 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, InputObject, MaybeUndefined, Object, Result};
+use chrono::NaiveDate;
+use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
+use validator::Validate;
+
+use crate::{
+    controller::{
+        task_controller::{assign_users, find_task, insert_task, modify_task, remove_task},
+        user_controller::{add_user_permissions, add_user_roles},
+    },
+    error::AppError,
+    form::{
+        permission_form::CreatePermissionRequest,
+        role_form::{
+            CreateRoleRequest, UpdateRoleRequest, UpdateUserPermissionRequest,
+            UpdateUserRolesRequest,
+        },
+        task_form::{AssignTaskRequest, CreateTaskRequest, UpdateTaskRequest},
+    },
+    models::{
+        _entities::{permission, role, user},
+        project::ProjectPermission,
+        task::TaskRef,
+    },
+    serializer::{PermissionSerializer, RoleSerializer, TaskSerializer},
+    AppState,
+};
+
+/// Changes to a task, as with `PUT /api/tasks/{task_id}`. Fields left out
+/// are kept, while `null` clears them.
+#[derive(Debug, InputObject)]
+pub struct UpdateTaskInput {
+    pub title: String,
+    pub description: String,
+    pub status: String,
+    pub auto_complete: Option<bool>,
+    pub estimate_minutes: MaybeUndefined<i32>,
+    pub due_date: MaybeUndefined<NaiveDate>,
+    pub priority: MaybeUndefined<String>,
+    pub labels: Option<Vec<String>>,
+    pub recurrence: MaybeUndefined<String>,
+}
+
+impl From<UpdateTaskInput> for UpdateTaskRequest {
+    fn from(value: UpdateTaskInput) -> Self {
+        Self {
+            title: value.title,
+            description: Some(value.description),
+            status: value.status,
+            auto_complete: value.auto_complete,
+            estimate_minutes: value.estimate_minutes.into(),
+            due_date: value.due_date.into(),
+            priority: value.priority.into(),
+            labels: value.labels,
+            recurrence: value.recurrence.into(),
+        }
+    }
+}
+
+fn task_ref(id: String) -> Result<TaskRef, AppError> {
+    TaskRef::try_from(id).map_err(AppError::BadRequest)
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_task(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateTaskRequest,
+    ) -> Result<TaskSerializer> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+        let user = ctx.data::<user::Model>()?;
+
+        Ok(insert_task(app_state, user, input).await?)
+    }
+
+    async fn update_task(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: UpdateTaskInput,
+    ) -> Result<TaskSerializer> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+        let user = ctx.data::<user::Model>()?;
+
+        Ok(modify_task(app_state, user.clone(), task_ref(id)?, input.into()).await?)
+    }
+
+    async fn delete_task(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+        let user = ctx.data::<user::Model>()?;
+
+        let task = find_task(
+            app_state,
+            user,
+            task_ref(id)?,
+            ProjectPermission::ChangeTasks,
+        )
+        .await?;
+
+        remove_task(app_state, task).await?;
+
+        Ok(true)
+    }
+
+    async fn assign_task(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: AssignTaskRequest,
+    ) -> Result<TaskSerializer> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+        let user = ctx.data::<user::Model>()?;
+
+        Ok(assign_users(app_state, user.clone(), task_ref(id)?, input).await?)
+    }
+
+    async fn create_role(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateRoleRequest,
+    ) -> Result<RoleSerializer> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        input.validate().map_err(AppError::from)?;
+
+        Ok(input
+            .into_active_model()
+            .insert(&app_state.db)
+            .await?
+            .into())
+    }
+
+    async fn update_role(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        input: UpdateRoleRequest,
+    ) -> Result<RoleSerializer> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        let role = role::Entity::find_by_id(id)
+            .one(&app_state.db)
+            .await?
+            .ok_or(AppError::from(sqlx::Error::RowNotFound))?;
+
+        input.validate().map_err(AppError::from)?;
+
+        let mut role: role::ActiveModel = role.into();
+
+        role.name = Set(input.name);
+
+        Ok(role.update(&app_state.db).await?.into())
+    }
+
+    async fn delete_role(&self, ctx: &Context<'_>, id: i32) -> Result<bool> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        role::Entity::delete_by_id(id).exec(&app_state.db).await?;
+
+        Ok(true)
+    }
+
+    async fn create_permission(
+        &self,
+        ctx: &Context<'_>,
+        input: CreatePermissionRequest,
+    ) -> Result<PermissionSerializer> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        input.validate().map_err(AppError::from)?;
+
+        Ok(input
+            .into_active_model()
+            .insert(&app_state.db)
+            .await?
+            .into())
+    }
+
+    async fn update_permission(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        input: CreatePermissionRequest,
+    ) -> Result<PermissionSerializer> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        let permission = permission::Entity::find_by_id(id)
+            .one(&app_state.db)
+            .await?
+            .ok_or(AppError::from(sqlx::Error::RowNotFound))?;
+
+        input.validate().map_err(AppError::from)?;
+
+        let mut permission: permission::ActiveModel = permission.into();
+
+        permission.name = Set(input.name);
+        permission.code_name = Set(input.code_name);
+
+        Ok(permission.update(&app_state.db).await?.into())
+    }
+
+    async fn delete_permission(&self, ctx: &Context<'_>, id: i32) -> Result<bool> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        permission::Entity::delete_by_id(id)
+            .exec(&app_state.db)
+            .await?;
+
+        Ok(true)
+    }
+
+    /// Gives the user the roles they do not have yet, and returns the names
+    /// of the roles added.
+    async fn assign_roles(
+        &self,
+        ctx: &Context<'_>,
+        user_id: i32,
+        input: UpdateUserRolesRequest,
+    ) -> Result<Vec<String>> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        Ok(add_user_roles(&app_state.db, user_id, input).await?)
+    }
+
+    /// Gives the user the permissions they do not have yet, and returns the
+    /// permissions added.
+    async fn assign_permissions(
+        &self,
+        ctx: &Context<'_>,
+        user_id: i32,
+        input: UpdateUserPermissionRequest,
+    ) -> Result<Vec<PermissionSerializer>> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        Ok(add_user_permissions(&app_state.db, user_id, input)
+            .await?
+            .into_iter()
+            .map(PermissionSerializer::from)
+            .collect())
+    }
+}
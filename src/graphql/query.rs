@@ -0,0 +1,175 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::{Context, Object, Result};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder};
+
+use super::types::User;
+use crate::{
+    controller::task_controller::{find_task, serialize_task, task_list_query},
+    error::AppError,
+    models::{
+        _entities::{permission, role, user},
+        project::ProjectPermission,
+        task::{with_details, TaskRef},
+    },
+    serializer::{PermissionSerializer, RoleSerializer, TaskSerializer},
+    AppState,
+};
+
+/// Items per page of the lists, as in the REST API.
+const PAGE_SIZE: u64 = 10;
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The signed in user.
+    async fn me(&self, ctx: &Context<'_>) -> Result<User> {
+        Ok(User(ctx.data::<user::Model>()?.clone()))
+    }
+
+    async fn user(&self, ctx: &Context<'_>, id: i32) -> Result<Option<User>> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        Ok(user::Entity::find_by_id(id)
+            .one(&app_state.db)
+            .await?
+            .map(User))
+    }
+
+    /// Users whose fields contain the given values, newest first.
+    #[graphql(complexity = "PAGE_SIZE as usize * child_complexity")]
+    async fn users(
+        &self,
+        ctx: &Context<'_>,
+        name: Option<String>,
+        username: Option<String>,
+        email: Option<String>,
+        #[graphql(default = 1, validator(minimum = 1))] page: u64,
+    ) -> Result<Vec<User>> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        let mut user_query = user::Entity::find();
+
+        if let Some(name) = name {
+            user_query = user_query.filter(user::Column::Name.contains(name));
+        }
+
+        if let Some(username) = username {
+            user_query = user_query.filter(user::Column::Username.contains(username));
+        }
+
+        if let Some(email) = email {
+            user_query = user_query.filter(user::Column::Email.contains(email));
+        }
+
+        Ok(user_query
+            .order_by(user::Column::DateCreated, sea_orm::Order::Desc)
+            .paginate(&app_state.db, PAGE_SIZE)
+            .fetch_page(page - 1)
+            .await?
+            .into_iter()
+            .map(User)
+            .collect())
+    }
+
+    /// A task by id or key, e.g. `42` or `OPS-42`.
+    async fn task(&self, ctx: &Context<'_>, id: String) -> Result<TaskSerializer> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+        let user = ctx.data::<user::Model>()?;
+
+        let task_ref = TaskRef::try_from(id).map_err(AppError::BadRequest)?;
+        let task = find_task(app_state, user, task_ref, ProjectPermission::View).await?;
+
+        Ok(serialize_task(&app_state.db, task).await?)
+    }
+
+    /// The task list, with the filters of `GET /api/tasks`.
+    #[allow(clippy::too_many_arguments)]
+    #[graphql(complexity = "PAGE_SIZE as usize * child_complexity")]
+    async fn tasks(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<String>,
+        project_id: Option<i32>,
+        assignee: Option<String>,
+        watching: Option<bool>,
+        priority: Option<String>,
+        label: Option<String>,
+        overdue: Option<bool>,
+        sort: Option<String>,
+        view: Option<String>,
+        #[graphql(default = 1, validator(minimum = 1))] page: u64,
+    ) -> Result<Vec<TaskSerializer>> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+        let user = ctx.data::<user::Model>()?;
+
+        let mut params: HashMap<String, String> = [
+            ("status", status),
+            ("project_id", project_id.map(|id| id.to_string())),
+            ("assignee", assignee),
+            ("watching", watching.map(|watching| watching.to_string())),
+            ("priority", priority),
+            ("label", label),
+            ("overdue", overdue.map(|overdue| overdue.to_string())),
+            ("sort", sort),
+            ("view", view),
+        ]
+        .into_iter()
+        .filter_map(|(param, value)| Some((param.to_string(), value?)))
+        .collect();
+
+        let (task_query, _) = task_list_query(app_state, user, &mut params).await?;
+
+        let tasks = task_query
+            .paginate(&app_state.db, PAGE_SIZE)
+            .fetch_page(page - 1)
+            .await?;
+
+        Ok(with_details(&app_state.db, tasks)
+            .await?
+            .into_iter()
+            .map(TaskSerializer::from)
+            .collect())
+    }
+
+    async fn roles(&self, ctx: &Context<'_>) -> Result<Vec<RoleSerializer>> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        Ok(role::Entity::find()
+            .all(&app_state.db)
+            .await?
+            .into_iter()
+            .map(RoleSerializer::from)
+            .collect())
+    }
+
+    async fn role(&self, ctx: &Context<'_>, id: i32) -> Result<Option<RoleSerializer>> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        Ok(role::Entity::find_by_id(id)
+            .one(&app_state.db)
+            .await?
+            .map(RoleSerializer::from))
+    }
+
+    async fn permissions(&self, ctx: &Context<'_>) -> Result<Vec<PermissionSerializer>> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        Ok(permission::Entity::find()
+            .all(&app_state.db)
+            .await?
+            .into_iter()
+            .map(PermissionSerializer::from)
+            .collect())
+    }
+
+    async fn permission(&self, ctx: &Context<'_>, id: i32) -> Result<Option<PermissionSerializer>> {
+        let app_state = ctx.data::<Arc<AppState>>()?;
+
+        Ok(permission::Entity::find_by_id(id)
+            .one(&app_state.db)
+            .await?
+            .map(PermissionSerializer::from))
+    }
+}
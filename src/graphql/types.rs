@@ -0,0 +1,94 @@
+use async_graphql::{dataloader::DataLoader, ComplexObject, Context, Object, Result};
+use chrono::NaiveDateTime;
+
+use super::loader::{AppLoader, PermissionsOf, ProfileOf, RecentTasksOf, RolesOf, UserId};
+use crate::{
+    models::_entities::user,
+    serializer::{PermissionSerializer, RoleSerializer, TaskSerializer, UserProfileSerializer},
+};
+
+/// A user with the relations shown next to them on the dashboard.
+pub struct User(pub user::Model);
+
+#[Object]
+impl User {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn username(&self) -> &str {
+        &self.0.username
+    }
+
+    async fn email(&self) -> &str {
+        &self.0.email
+    }
+
+    async fn date_created(&self) -> NaiveDateTime {
+        self.0.date_created
+    }
+
+    async fn profile(&self, ctx: &Context<'_>) -> Result<Option<UserProfileSerializer>> {
+        let loader = ctx.data::<DataLoader<AppLoader>>()?;
+
+        Ok(loader
+            .load_one(ProfileOf(self.0.id))
+            .await?
+            .map(UserProfileSerializer::from))
+    }
+
+    async fn roles(&self, ctx: &Context<'_>) -> Result<Vec<RoleSerializer>> {
+        let loader = ctx.data::<DataLoader<AppLoader>>()?;
+
+        Ok(loader
+            .load_one(RolesOf(self.0.id))
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(RoleSerializer::from)
+            .collect())
+    }
+
+    /// Permissions given to the user directly, not through their roles.
+    async fn permissions(&self, ctx: &Context<'_>) -> Result<Vec<PermissionSerializer>> {
+        let loader = ctx.data::<DataLoader<AppLoader>>()?;
+
+        Ok(loader
+            .load_one(PermissionsOf(self.0.id))
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(PermissionSerializer::from)
+            .collect())
+    }
+
+    /// The newest tasks the user reported, among the ones the viewer can see.
+    #[graphql(complexity = "limit as usize * child_complexity")]
+    async fn tasks(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 10, validator(minimum = 1, maximum = 50))] limit: u64,
+    ) -> Result<Vec<TaskSerializer>> {
+        let loader = ctx.data::<DataLoader<AppLoader>>()?;
+
+        let key = RecentTasksOf {
+            user_id: self.0.id,
+            limit,
+        };
+
+        Ok(loader.load_one(key).await?.unwrap_or_default())
+    }
+}
+
+#[ComplexObject]
+impl TaskSerializer {
+    async fn reporter(&self, ctx: &Context<'_>) -> Result<Option<User>> {
+        let loader = ctx.data::<DataLoader<AppLoader>>()?;
+
+        Ok(loader.load_one(UserId(self.reporter_id)).await?.map(User))
+    }
+}
@@ -0,0 +1,82 @@
+//! gRPC API of `proto/task_app.proto`, for internal services that call the
+//! task API too often for JSON. It serves on its own port, next to the HTTP
+//! API, with the same database connection and tokens.
+
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use axum::http::StatusCode;
+use tokio::net::TcpListener;
+use tokio_stream::{wrappers::TcpListenerStream, Stream};
+use tonic::{transport::Server, Code, Request, Status};
+
+use crate::{error::AppError, models::_entities::user, utils::verify_token, AppState};
+
+mod proto;
+mod task_service;
+mod user_service;
+
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/task_app.TaskService.rs"));
+    include!(concat!(env!("OUT_DIR"), "/task_app.UserService.rs"));
+}
+
+use generated::{task_service_server::TaskServiceServer, user_service_server::UserServiceServer};
+use task_service::TaskGrpc;
+use user_service::UserGrpc;
+
+/// Items fetched at a time by the streaming list RPCs.
+const STREAM_PAGE_SIZE: u64 = 100;
+
+/// Messages of a server streaming RPC.
+type ServerStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// Serves the gRPC services on `listener` until `shutdown` completes.
+pub async fn serve(
+    app_state: Arc<AppState>,
+    listener: TcpListener,
+    shutdown: impl Future<Output = ()>,
+) {
+    let result = Server::builder()
+        .add_service(TaskServiceServer::new(TaskGrpc::new(app_state.clone())))
+        .add_service(UserServiceServer::new(UserGrpc::new(app_state)))
+        .serve_with_incoming_shutdown(TcpListenerStream::new(listener), shutdown)
+        .await;
+
+    if let Err(e) = result {
+        tracing::error!("gRPC server failed: {}", e);
+    }
+}
+
+/// The user whose token is in the `authorization` metadata of `request`, as
+/// `Bearer <token>`.
+async fn authenticate<T>(
+    app_state: &Arc<AppState>,
+    request: &Request<T>,
+) -> Result<user::Model, Status> {
+    let token = request
+        .metadata()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(Status::unauthenticated("No token found in metadata."))?;
+
+    verify_token(app_state.clone(), token)
+        .await
+        .map_err(|_| Status::unauthenticated("Invalid token."))
+}
+
+impl From<AppError> for Status {
+    fn from(value: AppError) -> Self {
+        let (status_code, message) = value.status_and_message();
+
+        let code = match status_code {
+            StatusCode::BAD_REQUEST => Code::InvalidArgument,
+            StatusCode::UNAUTHORIZED => Code::PermissionDenied,
+            StatusCode::NOT_FOUND => Code::NotFound,
+            StatusCode::TOO_MANY_REQUESTS => Code::ResourceExhausted,
+            _ => Code::Internal,
+        };
+
+        Status::new(code, message)
+    }
+}
@@ -0,0 +1,649 @@
+//! Messages of `proto/task_app.proto`.
+
+use crate::{
+    error::AppError,
+    form::task_form,
+    models::_entities::{user, user_profile},
+    serializer::{ChecklistProgressSerializer, TaskSerializer, UserSerializer},
+};
+
+/// How dates and times are written, as in the `.proto` file.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UserSummary {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(string, tag = "3")]
+    pub username: String,
+    #[prost(string, tag = "4")]
+    pub email: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ChecklistProgress {
+    #[prost(int64, tag = "1")]
+    pub done: i64,
+    #[prost(int64, tag = "2")]
+    pub total: i64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Task {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub title: String,
+    #[prost(string, tag = "3")]
+    pub description: String,
+    #[prost(string, tag = "4")]
+    pub status: String,
+    #[prost(int32, tag = "5")]
+    pub reporter_id: i32,
+    #[prost(string, optional, tag = "6")]
+    pub key: Option<String>,
+    #[prost(int32, optional, tag = "7")]
+    pub project_id: Option<i32>,
+    #[prost(string, tag = "8")]
+    pub rank: String,
+    #[prost(message, repeated, tag = "9")]
+    pub assignees: Vec<UserSummary>,
+    #[prost(message, optional, tag = "10")]
+    pub checklist: Option<ChecklistProgress>,
+    #[prost(bool, tag = "11")]
+    pub auto_complete: bool,
+    #[prost(int32, optional, tag = "12")]
+    pub estimate_minutes: Option<i32>,
+    #[prost(string, optional, tag = "13")]
+    pub due_date: Option<String>,
+    #[prost(string, optional, tag = "14")]
+    pub priority: Option<String>,
+    #[prost(string, repeated, tag = "15")]
+    pub labels: Vec<String>,
+    #[prost(string, optional, tag = "16")]
+    pub recurrence: Option<String>,
+    #[prost(string, tag = "17")]
+    pub date_created: String,
+    #[prost(string, optional, tag = "18")]
+    pub date_updated: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetTaskRequest {
+    #[prost(string, tag = "1")]
+    pub id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListTasksRequest {
+    #[prost(string, optional, tag = "1")]
+    pub status: Option<String>,
+    #[prost(int32, optional, tag = "2")]
+    pub project_id: Option<i32>,
+    #[prost(string, optional, tag = "3")]
+    pub assignee: Option<String>,
+    #[prost(bool, tag = "4")]
+    pub watching: bool,
+    #[prost(string, optional, tag = "5")]
+    pub priority: Option<String>,
+    #[prost(string, optional, tag = "6")]
+    pub label: Option<String>,
+    #[prost(bool, tag = "7")]
+    pub overdue: bool,
+    #[prost(string, optional, tag = "8")]
+    pub sort: Option<String>,
+    #[prost(string, optional, tag = "9")]
+    pub view: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CreateTaskRequest {
+    #[prost(string, tag = "1")]
+    pub title: String,
+    #[prost(string, tag = "2")]
+    pub description: String,
+    #[prost(string, tag = "3")]
+    pub status: String,
+    #[prost(int32, tag = "4")]
+    pub user_id: i32,
+    #[prost(int32, optional, tag = "5")]
+    pub project_id: Option<i32>,
+    #[prost(bool, tag = "6")]
+    pub auto_complete: bool,
+    #[prost(int32, optional, tag = "7")]
+    pub estimate_minutes: Option<i32>,
+    #[prost(string, optional, tag = "8")]
+    pub due_date: Option<String>,
+    #[prost(string, optional, tag = "9")]
+    pub priority: Option<String>,
+    #[prost(string, repeated, tag = "10")]
+    pub labels: Vec<String>,
+    #[prost(string, optional, tag = "11")]
+    pub recurrence: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UpdateTaskRequest {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub title: String,
+    #[prost(string, tag = "3")]
+    pub description: String,
+    #[prost(string, tag = "4")]
+    pub status: String,
+    #[prost(bool, optional, tag = "5")]
+    pub auto_complete: Option<bool>,
+    #[prost(int32, optional, tag = "6")]
+    pub estimate_minutes: Option<i32>,
+    #[prost(string, optional, tag = "7")]
+    pub due_date: Option<String>,
+    #[prost(string, optional, tag = "8")]
+    pub priority: Option<String>,
+    #[prost(string, repeated, tag = "9")]
+    pub labels: Vec<String>,
+    #[prost(bool, tag = "10")]
+    pub replace_labels: bool,
+    #[prost(string, optional, tag = "11")]
+    pub recurrence: Option<String>,
+    #[prost(string, repeated, tag = "12")]
+    pub clear: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeleteTaskRequest {
+    #[prost(string, tag = "1")]
+    pub id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeleteTaskResponse {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct WatchTasksRequest {
+    #[prost(uint64, optional, tag = "1")]
+    pub last_event_id: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TaskEvent {
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    #[prost(string, tag = "2")]
+    pub kind: String,
+    #[prost(int32, tag = "3")]
+    pub task_id: i32,
+    #[prost(message, optional, tag = "4")]
+    pub task: Option<Task>,
+    #[prost(bool, tag = "5")]
+    pub resync: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UserProfile {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, optional, tag = "2")]
+    pub address: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub mobile_number: Option<String>,
+    #[prost(int32, tag = "4")]
+    pub weekly_capacity_minutes: i32,
+    #[prost(string, tag = "5")]
+    pub timezone: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct User {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(string, tag = "3")]
+    pub username: String,
+    #[prost(string, tag = "4")]
+    pub email: String,
+    #[prost(message, optional, tag = "5")]
+    pub profile: Option<UserProfile>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetCurrentUserRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetUserRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListUsersRequest {
+    #[prost(string, optional, tag = "1")]
+    pub name: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub username: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub email: Option<String>,
+}
+
+/// Fields of a task that `UpdateTaskRequest.clear` can clear.
+const CLEARABLE_FIELDS: [&str; 4] = ["estimate_minutes", "due_date", "priority", "recurrence"];
+
+/// Reads a date written as `DATE_FORMAT`.
+fn parse_date(date: Option<String>) -> Result<Option<chrono::NaiveDate>, AppError> {
+    date.map(|date| {
+        chrono::NaiveDate::parse_from_str(&date, DATE_FORMAT).map_err(|_| {
+            AppError::BadRequest(format!("Invalid date {}, expected YYYY-MM-DD.", date))
+        })
+    })
+    .transpose()
+}
+
+/// The change to the nullable `field`: cleared, set to `value`, or kept.
+fn nullable<T>(
+    field: &str,
+    value: Option<T>,
+    clear: &[String],
+) -> Result<Option<Option<T>>, AppError> {
+    match (value, clear.iter().any(|cleared| cleared == field)) {
+        (Some(_), true) => Err(AppError::BadRequest(format!(
+            "{} cannot be both set and cleared.",
+            field
+        ))),
+        (None, true) => Ok(Some(None)),
+        (value, false) => Ok(value.map(Some)),
+    }
+}
+
+impl TryFrom<CreateTaskRequest> for task_form::CreateTaskRequest {
+    type Error = AppError;
+
+    fn try_from(value: CreateTaskRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            title: value.title,
+            description: value.description,
+            status: value.status,
+            user_id: value.user_id,
+            project_id: value.project_id,
+            auto_complete: value.auto_complete,
+            estimate_minutes: value.estimate_minutes,
+            due_date: parse_date(value.due_date)?,
+            priority: value.priority,
+            labels: value.labels,
+            recurrence: value.recurrence,
+        })
+    }
+}
+
+impl TryFrom<UpdateTaskRequest> for task_form::UpdateTaskRequest {
+    type Error = AppError;
+
+    fn try_from(value: UpdateTaskRequest) -> Result<Self, Self::Error> {
+        if let Some(field) = value
+            .clear
+            .iter()
+            .find(|field| !CLEARABLE_FIELDS.contains(&field.as_str()))
+        {
+            return Err(AppError::BadRequest(format!(
+                "{} cannot be cleared.",
+                field
+            )));
+        }
+
+        Ok(Self {
+            title: value.title,
+            description: Some(value.description),
+            status: value.status,
+            auto_complete: value.auto_complete,
+            estimate_minutes: nullable("estimate_minutes", value.estimate_minutes, &value.clear)?,
+            due_date: nullable("due_date", parse_date(value.due_date)?, &value.clear)?,
+            priority: nullable("priority", value.priority, &value.clear)?,
+            labels: value.replace_labels.then_some(value.labels),
+            recurrence: nullable("recurrence", value.recurrence, &value.clear)?,
+        })
+    }
+}
+
+impl From<UserSerializer> for UserSummary {
+    fn from(value: UserSerializer) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            username: value.username,
+            email: value.email,
+        }
+    }
+}
+
+impl From<ChecklistProgressSerializer> for ChecklistProgress {
+    fn from(value: ChecklistProgressSerializer) -> Self {
+        Self {
+            done: value.done,
+            total: value.total,
+        }
+    }
+}
+
+impl From<TaskSerializer> for Task {
+    fn from(value: TaskSerializer) -> Self {
+        Self {
+            id: value.id,
+            title: value.title,
+            description: value.description,
+            status: value.status,
+            reporter_id: value.reporter_id,
+            key: value.key,
+            project_id: value.project_id,
+            rank: value.rank,
+            assignees: value.assignees.into_iter().map(UserSummary::from).collect(),
+            checklist: Some(value.checklist.into()),
+            auto_complete: value.auto_complete,
+            estimate_minutes: value.estimate_minutes,
+            due_date: value
+                .due_date
+                .map(|due_date| due_date.format(DATE_FORMAT).to_string()),
+            priority: value.priority,
+            labels: value.labels,
+            recurrence: value.recurrence,
+            date_created: value.date_created.format(DATE_TIME_FORMAT).to_string(),
+            date_updated: value.date_updated,
+        }
+    }
+}
+
+impl From<user_profile::Model> for UserProfile {
+    fn from(value: user_profile::Model) -> Self {
+        Self {
+            id: value.id,
+            address: value.address,
+            mobile_number: value.mobile_number,
+            weekly_capacity_minutes: value.weekly_capacity_minutes,
+            timezone: value.timezone,
+        }
+    }
+}
+
+impl From<(user::Model, Option<user_profile::Model>)> for User {
+    fn from(value: (user::Model, Option<user_profile::Model>)) -> Self {
+        let (user, profile) = value;
+
+        Self {
+            id: user.id,
+            name: user.name,
+            username: user.username,
+            email: user.email,
+            profile: profile.map(UserProfile::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, fmt::Debug, path::Path};
+
+    use protobuf::{
+        reflect::{FileDescriptor, ReflectFieldRef, ReflectValueRef},
+        MessageDyn,
+    };
+
+    use super::*;
+
+    /// The descriptor of `proto/task_app.proto`, parsed without `protoc`.
+    fn proto_file() -> FileDescriptor {
+        let proto_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("proto");
+        let parsed = protobuf_parse::Parser::new()
+            .pure()
+            .include(&proto_dir)
+            .input(proto_dir.join("task_app.proto"))
+            .parse_and_typecheck()
+            .unwrap();
+
+        FileDescriptor::new_dynamic_fds(parsed.file_descriptors, &[])
+            .unwrap()
+            .into_iter()
+            .find(|file| file.proto().name() == "task_app.proto")
+            .unwrap()
+    }
+
+    /// Asserts that `message` has no field unknown to the `.proto` file, and
+    /// that every field it declares is set, in nested messages too.
+    fn assert_all_known_and_set(message: &dyn MessageDyn) {
+        let descriptor = message.descriptor_dyn();
+
+        let unknown: Vec<u32> = message
+            .unknown_fields_dyn()
+            .iter()
+            .map(|(tag, _)| tag)
+            .collect();
+        assert!(
+            unknown.is_empty(),
+            "{} has fields {:?} missing from the .proto file",
+            descriptor.name(),
+            unknown
+        );
+
+        for field in descriptor.fields() {
+            let values: Vec<ReflectValueRef> = match field.get_reflect(message) {
+                ReflectFieldRef::Optional(value) => value.value().into_iter().collect(),
+                ReflectFieldRef::Repeated(values) => {
+                    (0..values.len()).map(|i| values.get(i)).collect()
+                }
+                ReflectFieldRef::Map(_) => panic!("{} is a map", field.full_name()),
+            };
+
+            assert!(
+                !values.is_empty(),
+                "{} is missing from src/grpc/proto.rs or not set by the test",
+                field.full_name()
+            );
+
+            for value in values {
+                if let ReflectValueRef::Message(nested) = value {
+                    assert_all_known_and_set(&*nested);
+                }
+            }
+        }
+    }
+
+    /// Encodes `message`, decodes it as `name` of the `.proto` file, and
+    /// encodes it back.
+    fn assert_matches_proto<M>(file: &FileDescriptor, name: &str, message: M)
+    where
+        M: prost::Message + Default + PartialEq + Debug,
+    {
+        let descriptor = file
+            .message_by_package_relative_name(name)
+            .unwrap_or_else(|| panic!("{} is missing from the .proto file", name));
+
+        let decoded = descriptor
+            .parse_from_bytes(&message.encode_to_vec())
+            .unwrap_or_else(|e| panic!("{} does not decode as in the .proto file: {}", name, e));
+        assert_all_known_and_set(&*decoded);
+
+        let encoded = decoded.write_to_bytes_dyn().unwrap();
+        assert_eq!(M::decode(encoded.as_slice()).unwrap(), message);
+    }
+
+    fn user_summary() -> UserSummary {
+        UserSummary {
+            id: 2,
+            name: "Bob".to_string(),
+            username: "bob".to_string(),
+            email: "bob@example.com".to_string(),
+        }
+    }
+
+    fn task() -> Task {
+        Task {
+            id: 7,
+            title: "Ship it".to_string(),
+            description: "Before Friday".to_string(),
+            status: "in_progress".to_string(),
+            reporter_id: 1,
+            key: Some("OPS-7".to_string()),
+            project_id: Some(3),
+            rank: "m".to_string(),
+            assignees: vec![user_summary()],
+            checklist: Some(ChecklistProgress { done: 1, total: 2 }),
+            auto_complete: true,
+            estimate_minutes: Some(90),
+            due_date: Some("2025-03-01".to_string()),
+            priority: Some("high".to_string()),
+            labels: vec!["ops".to_string()],
+            recurrence: Some("FREQ=WEEKLY".to_string()),
+            date_created: "2025-02-01T09:30:00".to_string(),
+            date_updated: Some("2025-02-02T10:00:00".to_string()),
+        }
+    }
+
+    #[test]
+    fn messages_match_the_proto_file() {
+        let file = proto_file();
+        let mut checked = BTreeSet::new();
+
+        macro_rules! check {
+            ($($message:expr),+ $(,)?) => {
+                $({
+                    let message = $message;
+                    let name = std::any::type_name_of_val(&message)
+                        .rsplit("::")
+                        .next()
+                        .unwrap();
+                    assert_matches_proto(&file, name, message.clone());
+                    checked.insert(name.to_string());
+                })+
+            };
+        }
+
+        check!(
+            user_summary(),
+            ChecklistProgress { done: 1, total: 2 },
+            task(),
+            GetTaskRequest {
+                id: "OPS-7".to_string(),
+            },
+            ListTasksRequest {
+                status: Some("pending".to_string()),
+                project_id: Some(3),
+                assignee: Some("me".to_string()),
+                watching: true,
+                priority: Some("none".to_string()),
+                label: Some("ops".to_string()),
+                overdue: true,
+                sort: Some("rank".to_string()),
+                view: Some("mine".to_string()),
+            },
+            CreateTaskRequest {
+                title: "Ship it".to_string(),
+                description: "Before Friday".to_string(),
+                status: "pending".to_string(),
+                user_id: 1,
+                project_id: Some(3),
+                auto_complete: true,
+                estimate_minutes: Some(90),
+                due_date: Some("2025-03-01".to_string()),
+                priority: Some("high".to_string()),
+                labels: vec!["ops".to_string()],
+                recurrence: Some("FREQ=DAILY".to_string()),
+            },
+            UpdateTaskRequest {
+                id: "OPS-7".to_string(),
+                title: "Ship it".to_string(),
+                description: "Before Friday".to_string(),
+                status: "completed".to_string(),
+                auto_complete: Some(true),
+                estimate_minutes: Some(30),
+                due_date: Some("2025-03-01".to_string()),
+                priority: Some("low".to_string()),
+                labels: vec!["ops".to_string()],
+                replace_labels: true,
+                recurrence: Some("FREQ=MONTHLY".to_string()),
+                clear: vec!["priority".to_string()],
+            },
+            DeleteTaskRequest {
+                id: "OPS-7".to_string(),
+            },
+            DeleteTaskResponse {},
+            WatchTasksRequest {
+                last_event_id: Some(41),
+            },
+            TaskEvent {
+                id: 42,
+                kind: "task.updated".to_string(),
+                task_id: 7,
+                task: Some(task()),
+                resync: true,
+            },
+            UserProfile {
+                id: 5,
+                address: Some("1 Main Street".to_string()),
+                mobile_number: Some("+15550100".to_string()),
+                weekly_capacity_minutes: 2400,
+                timezone: "Europe/Paris".to_string(),
+            },
+            User {
+                id: 2,
+                name: "Bob".to_string(),
+                username: "bob".to_string(),
+                email: "bob@example.com".to_string(),
+                profile: Some(UserProfile {
+                    id: 5,
+                    address: Some("1 Main Street".to_string()),
+                    mobile_number: Some("+15550100".to_string()),
+                    weekly_capacity_minutes: 2400,
+                    timezone: "Europe/Paris".to_string(),
+                }),
+            },
+            GetCurrentUserRequest {},
+            GetUserRequest { id: 2 },
+            ListUsersRequest {
+                name: Some("Bob".to_string()),
+                username: Some("bob".to_string()),
+                email: Some("bob@example.com".to_string()),
+            },
+        );
+
+        let declared: BTreeSet<String> = file
+            .messages()
+            .map(|message| message.name().to_string())
+            .collect();
+        assert_eq!(checked, declared);
+    }
+
+    #[test]
+    fn update_request_sets_clears_and_keeps_fields() {
+        let request = UpdateTaskRequest {
+            id: "OPS-7".to_string(),
+            title: "Ship it".to_string(),
+            status: "pending".to_string(),
+            due_date: Some("2025-03-01".to_string()),
+            clear: vec!["priority".to_string()],
+            ..Default::default()
+        };
+
+        let form = task_form::UpdateTaskRequest::try_from(request.clone()).unwrap();
+
+        assert_eq!(
+            form.due_date,
+            Some(chrono::NaiveDate::from_ymd_opt(2025, 3, 1))
+        );
+        assert_eq!(form.priority, Some(None));
+        assert_eq!(form.estimate_minutes, None);
+        assert_eq!(form.labels, None);
+
+        let both = UpdateTaskRequest {
+            priority: Some("high".to_string()),
+            ..request.clone()
+        };
+        assert!(task_form::UpdateTaskRequest::try_from(both).is_err());
+
+        let unknown = UpdateTaskRequest {
+            clear: vec!["title".to_string()],
+            ..request
+        };
+        assert!(task_form::UpdateTaskRequest::try_from(unknown).is_err());
+    }
+}
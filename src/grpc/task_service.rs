@@ -0,0 +1,225 @@
+use std::{collections::HashMap, sync::Arc};
+
+use sea_orm::PaginatorTrait;
+use tokio::sync::mpsc;
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream},
+    StreamExt,
+};
+use tonic::{Request, Response, Status};
+
+use super::{
+    authenticate,
+    generated::task_service_server::TaskService,
+    proto::{
+        CreateTaskRequest, DeleteTaskRequest, DeleteTaskResponse, GetTaskRequest, ListTasksRequest,
+        Task, TaskEvent, UpdateTaskRequest, WatchTasksRequest,
+    },
+    ServerStream, STREAM_PAGE_SIZE,
+};
+use crate::{
+    controller::task_controller::{
+        find_task, insert_task, modify_task, remove_task, serialize_task, task_list_query,
+    },
+    error::AppError,
+    events::{TaskChange, TaskChangeKind},
+    models::{
+        project::ProjectPermission,
+        task::{with_details, TaskRef},
+    },
+    serializer::TaskSerializer,
+    AppState,
+};
+
+pub struct TaskGrpc {
+    app_state: Arc<AppState>,
+}
+
+impl TaskGrpc {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+fn task_ref(id: String) -> Result<TaskRef, AppError> {
+    TaskRef::try_from(id).map_err(AppError::BadRequest)
+}
+
+fn task_event(change: TaskChange) -> TaskEvent {
+    let task = match change.kind {
+        TaskChangeKind::Deleted => None,
+        _ => serde_json::from_value::<TaskSerializer>(change.data)
+            .ok()
+            .map(Task::from),
+    };
+
+    TaskEvent {
+        id: change.id,
+        kind: change.kind.as_str().to_string(),
+        task_id: change.task_id,
+        task,
+        resync: false,
+    }
+}
+
+/// Tells the client that changes were missed and its tasks must be reloaded.
+fn resync_event() -> TaskEvent {
+    TaskEvent {
+        resync: true,
+        ..Default::default()
+    }
+}
+
+#[tonic::async_trait]
+impl TaskService for TaskGrpc {
+    async fn get_task(&self, request: Request<GetTaskRequest>) -> Result<Response<Task>, Status> {
+        let user = authenticate(&self.app_state, &request).await?;
+        let task_ref = task_ref(request.into_inner().id)?;
+
+        let task = find_task(&self.app_state, &user, task_ref, ProjectPermission::View).await?;
+        let task = serialize_task(&self.app_state.db, task)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(Response::new(task.into()))
+    }
+
+    type ListTasksStream = ServerStream<Task>;
+
+    async fn list_tasks(
+        &self,
+        request: Request<ListTasksRequest>,
+    ) -> Result<Response<Self::ListTasksStream>, Status> {
+        let user = authenticate(&self.app_state, &request).await?;
+        let request = request.into_inner();
+
+        let mut params: HashMap<String, String> = [
+            ("status", request.status),
+            ("project_id", request.project_id.map(|id| id.to_string())),
+            ("assignee", request.assignee),
+            ("watching", request.watching.then(|| "true".to_string())),
+            ("priority", request.priority),
+            ("label", request.label),
+            ("overdue", request.overdue.then(|| "true".to_string())),
+            ("sort", request.sort),
+            ("view", request.view),
+        ]
+        .into_iter()
+        .filter_map(|(param, value)| Some((param.to_string(), value?)))
+        .collect();
+
+        let (task_query, _) = task_list_query(&self.app_state, &user, &mut params).await?;
+
+        let app_state = self.app_state.clone();
+        let (sender, receiver) = mpsc::channel(STREAM_PAGE_SIZE as usize);
+
+        tokio::spawn(async move {
+            let mut pages = task_query.paginate(&app_state.db, STREAM_PAGE_SIZE);
+
+            loop {
+                let tasks = match pages.fetch_and_next().await {
+                    Ok(Some(tasks)) => with_details(&app_state.db, tasks).await,
+                    Ok(None) => return,
+                    Err(e) => Err(e),
+                };
+
+                let tasks = match tasks {
+                    Ok(tasks) => tasks,
+                    Err(e) => {
+                        let _ = sender.send(Err(AppError::from(e).into())).await;
+                        return;
+                    }
+                };
+
+                for task in tasks {
+                    let task = Task::from(TaskSerializer::from(task));
+
+                    // The client went away.
+                    if sender.send(Ok(task)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+    }
+
+    async fn create_task(
+        &self,
+        request: Request<CreateTaskRequest>,
+    ) -> Result<Response<Task>, Status> {
+        let user = authenticate(&self.app_state, &request).await?;
+        let task_request = request.into_inner().try_into()?;
+
+        let task = insert_task(&self.app_state, &user, task_request).await?;
+
+        Ok(Response::new(task.into()))
+    }
+
+    async fn update_task(
+        &self,
+        request: Request<UpdateTaskRequest>,
+    ) -> Result<Response<Task>, Status> {
+        let user = authenticate(&self.app_state, &request).await?;
+        let request = request.into_inner();
+
+        let task_ref = task_ref(request.id.clone())?;
+        let task_request = request.try_into()?;
+
+        let task = modify_task(&self.app_state, user, task_ref, task_request).await?;
+
+        Ok(Response::new(task.into()))
+    }
+
+    async fn delete_task(
+        &self,
+        request: Request<DeleteTaskRequest>,
+    ) -> Result<Response<DeleteTaskResponse>, Status> {
+        let user = authenticate(&self.app_state, &request).await?;
+        let task_ref = task_ref(request.into_inner().id)?;
+
+        let task = find_task(
+            &self.app_state,
+            &user,
+            task_ref,
+            ProjectPermission::ChangeTasks,
+        )
+        .await?;
+
+        remove_task(&self.app_state, task).await?;
+
+        Ok(Response::new(DeleteTaskResponse {}))
+    }
+
+    type WatchTasksStream = ServerStream<TaskEvent>;
+
+    async fn watch_tasks(
+        &self,
+        request: Request<WatchTasksRequest>,
+    ) -> Result<Response<Self::WatchTasksStream>, Status> {
+        let user = authenticate(&self.app_state, &request).await?;
+
+        let subscription = self
+            .app_state
+            .events
+            .subscribe(request.into_inner().last_event_id);
+
+        let resync = tokio_stream::iter(subscription.missed.then(resync_event));
+
+        let replay_user = user.clone();
+        let replay = tokio_stream::iter(subscription.replay)
+            .filter(move |change| change.is_visible_to(&replay_user))
+            .map(task_event);
+
+        let live =
+            BroadcastStream::new(subscription.receiver).filter_map(move |change| match change {
+                Ok(change) => change.is_visible_to(&user).then(|| task_event(change)),
+                Err(BroadcastStreamRecvError::Lagged(_)) => Some(resync_event()),
+            });
+
+        let stream = resync.chain(replay).chain(live).map(Ok);
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+use super::{
+    authenticate,
+    generated::user_service_server::UserService,
+    proto::{GetCurrentUserRequest, GetUserRequest, ListUsersRequest, User},
+    ServerStream, STREAM_PAGE_SIZE,
+};
+use crate::{
+    error::AppError,
+    models::_entities::{user, user_profile},
+    AppState,
+};
+
+pub struct UserGrpc {
+    app_state: Arc<AppState>,
+}
+
+impl UserGrpc {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+
+    async fn find_user(&self, user_id: i32) -> Result<User, AppError> {
+        let user = user::Entity::find_by_id(user_id)
+            .find_also_related(user_profile::Entity)
+            .one(&self.app_state.db)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        Ok(user.into())
+    }
+}
+
+#[tonic::async_trait]
+impl UserService for UserGrpc {
+    async fn get_current_user(
+        &self,
+        request: Request<GetCurrentUserRequest>,
+    ) -> Result<Response<User>, Status> {
+        let user = authenticate(&self.app_state, &request).await?;
+
+        Ok(Response::new(self.find_user(user.id).await?))
+    }
+
+    async fn get_user(&self, request: Request<GetUserRequest>) -> Result<Response<User>, Status> {
+        authenticate(&self.app_state, &request).await?;
+
+        Ok(Response::new(
+            self.find_user(request.into_inner().id).await?,
+        ))
+    }
+
+    type ListUsersStream = ServerStream<User>;
+
+    async fn list_users(
+        &self,
+        request: Request<ListUsersRequest>,
+    ) -> Result<Response<Self::ListUsersStream>, Status> {
+        authenticate(&self.app_state, &request).await?;
+        let request = request.into_inner();
+
+        let mut user_query = user::Entity::find().find_also_related(user_profile::Entity);
+
+        if let Some(name) = request.name {
+            user_query = user_query.filter(user::Column::Name.contains(name));
+        }
+
+        if let Some(username) = request.username {
+            user_query = user_query.filter(user::Column::Username.contains(username));
+        }
+
+        if let Some(email) = request.email {
+            user_query = user_query.filter(user::Column::Email.contains(email));
+        }
+
+        let user_query = user_query
+            .order_by(user::Column::DateCreated, sea_orm::Order::Desc)
+            .order_by(user::Column::Id, sea_orm::Order::Desc);
+
+        let app_state = self.app_state.clone();
+        let (sender, receiver) = mpsc::channel(STREAM_PAGE_SIZE as usize);
+
+        tokio::spawn(async move {
+            let mut pages = user_query.paginate(&app_state.db, STREAM_PAGE_SIZE);
+
+            loop {
+                let users = match pages.fetch_and_next().await {
+                    Ok(Some(users)) => users,
+                    Ok(None) => return,
+                    Err(e) => {
+                        let _ = sender.send(Err(AppError::from(e).into())).await;
+                        return;
+                    }
+                };
+
+                for user in users {
+                    // The client went away.
+                    if sender.send(Ok(user.into())).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+    }
+}
@@ -0,0 +1,245 @@
+//! Minimal iCalendar support (RFC 5545) for the task feed and `.ics` imports.
+
+/// Escapes a text value.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Reverses `escape`.
+pub fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n' | 'N') => text.push('\n'),
+                Some(c) => text.push(c),
+                None => text.push('\\'),
+            },
+            (c, false) => text.push(c),
+        }
+    }
+
+    text
+}
+
+/// Splits a list value, such as `CATEGORIES`, on its unescaped commas.
+pub fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                item.push(c);
+                item.extend(chars.next());
+            }
+            ',' => items.push(unescape(&std::mem::take(&mut item))),
+            c => item.push(c),
+        }
+    }
+
+    items.push(unescape(&item));
+    items
+}
+
+/// Appends the content line `name:value` to `out`, folded so no line is
+/// longer than 75 octets. `value` must already be escaped.
+pub fn write_line(out: &mut String, name: &str, value: &str) {
+    let line = format!("{}:{}", name, value);
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            length = 1;
+        }
+
+        out.push(c);
+        length += c.len_utf8();
+    }
+
+    out.push_str("\r\n");
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Component {
+    pub name: String,
+    pub properties: Vec<Property>,
+    pub components: Vec<Component>,
+}
+
+impl Component {
+    pub fn property(&self, name: &str) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|property| property.name == name)
+    }
+
+    /// Every nested component called `name`, at any depth.
+    pub fn find_all<'a>(&'a self, name: &str, found: &mut Vec<&'a Component>) {
+        for component in &self.components {
+            if component.name == name {
+                found.push(component);
+            }
+
+            component.find_all(name, found);
+        }
+    }
+}
+
+/// Splits an unfolded content line into its property. Parameter values may
+/// be quoted, so colons and semicolons inside quotes are kept.
+fn parse_line(line: &str) -> Option<Property> {
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars();
+
+    for c in chars.by_ref() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => parts.push(std::mem::take(&mut part)),
+            ':' if !quoted => break,
+            c => part.push(c),
+        }
+    }
+
+    parts.push(part);
+
+    let value: String = chars.collect();
+    let mut parts = parts.into_iter();
+    let name = parts.next()?.to_ascii_uppercase();
+
+    if name.is_empty() {
+        return None;
+    }
+
+    let params = parts
+        .filter_map(|param| {
+            param
+                .split_once('=')
+                .map(|(name, value)| (name.to_ascii_uppercase(), value.to_string()))
+        })
+        .collect();
+
+    Some(Property {
+        name,
+        params,
+        value,
+    })
+}
+
+/// Parses `text` into its top level components, usually one `VCALENDAR`.
+/// Folded lines are joined first.
+pub fn parse(text: &str) -> Result<Vec<Component>, String> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some((_, previous))) => previous.push_str(continuation),
+            _ if line.trim().is_empty() => {}
+            _ => lines.push((i + 1, line.to_string())),
+        }
+    }
+
+    let mut components = Vec::new();
+    let mut open: Vec<Component> = Vec::new();
+
+    for (number, line) in lines {
+        let property =
+            parse_line(&line).ok_or(format!("Invalid content line on line {}.", number))?;
+
+        match property.name.as_str() {
+            "BEGIN" => open.push(Component {
+                name: property.value.to_ascii_uppercase(),
+                ..Default::default()
+            }),
+            "END" => {
+                let component = open
+                    .pop()
+                    .filter(|component| component.name == property.value.to_ascii_uppercase())
+                    .ok_or(format!(
+                        "Unexpected END:{} on line {}.",
+                        property.value, number
+                    ))?;
+
+                match open.last_mut() {
+                    Some(parent) => parent.components.push(component),
+                    None => components.push(component),
+                }
+            }
+            _ => open
+                .last_mut()
+                .ok_or(format!(
+                    "Property outside of a component on line {}.",
+                    number
+                ))?
+                .properties
+                .push(property),
+        }
+    }
+
+    if let Some(component) = open.last() {
+        return Err(format!("Missing END:{}.", component.name));
+    }
+
+    Ok(components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_folded_escaped_lines() {
+        let summary = format!("Plan; review, ship\n{}", "long ".repeat(20));
+
+        let mut text = String::from("BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\n");
+        write_line(&mut text, "SUMMARY", &escape(&summary));
+        write_line(&mut text, "CATEGORIES", "a\\,b,c");
+        text.push_str("DUE;VALUE=DATE:20250301\r\nEND:VTODO\r\nEND:VCALENDAR\r\n");
+
+        assert!(text.lines().all(|line| line.len() <= 75));
+
+        let calendars = parse(&text).unwrap();
+        let mut todos = Vec::new();
+        calendars[0].find_all("VTODO", &mut todos);
+
+        let todo = todos[0];
+        assert_eq!(unescape(&todo.property("SUMMARY").unwrap().value), summary);
+        assert_eq!(
+            split_list(&todo.property("CATEGORIES").unwrap().value),
+            vec!["a,b", "c"]
+        );
+        assert_eq!(
+            todo.property("DUE").unwrap().params,
+            vec![("VALUE".to_string(), "DATE".to_string())]
+        );
+
+        assert!(parse("BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nEND:VCALENDAR\r\n").is_err());
+    }
+}
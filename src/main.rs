@@ -1,23 +1,45 @@
 use std::sync::Arc;
 
+use api_version::{ApiVersion, V1, V2};
 use axum::{http::StatusCode, Router};
 use sea_orm::{Database, DatabaseConnection};
 use tokio::{net::TcpListener, signal};
 use tower_http::trace::TraceLayer;
 
 mod api_response;
+mod api_version;
 mod auth;
+mod collaboration;
 mod controller;
+mod csv;
+mod dav;
 mod error;
+mod events;
 mod form;
+mod graphql;
+mod grpc;
+mod ical;
 mod middlewares;
 mod models;
+mod notification;
+mod openapi;
+mod placeholder;
+mod plain_text;
+mod quick_add;
+mod rank;
+mod recurrence;
 mod serializer;
+mod stats;
+#[cfg(test)]
+mod testing;
 mod utils;
+mod webhook;
 
 #[derive(Clone, Debug)]
 struct AppState {
     db: DatabaseConnection,
+    events: events::EventHub,
+    collaboration: collaboration::CollaborationHub,
 }
 
 #[tokio::main]
@@ -38,47 +60,125 @@ async fn main() {
         .await
         .expect("Could not create TCP Listener");
 
-    let app = create_app().await;
+    let app_state = create_app_state().await;
+    let app = create_router(app_state.clone()).await;
+
+    if let Ok(grpc_address) = std::env::var("GRPC_ADDRESS") {
+        tracing::info!("gRPC listening on {}", grpc_address);
+
+        let grpc_listener = TcpListener::bind(grpc_address)
+            .await
+            .expect("Could not create gRPC TCP Listener");
+
+        tokio::spawn(grpc::serve(
+            app_state.clone(),
+            grpc_listener,
+            shutdown_signal(),
+        ));
+    }
+
+    let collaboration = app_state.collaboration.clone();
 
     axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(async move {
+            shutdown_signal().await;
+            collaboration.shutdown();
+        })
         .await
         .unwrap();
+
+    // Upgraded websocket connections outlive the server; let them send their close frames.
+    let _ = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        app_state.collaboration.closed(),
+    )
+    .await;
 }
 
+/// The app on the database of the environment, as the tests use it; `main`
+/// keeps the state to share it with the gRPC server.
+#[cfg(test)]
 async fn create_app() -> Router {
+    create_router(create_app_state().await).await
+}
+
+async fn create_app_state() -> Arc<AppState> {
     let database_url = std::env::var("DATABASE_URL").expect("Database url not found");
 
     let db = Database::connect(&database_url)
         .await
         .expect("Cannot connect to a database");
 
-    let app_state = Arc::new(AppState { db });
+    webhook::worker::spawn(db.clone());
+
+    Arc::new(AppState {
+        db,
+        events: events::EventHub::default(),
+        collaboration: collaboration::CollaborationHub::default(),
+    })
+}
+
+async fn create_router(app_state: Arc<AppState>) -> Router {
+    let v1 = api_routes::<V1>(app_state.clone())
+        .await
+        .layer(axum::middleware::from_fn(api_version::deprecate_v1));
 
     Router::new()
-        .nest("/api", controller::task_controller::get_routes().await)
-        .nest("/api", controller::user_controller::get_routes().await)
-        .nest(
-            "/api",
-            controller::permission_controller::get_routes().await,
-        )
-        .nest("/api", controller::role_controller::get_routes().await)
-        .nest("/api", controller::user_role_controller::get_routes().await)
-        // .nest("/api", controller::auth_controller::get_routes().await)
-        .nest(
-            "/api",
-            controller::auth_controller::get_logout_route().await,
-        )
+        .nest("/api", controller::graphql_controller::get_routes().await)
         .route_layer(axum::middleware::from_fn_with_state(
             app_state.clone(),
             middlewares::auth_guard::auth_guard,
         ))
-        .nest("/api", controller::auth_controller::get_login_route().await)
+        .nest("/api/v1", v1.clone())
+        .nest("/api", v1)
+        .nest("/api/v2", api_routes::<V2>(app_state.clone()).await)
+        .nest("/api", controller::docs_controller::get_routes().await)
+        .nest(
+            "/api",
+            controller::graphql_controller::get_playground_route().await,
+        )
+        .nest("/api", controller::caldav_controller::get_routes().await)
+        .merge(controller::caldav_controller::get_well_known_route().await)
         .with_state(app_state)
         .fallback(fallback_handler)
         .layer(TraceLayer::new_for_http())
 }
 
+/// The REST API of version `V`, mounted under `/api/v1`, `/api/v2` and, as
+/// an alias of v1, `/api`.
+async fn api_routes<V: ApiVersion>(app_state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .merge(controller::task_controller::get_routes::<V>().await)
+        .merge(controller::user_controller::get_routes::<V>().await)
+        .merge(controller::permission_controller::get_routes().await)
+        .merge(controller::role_controller::get_routes().await)
+        .merge(controller::user_role_controller::get_routes().await)
+        .merge(controller::webhook_controller::get_routes().await)
+        .merge(controller::event_controller::get_routes().await)
+        .merge(controller::notification_controller::get_routes().await)
+        .merge(controller::project_controller::get_routes::<V>().await)
+        .merge(controller::board_controller::get_routes::<V>().await)
+        .merge(controller::checklist_controller::get_routes().await)
+        .merge(controller::time_entry_controller::get_routes().await)
+        .merge(controller::report_controller::get_routes().await)
+        .merge(controller::stats_controller::get_routes().await)
+        .merge(controller::saved_view_controller::get_routes().await)
+        .merge(controller::template_controller::get_routes::<V>().await)
+        .merge(controller::task_transfer_controller::get_routes().await)
+        .merge(controller::calendar_controller::get_routes().await)
+        .merge(controller::quick_add_controller::get_routes::<V>().await)
+        .merge(controller::sync_controller::get_routes().await)
+        // .merge(controller::auth_controller::get_routes().await)
+        .merge(controller::auth_controller::get_logout_route().await)
+        .route_layer(axum::middleware::from_fn_with_state(
+            app_state,
+            middlewares::auth_guard::auth_guard,
+        ))
+        .merge(controller::auth_controller::get_login_route().await)
+        .merge(controller::ws_controller::get_routes().await)
+        .merge(controller::calendar_controller::get_feed_route().await)
+}
+
 async fn fallback_handler() -> StatusCode {
     StatusCode::NOT_FOUND
 }
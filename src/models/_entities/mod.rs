@@ -1,11 +1,30 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
+pub mod board_column;
+pub mod caldav_resource;
+pub mod calendar_feed;
+pub mod notification;
+pub mod notification_preference;
 pub mod prelude;
 
 pub mod permission;
+pub mod project;
+pub mod project_member;
 pub mod role;
+pub mod saved_view;
+pub mod saved_view_role;
 pub mod task;
+pub mod task_assignee;
+pub mod task_change;
+pub mod task_checklist_item;
+pub mod task_event;
+pub mod task_field_version;
+pub mod task_template;
+pub mod task_watcher;
+pub mod time_entry;
 pub mod user;
 pub mod user_permission;
 pub mod user_profile;
 pub mod user_role;
+pub mod webhook;
+pub mod webhook_delivery;
@@ -1,9 +1,28 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
+pub use super::board_column::Entity as BoardColumn;
+pub use super::caldav_resource::Entity as CaldavResource;
+pub use super::calendar_feed::Entity as CalendarFeed;
+pub use super::notification::Entity as Notification;
+pub use super::notification_preference::Entity as NotificationPreference;
 pub use super::permission::Entity as Permission;
+pub use super::project::Entity as Project;
+pub use super::project_member::Entity as ProjectMember;
 pub use super::role::Entity as Role;
+pub use super::saved_view::Entity as SavedView;
+pub use super::saved_view_role::Entity as SavedViewRole;
 pub use super::task::Entity as Task;
+pub use super::task_assignee::Entity as TaskAssignee;
+pub use super::task_change::Entity as TaskChange;
+pub use super::task_checklist_item::Entity as TaskChecklistItem;
+pub use super::task_event::Entity as TaskEvent;
+pub use super::task_field_version::Entity as TaskFieldVersion;
+pub use super::task_template::Entity as TaskTemplate;
+pub use super::task_watcher::Entity as TaskWatcher;
+pub use super::time_entry::Entity as TimeEntry;
 pub use super::user::Entity as User;
 pub use super::user_permission::Entity as UserPermission;
 pub use super::user_profile::Entity as UserProfile;
 pub use super::user_role::Entity as UserRole;
+pub use super::webhook::Entity as Webhook;
+pub use super::webhook_delivery::Entity as WebhookDelivery;
@@ -14,10 +14,18 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::saved_view_role::Entity")]
+    SavedViewRole,
     #[sea_orm(has_many = "super::user_role::Entity")]
     UserRole,
 }
 
+impl Related<super::saved_view_role::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SavedViewRole.def()
+    }
+}
+
 impl Related<super::user_role::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::UserRole.def()
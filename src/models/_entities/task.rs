@@ -15,10 +15,43 @@ pub struct Model {
     pub date_created: DateTime,
     pub date_updated: Option<String>,
     pub user_id: i32,
+    pub project_id: Option<i32>,
+    pub rank: String,
+    #[sea_orm(unique)]
+    pub key: Option<String>,
+    pub auto_complete: bool,
+    pub estimate_minutes: Option<i32>,
+    pub due_date: Option<Date>,
+    pub priority: Option<String>,
+    #[sea_orm(column_type = "Text")]
+    pub labels: String,
+    pub recurrence: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::notification::Entity")]
+    Notification,
+    #[sea_orm(
+        belongs_to = "super::project::Entity",
+        from = "Column::ProjectId",
+        to = "super::project::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Project,
+    #[sea_orm(has_many = "super::task_assignee::Entity")]
+    TaskAssignee,
+    #[sea_orm(has_many = "super::task_checklist_item::Entity")]
+    TaskChecklistItem,
+    #[sea_orm(has_many = "super::task_event::Entity")]
+    TaskEvent,
+    #[sea_orm(has_many = "super::task_field_version::Entity")]
+    TaskFieldVersion,
+    #[sea_orm(has_many = "super::task_watcher::Entity")]
+    TaskWatcher,
+    #[sea_orm(has_many = "super::time_entry::Entity")]
+    TimeEntry,
     #[sea_orm(
         belongs_to = "super::user::Entity",
         from = "Column::UserId",
@@ -29,6 +62,54 @@ pub enum Relation {
     User,
 }
 
+impl Related<super::notification::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notification.def()
+    }
+}
+
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl Related<super::task_assignee::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskAssignee.def()
+    }
+}
+
+impl Related<super::task_checklist_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskChecklistItem.def()
+    }
+}
+
+impl Related<super::task_event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskEvent.def()
+    }
+}
+
+impl Related<super::task_field_version::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskFieldVersion.def()
+    }
+}
+
+impl Related<super::task_watcher::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskWatcher.def()
+    }
+}
+
+impl Related<super::time_entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TimeEntry.def()
+    }
+}
+
 impl Related<super::user::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::User.def()
@@ -18,14 +18,74 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_one = "super::calendar_feed::Entity")]
+    CalendarFeed,
+    #[sea_orm(has_many = "super::notification::Entity")]
+    Notification,
+    #[sea_orm(has_many = "super::notification_preference::Entity")]
+    NotificationPreference,
+    #[sea_orm(has_many = "super::project::Entity")]
+    Project,
+    #[sea_orm(has_many = "super::project_member::Entity")]
+    ProjectMember,
+    #[sea_orm(has_many = "super::saved_view::Entity")]
+    SavedView,
     #[sea_orm(has_many = "super::task::Entity")]
     Task,
+    #[sea_orm(has_many = "super::task_assignee::Entity")]
+    TaskAssignee,
+    #[sea_orm(has_many = "super::task_event::Entity")]
+    TaskEvent,
+    #[sea_orm(has_many = "super::task_template::Entity")]
+    TaskTemplate,
+    #[sea_orm(has_many = "super::task_watcher::Entity")]
+    TaskWatcher,
+    #[sea_orm(has_many = "super::time_entry::Entity")]
+    TimeEntry,
     #[sea_orm(has_many = "super::user_permission::Entity")]
     UserPermission,
     #[sea_orm(has_many = "super::user_profile::Entity")]
     UserProfile,
     #[sea_orm(has_many = "super::user_role::Entity")]
     UserRole,
+    #[sea_orm(has_many = "super::webhook::Entity")]
+    Webhook,
+}
+
+impl Related<super::calendar_feed::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CalendarFeed.def()
+    }
+}
+
+impl Related<super::notification::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notification.def()
+    }
+}
+
+impl Related<super::notification_preference::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NotificationPreference.def()
+    }
+}
+
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl Related<super::project_member::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProjectMember.def()
+    }
+}
+
+impl Related<super::saved_view::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SavedView.def()
+    }
 }
 
 impl Related<super::task::Entity> for Entity {
@@ -34,6 +94,36 @@ impl Related<super::task::Entity> for Entity {
     }
 }
 
+impl Related<super::task_assignee::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskAssignee.def()
+    }
+}
+
+impl Related<super::task_event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskEvent.def()
+    }
+}
+
+impl Related<super::task_template::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskTemplate.def()
+    }
+}
+
+impl Related<super::task_watcher::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskWatcher.def()
+    }
+}
+
+impl Related<super::time_entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TimeEntry.def()
+    }
+}
+
 impl Related<super::user_permission::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::UserPermission.def()
@@ -51,3 +141,9 @@ impl Related<super::user_role::Entity> for Entity {
         Relation::UserRole.def()
     }
 }
+
+impl Related<super::webhook::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhook.def()
+    }
+}
@@ -11,6 +11,8 @@ pub struct Model {
     pub user_id: i32,
     pub address: Option<String>,
     pub mobile_number: Option<String>,
+    pub weekly_capacity_minutes: i32,
+    pub timezone: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,5 @@
+use sea_orm::ActiveModelBehavior;
+
+use super::_entities::board_column::ActiveModel;
+
+impl ActiveModelBehavior for ActiveModel {}
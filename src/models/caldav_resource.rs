@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use sea_orm::{ActiveModelBehavior, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+
+use super::_entities::caldav_resource::{ActiveModel, Column, Entity, Model};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Resource name and UID of a task no CalDAV client named.
+pub fn default_resource(task_id: i32) -> (String, String) {
+    (
+        format!("task-{}.ics", task_id),
+        format!("task-{}@task_app", task_id),
+    )
+}
+
+/// Resource name and UID of every task in `task_ids` that has them.
+pub async fn resources_of<C>(db: &C, task_ids: Vec<i32>) -> Result<HashMap<i32, Model>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    Ok(Entity::find()
+        .filter(Column::TaskId.is_in(task_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|resource| (resource.task_id, resource))
+        .collect())
+}
+
+/// Ids of the tasks that may be called `name`: the ones a client stored under
+/// that name, then the one whose default name it is.
+pub async fn task_ids_named<C>(db: &C, name: &str) -> Result<Vec<i32>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let mut task_ids: Vec<i32> = Entity::find()
+        .filter(Column::Name.eq(name))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|resource| resource.task_id)
+        .collect();
+
+    if let Some(task_id) = name
+        .strip_prefix("task-")
+        .and_then(|name| name.strip_suffix(".ics"))
+        .and_then(|task_id| task_id.parse().ok())
+    {
+        task_ids.push(task_id);
+    }
+
+    Ok(task_ids)
+}
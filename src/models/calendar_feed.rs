@@ -0,0 +1,10 @@
+use sea_orm::ActiveModelBehavior;
+
+use super::_entities::calendar_feed::ActiveModel;
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// A new random feed token, hard enough to guess to stand in for a password.
+pub fn new_token() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
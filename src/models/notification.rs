@@ -0,0 +1,20 @@
+use sea_orm::{ActiveModelBehavior, Linked, RelationDef, RelationTrait};
+
+use super::_entities::{
+    notification::{ActiveModel, Entity, Relation},
+    user,
+};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// The user whose action triggered the notification.
+pub struct NotificationActor;
+
+impl Linked for NotificationActor {
+    type FromEntity = Entity;
+    type ToEntity = user::Entity;
+
+    fn link(&self) -> Vec<RelationDef> {
+        vec![Relation::Actor.def()]
+    }
+}
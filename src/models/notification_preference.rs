@@ -0,0 +1,5 @@
+use sea_orm::ActiveModelBehavior;
+
+use super::_entities::notification_preference::ActiveModel;
+
+impl ActiveModelBehavior for ActiveModel {}
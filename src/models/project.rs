@@ -0,0 +1,366 @@
+use sea_orm::{
+    sea_query::{Expr, Query},
+    ActiveModelBehavior, ActiveModelTrait,
+    ActiveValue::NotSet,
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, ModelTrait, QueryFilter, Set,
+};
+
+use super::_entities::{
+    permission,
+    project::{ActiveModel, Column, Entity, Model},
+    project_member, user, user_permission,
+};
+
+pub const INBOX_NAME: &str = "Inbox";
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Role of a user inside a single project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectRole {
+    Owner,
+    Editor,
+    Viewer,
+}
+
+impl ProjectRole {
+    pub const ALL: [ProjectRole; 3] =
+        [ProjectRole::Owner, ProjectRole::Editor, ProjectRole::Viewer];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectRole::Owner => "owner",
+            ProjectRole::Editor => "editor",
+            ProjectRole::Viewer => "viewer",
+        }
+    }
+
+    pub fn parse(role: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|item| item.as_str() == role)
+    }
+
+    pub fn is_valid(role: &str) -> bool {
+        Self::parse(role).is_some()
+    }
+
+    pub fn grants(&self, permission: ProjectPermission) -> bool {
+        match self {
+            ProjectRole::Owner => true,
+            ProjectRole::Editor => permission != ProjectPermission::Manage,
+            ProjectRole::Viewer => permission == ProjectPermission::View,
+        }
+    }
+}
+
+/// Actions guarded inside a project.
+///
+/// Each one maps to a permission code name, so granting that permission to a
+/// user through `user_permission` allows the action on every project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectPermission {
+    View,
+    ChangeTasks,
+    Manage,
+}
+
+impl ProjectPermission {
+    pub fn code_name(&self) -> &'static str {
+        match self {
+            ProjectPermission::View => "view_project",
+            ProjectPermission::ChangeTasks => "change_project_task",
+            ProjectPermission::Manage => "manage_project",
+        }
+    }
+}
+
+/// Matches the projects `user_id` is a member of.
+pub fn member_of(user_id: i32) -> Condition {
+    Condition::all().add(
+        Column::Id.in_subquery(
+            Query::select()
+                .column(project_member::Column::ProjectId)
+                .from(project_member::Entity)
+                .and_where(project_member::Column::UserId.eq(user_id))
+                .to_owned(),
+        ),
+    )
+}
+
+/// Matches the users who are members of `project_id`.
+pub fn members_of(project_id: i32) -> Condition {
+    Condition::all().add(
+        user::Column::Id.in_subquery(
+            Query::select()
+                .column(project_member::Column::UserId)
+                .from(project_member::Entity)
+                .and_where(project_member::Column::ProjectId.eq(project_id))
+                .to_owned(),
+        ),
+    )
+}
+
+/// Matches the users granted `permission` on every project.
+pub fn granted(permission: ProjectPermission) -> Condition {
+    Condition::all().add(
+        user::Column::Id.in_subquery(
+            Query::select()
+                .column((user_permission::Entity, user_permission::Column::UserId))
+                .from(user_permission::Entity)
+                .inner_join(
+                    permission::Entity,
+                    Expr::col((permission::Entity, permission::Column::Id)).equals((
+                        user_permission::Entity,
+                        user_permission::Column::PermissionId,
+                    )),
+                )
+                .and_where(
+                    Expr::col((permission::Entity, permission::Column::CodeName))
+                        .eq(permission.code_name()),
+                )
+                .to_owned(),
+        ),
+    )
+}
+
+/// Role of `user_id` in `project_id`, if they are a member.
+pub async fn role_of<C>(db: &C, project_id: i32, user_id: i32) -> Result<Option<ProjectRole>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    Ok(project_member::Entity::find()
+        .filter(project_member::Column::ProjectId.eq(project_id))
+        .filter(project_member::Column::UserId.eq(user_id))
+        .one(db)
+        .await?
+        .and_then(|member| ProjectRole::parse(&member.role)))
+}
+
+/// Whether `user` may perform `permission` on `project`, either through their
+/// role in the project or through a global permission.
+pub async fn has_permission<C>(
+    db: &C,
+    user: &user::Model,
+    project: &Model,
+    permission: ProjectPermission,
+) -> Result<bool, DbErr>
+where
+    C: ConnectionTrait,
+{
+    if let Some(role) = role_of(db, project.id, user.id).await? {
+        if role.grants(permission) {
+            return Ok(true);
+        }
+    }
+
+    has_global_permission(db, user, permission).await
+}
+
+/// Whether `user` was granted `permission` on every project.
+pub async fn has_global_permission<C>(
+    db: &C,
+    user: &user::Model,
+    permission: ProjectPermission,
+) -> Result<bool, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let global = user
+        .find_related(permission::Entity)
+        .filter(permission::Column::CodeName.eq(permission.code_name()))
+        .one(db)
+        .await?;
+
+    Ok(global.is_some())
+}
+
+/// Derives a task key prefix from a project or user name, e.g. "Platform
+/// Ops" gives "PO" and "operations" gives "OPE".
+pub fn prefix_from(name: &str) -> String {
+    let words: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_uppercase())
+        .collect();
+
+    let prefix: String = match words.as_slice() {
+        [] => String::new(),
+        [word] => word.chars().take(3).collect(),
+        words => words
+            .iter()
+            .filter_map(|word| word.chars().next())
+            .take(4)
+            .collect(),
+    };
+
+    if prefix.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        prefix
+    } else {
+        format!("P{}", prefix)
+    }
+}
+
+/// Whether `prefix` is free to use for a new project.
+pub async fn is_prefix_available<C>(db: &C, prefix: &str) -> Result<bool, DbErr>
+where
+    C: ConnectionTrait,
+{
+    Ok(Entity::find()
+        .filter(Column::KeyPrefix.eq(prefix))
+        .one(db)
+        .await?
+        .is_none())
+}
+
+/// Derives a prefix from `name`, numbering it when it is already taken.
+pub async fn unique_prefix<C>(db: &C, name: &str) -> Result<String, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let base = prefix_from(name);
+    let mut prefix = base.clone();
+    let mut suffix = 2;
+
+    while !is_prefix_available(db, &prefix).await? {
+        prefix = format!("{}{}", base, suffix);
+        suffix += 1;
+    }
+
+    Ok(prefix)
+}
+
+/// Allocates the next task key of a project, e.g. "OPS-42". The counter is
+/// incremented before it is read, so when this runs inside a transaction
+/// concurrent requests never get the same number.
+pub async fn allocate_task_key<C>(db: &C, project_id: i32) -> Result<String, DbErr>
+where
+    C: ConnectionTrait,
+{
+    Entity::update_many()
+        .col_expr(
+            Column::NextTaskNumber,
+            Expr::col(Column::NextTaskNumber).add(1),
+        )
+        .filter(Column::Id.eq(project_id))
+        .exec(db)
+        .await?;
+
+    let project = Entity::find_by_id(project_id)
+        .one(db)
+        .await?
+        .ok_or(DbErr::RecordNotFound(format!("Project {}", project_id)))?;
+
+    Ok(format!(
+        "{}-{}",
+        project.key_prefix,
+        project.next_task_number - 1
+    ))
+}
+
+/// Returns the Inbox of `user`, creating it the first time.
+pub async fn inbox<C>(db: &C, user: &user::Model) -> Result<Model, DbErr>
+where
+    C: ConnectionTrait,
+{
+    if let Some(inbox) = Entity::find()
+        .filter(Column::OwnerId.eq(user.id))
+        .filter(Column::IsInbox.eq(true))
+        .one(db)
+        .await?
+    {
+        return Ok(inbox);
+    }
+
+    let inbox = ActiveModel {
+        id: NotSet,
+        name: Set(INBOX_NAME.to_string()),
+        description: Set(None),
+        owner_id: Set(user.id),
+        key_prefix: Set(unique_prefix(db, &user.username).await?),
+        next_task_number: NotSet,
+        is_inbox: Set(true),
+        is_archived: Set(false),
+        date_created: NotSet,
+    }
+    .insert(db)
+    .await?;
+
+    add_member(db, inbox.id, user.id, ProjectRole::Owner).await?;
+
+    Ok(inbox)
+}
+
+pub async fn add_member<C>(
+    db: &C,
+    project_id: i32,
+    user_id: i32,
+    role: ProjectRole,
+) -> Result<project_member::Model, DbErr>
+where
+    C: ConnectionTrait,
+{
+    project_member::ActiveModel {
+        id: NotSet,
+        project_id: Set(project_id),
+        user_id: Set(user_id),
+        role: Set(role.as_str().to_string()),
+        date_created: NotSet,
+    }
+    .insert(db)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{app_state, create_project, create_task, create_user};
+
+    #[test]
+    fn roles_grant_increasing_permissions() {
+        use ProjectPermission::*;
+
+        assert!(ProjectRole::Viewer.grants(View));
+        assert!(!ProjectRole::Viewer.grants(ChangeTasks));
+        assert!(ProjectRole::Editor.grants(ChangeTasks));
+        assert!(!ProjectRole::Editor.grants(Manage));
+        assert!(ProjectRole::Owner.grants(Manage));
+        assert_eq!(ProjectRole::parse("editor"), Some(ProjectRole::Editor));
+        assert!(!ProjectRole::is_valid("admin"));
+    }
+
+    #[test]
+    fn derives_prefixes_from_names() {
+        assert_eq!(prefix_from("operations"), "OPE");
+        assert_eq!(prefix_from("Platform Ops team"), "POT");
+        assert_eq!(prefix_from("2025 roadmap"), "P2R");
+        assert_eq!(prefix_from("--"), "P");
+    }
+
+    #[tokio::test]
+    async fn tasks_of_a_project_get_consecutive_keys() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let ops = create_project(&app_state, &alice, "ops", &[]).await;
+        let web = create_project(&app_state, &alice, "web", &[]).await;
+
+        let first = create_task(&app_state, &alice, &ops, "Deploy").await;
+        let (second, third) = tokio::join!(
+            create_task(&app_state, &alice, &ops, "Release"),
+            create_task(&app_state, &alice, &ops, "Announce"),
+        );
+        let other = create_task(&app_state, &alice, &web, "Design").await;
+
+        assert_eq!(first.key.as_deref(), Some("OPS-1"));
+
+        let mut keys = vec![second.key.unwrap(), third.key.unwrap()];
+        keys.sort();
+        assert_eq!(keys, vec!["OPS-2", "OPS-3"]);
+
+        // Every project counts on its own.
+        assert_eq!(other.key.as_deref(), Some("WEB-1"));
+
+        assert_eq!(
+            allocate_task_key(&app_state.db, ops.id).await.unwrap(),
+            "OPS-4"
+        );
+    }
+}
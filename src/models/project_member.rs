@@ -0,0 +1,5 @@
+use sea_orm::ActiveModelBehavior;
+
+use super::_entities::project_member::ActiveModel;
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::Query, ActiveModelBehavior, ColumnTrait, Condition, ConnectionTrait, DbErr,
+    EntityTrait, QueryFilter, QueryOrder,
+};
+
+use super::_entities::{
+    saved_view::{ActiveModel, Column, Entity, Model},
+    saved_view_role, user, user_role,
+};
+
+/// Who, besides its owner, can use a saved view.
+pub const VISIBILITIES: [&str; 3] = ["private", "roles", "everyone"];
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Matches the views `user_id` owns, and the ones shared with everyone or
+/// with one of their roles.
+pub fn visible_to(user_id: i32) -> Condition {
+    Condition::any()
+        .add(Column::UserId.eq(user_id))
+        .add(Column::Visibility.eq("everyone"))
+        .add(
+            Condition::all().add(Column::Visibility.eq("roles")).add(
+                Column::Id.in_subquery(
+                    Query::select()
+                        .column(saved_view_role::Column::SavedViewId)
+                        .from(saved_view_role::Entity)
+                        .and_where(
+                            saved_view_role::Column::RoleId.in_subquery(
+                                Query::select()
+                                    .column(user_role::Column::RoleId)
+                                    .from(user_role::Entity)
+                                    .and_where(user_role::Column::UserId.eq(user_id))
+                                    .to_owned(),
+                            ),
+                        )
+                        .to_owned(),
+                ),
+            ),
+        )
+}
+
+/// The view of `user` called `slug`, or else the oldest view with that slug
+/// shared with them.
+pub async fn find_view_by_slug<C>(
+    db: &C,
+    user: &user::Model,
+    slug: &str,
+) -> Result<Option<Model>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let own = Entity::find()
+        .filter(Column::UserId.eq(user.id))
+        .filter(Column::Slug.eq(slug))
+        .one(db)
+        .await?;
+
+    if own.is_some() {
+        return Ok(own);
+    }
+
+    Entity::find()
+        .filter(visible_to(user.id))
+        .filter(Column::Slug.eq(slug))
+        .order_by_asc(Column::Id)
+        .one(db)
+        .await
+}
+
+/// Filter parameters stored with `view`.
+pub fn view_filters(view: &Model) -> HashMap<String, String> {
+    serde_json::from_str(&view.filters).unwrap_or_default()
+}
+
+/// Columns `view` lists tasks with, or none to list every field.
+pub fn view_columns(view: &Model) -> Vec<String> {
+    view.columns
+        .split(',')
+        .filter(|column| !column.is_empty())
+        .map(|column| column.to_string())
+        .collect()
+}
+
+/// Ids of the roles every view in `view_ids` is shared with.
+pub async fn roles_of<C>(db: &C, view_ids: Vec<i32>) -> Result<HashMap<i32, Vec<i32>>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let mut roles: HashMap<i32, Vec<i32>> = HashMap::new();
+
+    for view_role in saved_view_role::Entity::find()
+        .filter(saved_view_role::Column::SavedViewId.is_in(view_ids))
+        .order_by_asc(saved_view_role::Column::RoleId)
+        .all(db)
+        .await?
+    {
+        roles
+            .entry(view_role.saved_view_id)
+            .or_default()
+            .push(view_role.role_id);
+    }
+
+    Ok(roles)
+}
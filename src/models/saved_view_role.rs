@@ -0,0 +1,5 @@
+use sea_orm::ActiveModelBehavior;
+
+use super::_entities::saved_view_role::ActiveModel;
+
+impl ActiveModelBehavior for ActiveModel {}
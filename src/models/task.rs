@@ -1,5 +1,334 @@
-use sea_orm::ActiveModelBehavior;
+use std::collections::{BTreeSet, HashMap};
 
-use super::_entities::task::ActiveModel;
+use sea_orm::{
+    sea_query::{Expr, Query},
+    ActiveModelBehavior, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+
+use super::_entities::{
+    project,
+    task::{ActiveModel, Column, Entity, Model},
+    task_assignee, task_watcher, user,
+};
+use super::project::{granted, member_of, members_of, ProjectPermission};
+use super::task_checklist_item::{progress_of, ChecklistProgress};
+use crate::rank;
+use serde::Deserialize;
+
+/// A task referenced in a URL, either by id or by key such as `OPS-42`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum TaskRef {
+    Id(i32),
+    Key(String),
+}
+
+impl TryFrom<String> for TaskRef {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if let Ok(id) = value.parse::<i32>() {
+            return Ok(TaskRef::Id(id));
+        }
+
+        match value.rsplit_once('-') {
+            Some((prefix, number)) if !prefix.is_empty() && number.parse::<u32>().is_ok() => {
+                Ok(TaskRef::Key(value.to_ascii_uppercase()))
+            }
+            _ => Err(format!("Invalid task id or key: {}", value)),
+        }
+    }
+}
+
+impl TaskRef {
+    pub fn condition(&self) -> Condition {
+        match self {
+            TaskRef::Id(id) => Condition::all().add(Column::Id.eq(*id)),
+            TaskRef::Key(key) => Condition::all().add(Column::Key.eq(key.as_str())),
+        }
+    }
+}
+
+/// Status of a finished task.
+pub const COMPLETED: &str = "completed";
+
+/// Task priorities, from lowest to highest. Tasks may have none.
+pub const PRIORITIES: [&str; 4] = ["low", "medium", "high", "urgent"];
+
+/// Task statuses in board order.
+pub const STATUSES: [&str; 3] = ["pending", "in_progress", COMPLETED];
+
+/// Query parameters the task list can be filtered on.
+pub const FILTERS: [&str; 7] = [
+    "status",
+    "project_id",
+    "assignee",
+    "watching",
+    "priority",
+    "label",
+    "overdue",
+];
+
+/// Orders the task list can be sorted in. The first one is the default.
+pub const SORTS: [&str; 3] = ["created", "rank", "due_date"];
+
+/// Fields a task can be imported with.
+pub const IMPORT_FIELDS: [&str; 10] = [
+    "title",
+    "description",
+    "status",
+    "priority",
+    "labels",
+    "estimate_minutes",
+    "due_date",
+    "project_id",
+    "auto_complete",
+    "recurrence",
+];
+
+/// Fields of a listed task, which saved views can pick their columns from.
+pub const COLUMNS: [&str; 18] = [
+    "id",
+    "title",
+    "description",
+    "status",
+    "reporter_id",
+    "key",
+    "project_id",
+    "rank",
+    "assignees",
+    "checklist",
+    "auto_complete",
+    "estimate_minutes",
+    "due_date",
+    "priority",
+    "labels",
+    "recurrence",
+    "date_created",
+    "date_updated",
+];
 
 impl ActiveModelBehavior for ActiveModel {}
+
+/// Labels of `task`, which are stored comma separated.
+pub fn labels_of(task: &Model) -> Vec<String> {
+    task.labels
+        .split(',')
+        .filter(|label| !label.is_empty())
+        .map(|label| label.to_string())
+        .collect()
+}
+
+/// Joins `labels` for storage, trimmed and without duplicates.
+pub fn join_labels(labels: &[String]) -> String {
+    let mut joined: Vec<&str> = Vec::new();
+
+    for label in labels.iter().map(|label| label.trim()) {
+        if !label.is_empty() && !joined.contains(&label) {
+            joined.push(label);
+        }
+    }
+
+    joined.join(",")
+}
+
+/// Matches the tasks labelled `label`.
+pub fn labelled(label: &str) -> Condition {
+    Condition::all().add(Expr::cust_with_values(
+        "instr(',' || task.labels || ',', ?) > 0",
+        [format!(",{},", label)],
+    ))
+}
+
+/// Matches the tasks assigned to `user_id`.
+pub fn assigned_to(user_id: i32) -> Condition {
+    Condition::all().add(
+        Column::Id.in_subquery(
+            Query::select()
+                .column(task_assignee::Column::TaskId)
+                .from(task_assignee::Entity)
+                .and_where(task_assignee::Column::UserId.eq(user_id))
+                .to_owned(),
+        ),
+    )
+}
+
+/// Matches the tasks watched by `user_id`.
+pub fn watched_by(user_id: i32) -> Condition {
+    Condition::all().add(
+        Column::Id.in_subquery(
+            Query::select()
+                .column(task_watcher::Column::TaskId)
+                .from(task_watcher::Entity)
+                .and_where(task_watcher::Column::UserId.eq(user_id))
+                .to_owned(),
+        ),
+    )
+}
+
+/// Matches the tasks in the projects `user_id` is a member of, and the ones
+/// outside of any project.
+pub fn in_projects_of(user_id: i32) -> Condition {
+    Condition::any().add(Column::ProjectId.is_null()).add(
+        Column::ProjectId.in_subquery(
+            Query::select()
+                .column(project::Column::Id)
+                .from(project::Entity)
+                .cond_where(member_of(user_id))
+                .to_owned(),
+        ),
+    )
+}
+
+/// Ids of the users who can see `task`, by the rule of the task list: the
+/// members of its project and the users allowed to view every project. Tasks
+/// outside of any project are seen by everyone.
+pub async fn audience<C>(db: &C, task: &Model) -> Result<Vec<i32>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let mut users = user::Entity::find();
+
+    if let Some(project_id) = task.project_id {
+        users = users.filter(
+            Condition::any()
+                .add(members_of(project_id))
+                .add(granted(ProjectPermission::View)),
+        );
+    }
+
+    users
+        .select_only()
+        .column(user::Column::Id)
+        .order_by_asc(user::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await
+}
+
+/// Ids of the users following `task`: its reporter, assignees and watchers.
+pub async fn followers<C>(db: &C, task: &Model) -> Result<Vec<i32>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let assignees = task_assignee::Entity::find()
+        .filter(task_assignee::Column::TaskId.eq(task.id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|assignee| assignee.user_id);
+
+    let watchers = task_watcher::Entity::find()
+        .filter(task_watcher::Column::TaskId.eq(task.id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|watcher| watcher.user_id);
+
+    let followers: BTreeSet<i32> = std::iter::once(task.user_id)
+        .chain(assignees)
+        .chain(watchers)
+        .collect();
+
+    Ok(followers.into_iter().collect())
+}
+
+/// Pairs every task with its assignees and checklist progress, loading them
+/// in one query each.
+pub async fn with_details<C>(
+    db: &C,
+    tasks: Vec<Model>,
+) -> Result<Vec<(Model, Vec<user::Model>, ChecklistProgress)>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let task_ids: Vec<i32> = tasks.iter().map(|task| task.id).collect();
+
+    let mut assignees: HashMap<i32, Vec<user::Model>> = HashMap::new();
+
+    for (assignee, user) in task_assignee::Entity::find()
+        .filter(task_assignee::Column::TaskId.is_in(task_ids.clone()))
+        .find_also_related(user::Entity)
+        .all(db)
+        .await?
+    {
+        if let Some(user) = user {
+            assignees.entry(assignee.task_id).or_default().push(user);
+        }
+    }
+
+    let mut checklists = progress_of(db, task_ids).await?;
+
+    Ok(tasks
+        .into_iter()
+        .map(|task| {
+            let task_assignees = assignees.remove(&task.id).unwrap_or_default();
+            let checklist = checklists.remove(&task.id).unwrap_or_default();
+            (task, task_assignees, checklist)
+        })
+        .collect())
+}
+
+/// Matches the tasks of one board column.
+pub fn in_column(project_id: Option<i32>, status: &str) -> Condition {
+    let project = match project_id {
+        Some(project_id) => Column::ProjectId.eq(project_id),
+        None => Column::ProjectId.is_null(),
+    };
+
+    Condition::all().add(project).add(Column::Status.eq(status))
+}
+
+/// Rank for a task appended at the bottom of a column.
+pub async fn next_rank<C>(db: &C, project_id: Option<i32>, status: &str) -> Result<String, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let last: Option<String> = Entity::find()
+        .select_only()
+        .column(Column::Rank)
+        .filter(in_column(project_id, status))
+        .order_by_desc(Column::Rank)
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    let key = rank::between(last.as_deref(), None).expect("There is always room at the end");
+
+    if key.len() > rank::MAX_LENGTH {
+        rebalance_column(db, project_id, status).await?;
+
+        return Box::pin(next_rank(db, project_id, status)).await;
+    }
+
+    Ok(key)
+}
+
+/// Rewrites the ranks of a column with evenly spread keys, keeping its order.
+pub async fn rebalance_column<C>(db: &C, project_id: Option<i32>, status: &str) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let task_ids: Vec<i32> = Entity::find()
+        .select_only()
+        .column(Column::Id)
+        .filter(in_column(project_id, status))
+        .order_by_asc(Column::Rank)
+        .order_by_asc(Column::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let keys = rank::spread(task_ids.len());
+
+    for (task_id, key) in task_ids.into_iter().zip(keys) {
+        Entity::update_many()
+            .col_expr(Column::Rank, key.into())
+            .filter(Column::Id.eq(task_id))
+            .exec(db)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::{Expr, Query},
+    ActiveModelBehavior, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait,
+    FromQueryResult, QueryFilter, QueryOrder, QuerySelect,
+};
+
+use super::{
+    _entities::{
+        project,
+        task_change::{ActiveModel, Column, Entity, Model},
+    },
+    project::member_of,
+};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Latest change of a task, which is its version.
+#[derive(Debug, FromQueryResult)]
+pub struct TaskVersion {
+    pub task_id: i32,
+    pub version: i32,
+}
+
+/// Matches the changes to the tasks in the projects `user_id` is a member of,
+/// and to the ones outside of any project.
+pub fn in_projects_of(user_id: i32) -> Condition {
+    Condition::any().add(Column::ProjectId.is_null()).add(
+        Column::ProjectId.in_subquery(
+            Query::select()
+                .column(project::Column::Id)
+                .from(project::Entity)
+                .cond_where(member_of(user_id))
+                .to_owned(),
+        ),
+    )
+}
+
+/// Sequence number of the latest change made to any task, or 0.
+pub async fn latest_change<C>(db: &C) -> Result<i32, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let latest: Option<Option<i32>> = Entity::find()
+        .select_only()
+        .column_as(Column::Id.max(), "latest")
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    Ok(latest.flatten().unwrap_or(0))
+}
+
+/// Changes made to the tasks of `project_id` after the change `since`, oldest
+/// first.
+pub async fn changes_since<C>(db: &C, project_id: i32, since: i32) -> Result<Vec<Model>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    Entity::find()
+        .filter(Column::ProjectId.eq(project_id))
+        .filter(Column::Id.gt(since))
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await
+}
+
+/// Versions of the tasks changed after the change `since` and up to `until`,
+/// by changes matching `condition`, least recently changed first.
+pub async fn changed_tasks<C>(
+    db: &C,
+    condition: Condition,
+    since: i32,
+    until: i32,
+    limit: u64,
+) -> Result<Vec<TaskVersion>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    Entity::find()
+        .select_only()
+        .column(Column::TaskId)
+        .column_as(Column::Id.max(), "version")
+        .filter(Column::Id.gt(since))
+        .filter(Column::Id.lte(until))
+        .filter(condition)
+        .group_by(Column::TaskId)
+        .order_by_asc(Expr::col(Column::Id).max())
+        .limit(limit)
+        .into_model::<TaskVersion>()
+        .all(db)
+        .await
+}
+
+/// First change of each of `task_ids`, the one that created it.
+pub async fn first_changes<C>(db: &C, task_ids: Vec<i32>) -> Result<HashMap<i32, i32>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let first: Vec<(i32, i32)> = Entity::find()
+        .select_only()
+        .column(Column::TaskId)
+        .column_as(Column::Id.min(), "first")
+        .filter(Column::TaskId.is_in(task_ids))
+        .group_by(Column::TaskId)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(first.into_iter().collect())
+}
+
+/// Version of `task_id`, the sequence number of its latest change, or 0.
+pub async fn task_version<C>(db: &C, task_id: i32) -> Result<i32, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let version: Option<Option<i32>> = Entity::find()
+        .select_only()
+        .column_as(Column::Id.max(), "version")
+        .filter(Column::TaskId.eq(task_id))
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    Ok(version.flatten().unwrap_or(0))
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::{Expr, Func, SimpleExpr},
+    ActiveModelBehavior, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+    QuerySelect,
+};
+
+use super::_entities::task_checklist_item::{ActiveModel, Column, Entity};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// How many checklist items of a task are done.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChecklistProgress {
+    pub done: i64,
+    pub total: i64,
+}
+
+impl ChecklistProgress {
+    /// Whether the task has a checklist and every item of it is done.
+    pub fn is_complete(&self) -> bool {
+        self.total > 0 && self.done == self.total
+    }
+}
+
+/// Checklist progress of every task in `task_ids`, counted in a single query.
+/// Tasks without a checklist are left out.
+pub async fn progress_of<C>(
+    db: &C,
+    task_ids: Vec<i32>,
+) -> Result<HashMap<i32, ChecklistProgress>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let rows: Vec<(i32, i64, i64)> = Entity::find()
+        .select_only()
+        .column(Column::TaskId)
+        .column_as(Expr::col(Column::Id).count(), "total")
+        .column_as(
+            SimpleExpr::from(Func::sum(Expr::case(Column::IsDone.eq(true), 1).finally(0))),
+            "done",
+        )
+        .filter(Column::TaskId.is_in(task_ids))
+        .group_by(Column::TaskId)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(task_id, total, done)| (task_id, ChecklistProgress { done, total }))
+        .collect())
+}
+
+/// Position for an item appended at the end of the checklist of `task_id`.
+pub async fn next_position<C>(db: &C, task_id: i32) -> Result<i32, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let last: Option<Option<i32>> = Entity::find()
+        .select_only()
+        .column_as(Expr::col(Column::Position).max(), "position")
+        .filter(Column::TaskId.eq(task_id))
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    Ok(last.flatten().unwrap_or(0) + 1)
+}
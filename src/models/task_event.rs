@@ -0,0 +1,131 @@
+use sea_orm::{ActiveModelBehavior, ActiveValue::NotSet, ConnectionTrait, DbErr, EntityTrait, Set};
+
+use super::_entities::{
+    task,
+    task_event::{ActiveModel, Entity},
+};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Task fields whose changes are recorded in the history.
+pub const TRACKED_FIELDS: [&str; 8] = [
+    "title",
+    "description",
+    "status",
+    "estimate_minutes",
+    "due_date",
+    "priority",
+    "labels",
+    "recurrence",
+];
+
+pub fn field_value(task: &task::Model, field: &str) -> Option<String> {
+    match field {
+        "title" => Some(task.title.clone()),
+        "description" => Some(task.description.clone()),
+        "status" => Some(task.status.clone()),
+        "estimate_minutes" => task.estimate_minutes.map(|minutes| minutes.to_string()),
+        "due_date" => task.due_date.map(|date| date.to_string()),
+        "priority" => task.priority.clone(),
+        "labels" => (!task.labels.is_empty()).then(|| task.labels.clone()),
+        "recurrence" => task.recurrence.clone(),
+        _ => None,
+    }
+}
+
+/// Builds one event per tracked field that differs between `before` and `after`.
+pub fn diff_task(
+    before: &task::Model,
+    after: &task::Model,
+    user_id: Option<i32>,
+) -> Vec<ActiveModel> {
+    TRACKED_FIELDS
+        .iter()
+        .filter_map(|field| {
+            let old_value = field_value(before, field);
+            let new_value = field_value(after, field);
+
+            (old_value != new_value).then(|| ActiveModel {
+                id: NotSet,
+                task_id: Set(after.id),
+                user_id: Set(user_id),
+                field: Set(field.to_string()),
+                old_value: Set(old_value),
+                new_value: Set(new_value),
+                date_created: NotSet,
+            })
+        })
+        .collect()
+}
+
+/// Stores the field-level changes between two versions of a task.
+///
+/// Every handler that modifies a task should call this with the same connection
+/// (or transaction) used for the update.
+pub async fn record_changes<C>(
+    db: &C,
+    before: &task::Model,
+    after: &task::Model,
+    user_id: Option<i32>,
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let events = diff_task(before, after, user_id);
+
+    if !events.is_empty() {
+        Entity::insert_many(events).exec(db).await?;
+    }
+
+    Ok(())
+}
+
+/// Sets `field` on the task to `value`, as stored in a task event.
+pub fn apply_field(
+    task: &mut task::ActiveModel,
+    field: &str,
+    value: Option<String>,
+) -> Result<(), String> {
+    let invalid = || format!("Invalid {} recorded.", field);
+
+    match field {
+        "estimate_minutes" => {
+            task.estimate_minutes = Set(value
+                .map(|v| v.parse())
+                .transpose()
+                .map_err(|_| invalid())?);
+            return Ok(());
+        }
+        "due_date" => {
+            task.due_date = Set(value
+                .map(|v| v.parse())
+                .transpose()
+                .map_err(|_| invalid())?);
+            return Ok(());
+        }
+        "priority" => {
+            task.priority = Set(value);
+            return Ok(());
+        }
+        "labels" => {
+            task.labels = Set(value.unwrap_or_default());
+            return Ok(());
+        }
+        "recurrence" => {
+            task.recurrence = Set(value);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let value = value.ok_or(format!("No previous value recorded for {}.", field))?;
+
+    match field {
+        "title" => task.title = Set(value),
+        "description" => task.description = Set(value),
+        "status" => task.status = Set(value),
+        _ => return Err(format!("Field {} cannot be reverted.", field)),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+use sea_orm::{ActiveModelBehavior, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+
+use super::_entities::task_field_version::{ActiveModel, Column, Entity};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Version of each tracked field of `task_id`, the change that last set it.
+pub async fn field_versions<C>(db: &C, task_id: i32) -> Result<HashMap<String, i32>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    Ok(Entity::find()
+        .filter(Column::TaskId.eq(task_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|field_version| (field_version.field, field_version.version))
+        .collect())
+}
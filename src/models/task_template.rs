@@ -0,0 +1,23 @@
+use sea_orm::{sea_query::Query, ActiveModelBehavior, ColumnTrait, Condition};
+
+use super::_entities::{
+    project,
+    task_template::{ActiveModel, Column},
+};
+use super::project::member_of;
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Matches the templates `user_id` created, and the ones of the projects they
+/// are a member of.
+pub fn visible_to(user_id: i32) -> Condition {
+    Condition::any().add(Column::UserId.eq(user_id)).add(
+        Column::ProjectId.in_subquery(
+            Query::select()
+                .column(project::Column::Id)
+                .from(project::Entity)
+                .cond_where(member_of(user_id))
+                .to_owned(),
+        ),
+    )
+}
@@ -0,0 +1,5 @@
+use sea_orm::ActiveModelBehavior;
+
+use super::_entities::task_watcher::ActiveModel;
+
+impl ActiveModelBehavior for ActiveModel {}
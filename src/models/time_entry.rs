@@ -0,0 +1,176 @@
+use chrono::{NaiveDateTime, Timelike, Utc};
+use sea_orm::{
+    sea_query::{Expr, SimpleExpr},
+    ActiveModelBehavior, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait,
+    FromQueryResult, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+};
+
+use super::_entities::{
+    project, task,
+    time_entry::{ActiveModel, Column, Entity, Model, Relation},
+    user,
+};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Current time, to the second, as stored in time entries.
+pub fn now() -> NaiveDateTime {
+    Utc::now()
+        .naive_utc()
+        .with_nanosecond(0)
+        .expect("Zero nanoseconds is valid")
+}
+
+/// The timer `user_id` is currently running, if any.
+pub async fn running_timer<C>(db: &C, user_id: i32) -> Result<Option<Model>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::EndedAt.is_null())
+        .one(db)
+        .await
+}
+
+/// What the rows of a time report are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportDimension {
+    User,
+    Task,
+    Project,
+    Day,
+}
+
+impl ReportDimension {
+    pub const ALL: [ReportDimension; 4] = [
+        ReportDimension::User,
+        ReportDimension::Task,
+        ReportDimension::Project,
+        ReportDimension::Day,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportDimension::User => "user",
+            ReportDimension::Task => "task",
+            ReportDimension::Project => "project",
+            ReportDimension::Day => "day",
+        }
+    }
+
+    pub fn parse(dimension: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|item| item.as_str() == dimension)
+    }
+
+    /// Columns selected for this dimension, with their aliases. The first one
+    /// identifies the group, the others describe it.
+    fn columns(&self) -> Vec<(SimpleExpr, &'static str)> {
+        match self {
+            ReportDimension::User => vec![
+                (
+                    Expr::col((user::Entity, user::Column::Id)).into(),
+                    "user_id",
+                ),
+                (
+                    Expr::col((user::Entity, user::Column::Username)).into(),
+                    "username",
+                ),
+            ],
+            ReportDimension::Task => vec![
+                (
+                    Expr::col((task::Entity, task::Column::Id)).into(),
+                    "task_id",
+                ),
+                (
+                    Expr::col((task::Entity, task::Column::Key)).into(),
+                    "task_key",
+                ),
+                (
+                    Expr::col((task::Entity, task::Column::Title)).into(),
+                    "task_title",
+                ),
+            ],
+            ReportDimension::Project => vec![
+                (
+                    Expr::col((project::Entity, project::Column::Id)).into(),
+                    "project_id",
+                ),
+                (
+                    Expr::col((project::Entity, project::Column::Name)).into(),
+                    "project_name",
+                ),
+            ],
+            ReportDimension::Day => vec![(Expr::cust("date(time_entry.started_at)"), "day")],
+        }
+    }
+}
+
+/// One row of a time report. Columns of the dimensions the report is not
+/// grouped by are `None`.
+#[derive(Debug, FromQueryResult)]
+pub struct TimeReportRow {
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub task_id: Option<i32>,
+    pub task_key: Option<String>,
+    pub task_title: Option<String>,
+    pub project_id: Option<i32>,
+    pub project_name: Option<String>,
+    pub day: Option<String>,
+    pub seconds: i64,
+}
+
+/// Sums the finished time entries started between `from` (inclusive) and
+/// `to` (exclusive) that match `condition`, grouped by `dimensions`. An entry
+/// counts towards the day it started on.
+pub async fn report<C>(
+    db: &C,
+    dimensions: &[ReportDimension],
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+    condition: Condition,
+) -> Result<Vec<TimeReportRow>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let mut query = Entity::find()
+        .select_only()
+        .join(JoinType::InnerJoin, Relation::User.def())
+        .join(JoinType::InnerJoin, Relation::Task.def())
+        .join(JoinType::LeftJoin, task::Relation::Project.def())
+        .filter(Column::EndedAt.is_not_null())
+        .filter(Column::StartedAt.gte(from))
+        .filter(Column::StartedAt.lt(to))
+        .filter(condition);
+
+    for dimension in ReportDimension::ALL {
+        let grouped = dimensions.contains(&dimension);
+
+        for (i, (expr, alias)) in dimension.columns().into_iter().enumerate() {
+            if !grouped {
+                query = query.column_as(Expr::cust("NULL"), alias);
+                continue;
+            }
+
+            query = query.column_as(expr.clone(), alias).group_by(expr.clone());
+
+            if i == 0 {
+                query = query.order_by_asc(expr);
+            }
+        }
+    }
+
+    query
+        .column_as(
+            Expr::cust(
+                "CAST(ROUND(SUM((julianday(time_entry.ended_at) - julianday(time_entry.started_at)) * 86400)) AS INTEGER)",
+            ),
+            "seconds",
+        )
+        .into_model::<TimeReportRow>()
+        .all(db)
+        .await
+}
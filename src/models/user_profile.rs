@@ -1,5 +1,58 @@
-use sea_orm::ActiveModelBehavior;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Utc, Weekday};
+use chrono_tz::Tz;
+use sea_orm::{ActiveModelBehavior, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
 
-use super::_entities::user_profile::ActiveModel;
+use super::_entities::user_profile::{self, ActiveModel};
 
 impl ActiveModelBehavior for ActiveModel {}
+
+/// Weekly capacity of users without a profile, 40 hours.
+pub const DEFAULT_WEEKLY_CAPACITY_MINUTES: i32 = 2400;
+
+/// Time zone of a user, UTC unless their profile names a known one.
+pub async fn timezone_of<C>(db: &C, user_id: i32) -> Result<Tz, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let profile = user_profile::Entity::find()
+        .filter(user_profile::Column::UserId.eq(user_id))
+        .one(db)
+        .await?;
+
+    Ok(profile
+        .and_then(|profile| profile.timezone.parse().ok())
+        .unwrap_or(Tz::UTC))
+}
+
+/// The current date and time where a user is.
+pub async fn local_now<C>(db: &C, user_id: i32) -> Result<NaiveDateTime, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let timezone = timezone_of(db, user_id).await?;
+
+    Ok(Utc::now().with_timezone(&timezone).naive_local())
+}
+
+/// Minutes a user can work on `date`: the weekly capacity spread over the
+/// working days, nothing on weekends.
+pub fn daily_capacity(weekly_capacity_minutes: i32, date: NaiveDate) -> i32 {
+    match date.weekday() {
+        Weekday::Sat | Weekday::Sun => 0,
+        _ => weekly_capacity_minutes / 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spreads_capacity_over_working_days() {
+        let friday = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        let saturday = NaiveDate::from_ymd_opt(2025, 1, 11).unwrap();
+
+        assert_eq!(daily_capacity(2400, friday), 480);
+        assert_eq!(daily_capacity(2400, saturday), 0);
+    }
+}
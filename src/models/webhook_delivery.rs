@@ -0,0 +1,5 @@
+use sea_orm::ActiveModelBehavior;
+
+use super::_entities::webhook_delivery::ActiveModel;
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+
+use sea_orm::{
+    ActiveValue::NotSet, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+
+use crate::models::{
+    _entities::{notification, notification_preference, task, user},
+    task::{audience, followers},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    TaskAssigned,
+    TaskStatusChanged,
+}
+
+impl NotificationKind {
+    pub const ALL: [NotificationKind; 2] = [
+        NotificationKind::TaskAssigned,
+        NotificationKind::TaskStatusChanged,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::TaskAssigned => "task_assigned",
+            NotificationKind::TaskStatusChanged => "task_status_changed",
+        }
+    }
+
+    pub fn is_valid(kind: &str) -> bool {
+        Self::ALL.iter().any(|item| item.as_str() == kind)
+    }
+}
+
+/// Notifies `recipients` about a task, skipping the actor and users who
+/// disabled this kind of notification. Kinds are enabled unless a preference
+/// says otherwise.
+pub async fn notify<C>(
+    db: &C,
+    kind: NotificationKind,
+    recipients: &[i32],
+    actor: &user::Model,
+    task: &task::Model,
+    message: String,
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let recipients: HashSet<i32> = recipients
+        .iter()
+        .copied()
+        .filter(|user_id| *user_id != actor.id)
+        .collect();
+
+    if recipients.is_empty() {
+        return Ok(());
+    }
+
+    let muted: HashSet<i32> = notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.is_in(recipients.clone()))
+        .filter(notification_preference::Column::Kind.eq(kind.as_str()))
+        .filter(notification_preference::Column::Enabled.eq(false))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|preference| preference.user_id)
+        .collect();
+
+    let notifications: Vec<notification::ActiveModel> = recipients
+        .difference(&muted)
+        .map(|user_id| notification::ActiveModel {
+            id: NotSet,
+            user_id: Set(*user_id),
+            actor_id: Set(Some(actor.id)),
+            task_id: Set(Some(task.id)),
+            kind: Set(kind.as_str().to_string()),
+            message: Set(message.clone()),
+            read_at: Set(None),
+            date_created: NotSet,
+        })
+        .collect();
+
+    if !notifications.is_empty() {
+        notification::Entity::insert_many(notifications)
+            .exec(db)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Notifies users who were given a task by someone else.
+pub async fn task_assigned<C>(
+    db: &C,
+    actor: &user::Model,
+    task: &task::Model,
+    user_ids: &[i32],
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    notify(
+        db,
+        NotificationKind::TaskAssigned,
+        user_ids,
+        actor,
+        task,
+        format!("{} assigned you \"{}\"", actor.username, task.title),
+    )
+    .await
+}
+
+/// Notifies the users affected by a task when its status changes.
+pub async fn task_status_changed<C>(
+    db: &C,
+    actor: &user::Model,
+    before: &task::Model,
+    after: &task::Model,
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    if before.status == after.status {
+        return Ok(());
+    }
+
+    // Followers removed from the project no longer see the task.
+    let audience = audience(db, after).await?;
+    let user_ids: Vec<i32> = followers(db, after)
+        .await?
+        .into_iter()
+        .filter(|user_id| audience.contains(user_id))
+        .collect();
+
+    notify(
+        db,
+        NotificationKind::TaskStatusChanged,
+        &user_ids,
+        actor,
+        after,
+        format!(
+            "{} changed the status of \"{}\" from {} to {}",
+            actor.username, after.title, before.status, after.status
+        ),
+    )
+    .await
+}
@@ -0,0 +1,349 @@
+//! OpenAPI 3.1 description of the API, generated from the handlers, the
+//! request forms, the serializers and the `JsonResponse` envelopes.
+//!
+//! Every handler of a route carries a `#[utoipa::path]` attribute and is
+//! listed in `ApiDoc`; a test checks that the router answers each documented
+//! operation and refuses the other methods of its path.
+//! CalDAV is left out, as its WebDAV methods cannot be described in OpenAPI.
+//!
+//! The REST routes are documented at `/api`, the alias of v1; the test also
+//! checks that `/api/v1` and `/api/v2` serve each of them.
+
+use utoipa::{
+    openapi::{
+        security::{Http, HttpAuthScheme, SecurityScheme},
+        ContentBuilder, Ref, RefOr, Response, ResponseBuilder,
+    },
+    Modify, OpenApi,
+};
+
+use crate::{api_response::ErrorResponse, controller, serializer::TaskV2Serializer};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Task App API",
+        description = "Tasks, projects and the people working on them.\n\n\
+            The REST routes are served under `/api/v2` and, deprecated, `/api/v1`, \
+            of which `/api` is an alias. This document describes v1; v2 returns \
+            tasks as `TaskV2Serializer` instead of `TaskSerializer`."
+    ),
+    paths(
+        controller::task_controller::get_tasks,
+        controller::task_controller::create_task,
+        controller::task_controller::get_task,
+        controller::task_controller::update_task,
+        controller::task_controller::delete_task,
+        controller::task_controller::get_task_history,
+        controller::task_controller::revert_task_event,
+        controller::task_controller::reposition_task,
+        controller::task_controller::assign_task,
+        controller::task_controller::unassign_task,
+        controller::task_controller::watch_task,
+        controller::task_controller::unwatch_task,
+        controller::checklist_controller::get_checklist,
+        controller::checklist_controller::create_checklist_item,
+        controller::checklist_controller::reorder_checklist,
+        controller::checklist_controller::update_checklist_item,
+        controller::checklist_controller::delete_checklist_item,
+        controller::board_controller::get_board,
+        controller::board_controller::update_wip_limits,
+        controller::event_controller::get_events,
+        controller::notification_controller::get_notifications,
+        controller::notification_controller::mark_all_read,
+        controller::notification_controller::get_preferences,
+        controller::notification_controller::update_preferences,
+        controller::notification_controller::mark_read,
+        controller::permission_controller::get_permissions,
+        controller::permission_controller::create_permission,
+        controller::permission_controller::get_permission,
+        controller::permission_controller::update_permission,
+        controller::permission_controller::delete_permission,
+        controller::project_controller::get_projects,
+        controller::project_controller::create_project,
+        controller::project_controller::get_project,
+        controller::project_controller::update_project,
+        controller::project_controller::delete_project,
+        controller::project_controller::get_project_members,
+        controller::project_controller::update_project_member,
+        controller::project_controller::delete_project_member,
+        controller::project_controller::get_project_tasks,
+        controller::project_controller::create_project_task,
+        controller::quick_add_controller::quick_add_task,
+        controller::report_controller::get_workload,
+        controller::role_controller::get_roles,
+        controller::role_controller::create_role,
+        controller::role_controller::get_role,
+        controller::role_controller::update_role,
+        controller::role_controller::delete_role,
+        controller::saved_view_controller::get_views,
+        controller::saved_view_controller::create_view,
+        controller::saved_view_controller::get_view,
+        controller::saved_view_controller::update_view,
+        controller::saved_view_controller::delete_view,
+        controller::stats_controller::get_task_stats,
+        controller::stats_controller::get_burndown,
+        controller::sync_controller::get_changes,
+        controller::sync_controller::push_changes,
+        controller::task_transfer_controller::export_tasks,
+        controller::task_transfer_controller::import_tasks,
+        controller::template_controller::get_templates,
+        controller::template_controller::create_template,
+        controller::template_controller::get_template,
+        controller::template_controller::update_template,
+        controller::template_controller::delete_template,
+        controller::template_controller::instantiate_template,
+        controller::time_entry_controller::get_timer,
+        controller::time_entry_controller::stop_timer,
+        controller::time_entry_controller::start_timer,
+        controller::time_entry_controller::get_task_time_entries,
+        controller::time_entry_controller::create_time_entry,
+        controller::time_entry_controller::update_time_entry,
+        controller::time_entry_controller::delete_time_entry,
+        controller::time_entry_controller::get_time_report,
+        controller::user_controller::get_users,
+        controller::user_controller::create_user,
+        controller::user_controller::get_user,
+        controller::user_controller::update_user,
+        controller::user_controller::delete_user,
+        controller::user_controller::get_user_tasks,
+        controller::user_controller::update_user_capacity,
+        controller::user_controller::update_user_timezone,
+        controller::user_controller::get_user_roles,
+        controller::user_controller::assign_roles,
+        controller::user_controller::sync_roles,
+        controller::user_controller::delete_role,
+        controller::user_controller::get_user_permissions,
+        controller::user_controller::assign_permissions,
+        controller::user_controller::sync_permissions,
+        controller::user_role_controller::get_user_roles,
+        controller::webhook_controller::get_webhooks,
+        controller::webhook_controller::create_webhook,
+        controller::webhook_controller::get_webhook,
+        controller::webhook_controller::update_webhook,
+        controller::webhook_controller::delete_webhook,
+        controller::webhook_controller::get_webhook_deliveries,
+        controller::calendar_controller::get_feed,
+        controller::calendar_controller::reset_feed,
+        controller::calendar_controller::revoke_feed,
+        controller::calendar_controller::import_calendar,
+        controller::calendar_controller::tasks_calendar,
+        controller::auth_controller::login,
+        controller::auth_controller::logout,
+        controller::ws_controller::websocket,
+        controller::docs_controller::get_openapi,
+        controller::docs_controller::get_docs,
+        controller::graphql_controller::execute_graphql,
+        controller::graphql_controller::get_graphiql,
+    ),
+    components(schemas(ErrorResponse, TaskV2Serializer)),
+    modifiers(&BearerAuth, &ErrorResponses),
+    security(("bearer_auth" = [])),
+)]
+pub struct ApiDoc;
+
+/// The access token returned by `/api/auth/login`, sent as a bearer token.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "bearer_auth",
+                SecurityScheme::Http(
+                    Http::builder()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            );
+    }
+}
+
+/// Documents the `ErrorResponse` envelope every handler answers errors with,
+/// rather than repeating it on each of them.
+struct ErrorResponses;
+
+impl Modify for ErrorResponses {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let error: RefOr<Response> = ResponseBuilder::new()
+            .description("The request failed, see `error`")
+            .content(
+                "application/json",
+                ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("ErrorResponse")))
+                    .build(),
+            )
+            .build()
+            .into();
+
+        for path in openapi.paths.paths.values_mut() {
+            for operation in [
+                &mut path.get,
+                &mut path.put,
+                &mut path.post,
+                &mut path.delete,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                operation
+                    .responses
+                    .responses
+                    .entry("default".to_string())
+                    .or_insert_with(|| error.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use axum::{
+        body::{to_bytes, Body},
+        http::{header, HeaderMap, Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        create_router,
+        testing::{app_state, create_user, token_of},
+    };
+
+    const METHODS: [&str; 5] = ["get", "post", "put", "delete", "patch"];
+
+    fn documented_operations() -> BTreeSet<(String, String)> {
+        let openapi = ApiDoc::openapi();
+        let mut operations = BTreeSet::new();
+
+        for (path, item) in openapi.paths.paths {
+            for (method, operation) in [
+                ("get", &item.get),
+                ("post", &item.post),
+                ("put", &item.put),
+                ("delete", &item.delete),
+                ("patch", &item.patch),
+            ] {
+                if operation.is_some() {
+                    operations.insert((method.to_string(), path.clone()));
+                }
+            }
+        }
+
+        operations
+    }
+
+    /// Status and headers of the answer of `app` to `method` on `path`, with
+    /// its parameters set to 1, and whether a route answered it rather than
+    /// refusing the method or falling back to an empty 404. Authenticated, as
+    /// the auth guard also answers the methods a path has no route for.
+    async fn request(
+        app: &Router,
+        token: &str,
+        method: &str,
+        path: &str,
+    ) -> (StatusCode, HeaderMap, bool) {
+        let uri: Vec<&str> = path
+            .split('/')
+            .map(|segment| {
+                if segment.starts_with('{') {
+                    "1"
+                } else {
+                    segment
+                }
+            })
+            .collect();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(method.to_uppercase().as_str())
+                    .uri(uri.join("/"))
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let headers = response.headers().clone();
+
+        // Only read for a 404, as event streams never end.
+        let routed = match status {
+            StatusCode::METHOD_NOT_ALLOWED => false,
+            StatusCode::NOT_FOUND => !to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap()
+                .is_empty(),
+            _ => true,
+        };
+
+        (status, headers, routed)
+    }
+
+    #[tokio::test]
+    async fn documents_every_route() {
+        let app_state = app_state().await;
+        // Requests for id 1 leave the user making them alone.
+        create_user(&app_state, "nobody").await;
+        let token = token_of(&create_user(&app_state, "alice").await).await;
+        let app = create_router(app_state).await;
+
+        let operations = documented_operations();
+        let paths: BTreeSet<&String> = operations.iter().map(|(_, path)| path).collect();
+
+        // The router answers every documented operation, and refuses the
+        // other methods of their paths.
+        for path in paths {
+            for method in METHODS {
+                let (status, _, routed) = request(&app, &token, method, path).await;
+
+                if operations.contains(&(method.to_string(), path.clone())) {
+                    assert!(routed, "{} {} is not routed", method, path);
+                } else {
+                    assert_eq!(
+                        status,
+                        StatusCode::METHOD_NOT_ALLOWED,
+                        "{} {} is routed but not documented",
+                        method,
+                        path
+                    );
+                }
+            }
+        }
+
+        // Each version serves the REST routes, v1 and its alias as deprecated.
+        for (method, path) in &operations {
+            let route = path.strip_prefix("/api").unwrap();
+
+            let (_, v1, in_v1) = request(&app, &token, method, &format!("/api/v1{}", route)).await;
+            let (_, v2, in_v2) = request(&app, &token, method, &format!("/api/v2{}", route)).await;
+
+            assert_eq!(in_v1, in_v2, "{} {} is not in every version", method, path);
+
+            if !in_v1 {
+                continue;
+            }
+
+            let (_, alias, _) = request(&app, &token, method, path).await;
+
+            for (headers, deprecated) in [(alias, true), (v1, true), (v2, false)] {
+                assert_eq!(
+                    headers.contains_key("deprecation") && headers.contains_key("sunset"),
+                    deprecated,
+                    "Deprecation headers of {} {}",
+                    method,
+                    path
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! `{{name}}` placeholders filled in when a task template is instantiated.
+//!
+//! Names are made of ASCII letters, digits and underscores, and may be padded
+//! with spaces inside the braces. Anything else between braces is left as is.
+
+use std::collections::{BTreeSet, HashMap};
+
+/// Calls `f` with the byte range and name of every placeholder in `text`.
+fn scan(text: &str, mut f: impl FnMut(usize, usize, &str)) {
+    let mut offset = 0;
+
+    while let Some(start) = text[offset..].find("{{").map(|i| offset + i) {
+        let Some(end) = text[start + 2..].find("}}").map(|i| start + 2 + i) else {
+            return;
+        };
+
+        let name = text[start + 2..end].trim();
+
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            f(start, end + 2, name);
+            offset = end + 2;
+        } else {
+            offset = start + 2;
+        }
+    }
+}
+
+/// Names of the placeholders in `text`.
+pub fn names(text: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+
+    scan(text, |_, _, name| {
+        names.insert(name.to_string());
+    });
+
+    names
+}
+
+/// Replaces the placeholders in `text` that have a value in `values`.
+pub fn render(text: &str, values: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(text.len());
+    let mut copied = 0;
+
+    scan(text, |start, end, name| {
+        if let Some(value) = values.get(name) {
+            rendered.push_str(&text[copied..start]);
+            rendered.push_str(value);
+            copied = end;
+        }
+    });
+
+    rendered.push_str(&text[copied..]);
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_known_placeholders() {
+        let text = "Welcome {{name}}, desk {{ desk }} {{name}} {{ not a name }} {{other}} {{";
+        let values = HashMap::from([
+            ("name".to_string(), "Ada".to_string()),
+            ("desk".to_string(), "4B".to_string()),
+        ]);
+
+        assert_eq!(
+            names(text).into_iter().collect::<Vec<_>>(),
+            vec!["desk", "name", "other"]
+        );
+        assert_eq!(
+            render(text, &values),
+            "Welcome Ada, desk 4B Ada {{ not a name }} {{other}} {{"
+        );
+    }
+}
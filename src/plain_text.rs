@@ -0,0 +1,336 @@
+//! Tasks as todo.txt lines and GitHub style Markdown checklist items.
+//!
+//! A todo.txt line looks like
+//! `(B) 2025-01-10 Write report +WORK @writing due:2025-01-20 estimate:90`:
+//! the priority (A urgent to D low) and creation date come first, then the
+//! title, then the project key prefix, labels and `key:value` fields. Done
+//! tasks start with `x`, their completion and creation dates, and keep their
+//! priority as `pri:`. Other statuses are written as `status:`.
+//!
+//! Tags and fields are only read from the end of a line, so titles keep any
+//! `+`, `@` or `:` in them, unless their last word looks like a tag itself.
+//! Labels cannot contain spaces in todo.txt, so spaces are written as `_`.
+//!
+//! Markdown checklist items, `- [ ] title` or `- [x] title`, only keep the
+//! title and whether the task is done.
+
+use chrono::NaiveDate;
+
+use crate::models::{
+    _entities::task,
+    task::{labels_of, COMPLETED, STATUSES},
+};
+
+/// The fields of a task the plain text formats can hold.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlainTask {
+    pub title: String,
+    pub status: String,
+    pub priority: Option<String>,
+    /// Key prefix of the project of the task.
+    pub project: Option<String>,
+    pub labels: Vec<String>,
+    pub due_date: Option<NaiveDate>,
+    pub estimate_minutes: Option<i32>,
+    pub date_created: Option<NaiveDate>,
+    pub date_completed: Option<NaiveDate>,
+}
+
+impl PlainTask {
+    /// `task`, in the project with the key prefix `project`. Completed tasks
+    /// count as completed when they were last updated.
+    pub fn from_task(task: &task::Model, project: Option<&str>) -> Self {
+        let date_created = task.date_created.date();
+        let date_completed = (task.status == COMPLETED).then(|| {
+            task.date_updated
+                .as_deref()
+                .and_then(|date_updated| date_updated.get(..10))
+                .and_then(|date| date.parse().ok())
+                .unwrap_or(date_created)
+        });
+
+        Self {
+            title: task.title.clone(),
+            status: task.status.clone(),
+            priority: task.priority.clone(),
+            project: project.map(str::to_string),
+            labels: labels_of(task),
+            due_date: task.due_date,
+            estimate_minutes: task.estimate_minutes,
+            date_created: Some(date_created),
+            date_completed,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.status == COMPLETED
+    }
+}
+
+fn todo_txt_priority(priority: &str) -> char {
+    match priority {
+        "urgent" => 'A',
+        "high" => 'B',
+        "medium" => 'C',
+        _ => 'D',
+    }
+}
+
+/// Task priority of a todo.txt priority letter. Anything below D is low.
+fn task_priority(letter: char) -> Option<String> {
+    let priority = match letter {
+        'A' => "urgent",
+        'B' => "high",
+        'C' => "medium",
+        'D'..='Z' => "low",
+        _ => return None,
+    };
+
+    Some(priority.to_string())
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+}
+
+/// `task` as one todo.txt line, without the line break.
+pub fn to_todo_txt(task: &PlainTask) -> String {
+    let mut words: Vec<String> = Vec::new();
+
+    if task.is_done() {
+        words.push("x".to_string());
+
+        if let (Some(date_completed), Some(date_created)) = (task.date_completed, task.date_created)
+        {
+            words.push(date_completed.to_string());
+            words.push(date_created.to_string());
+        }
+    } else {
+        if let Some(priority) = &task.priority {
+            words.push(format!("({})", todo_txt_priority(priority)));
+        }
+
+        if let Some(date_created) = task.date_created {
+            words.push(date_created.to_string());
+        }
+    }
+
+    words.push(task.title.clone());
+
+    if let Some(project) = &task.project {
+        words.push(format!("+{}", project));
+    }
+
+    for label in &task.labels {
+        words.push(format!(
+            "@{}",
+            label.split_whitespace().collect::<Vec<_>>().join("_")
+        ));
+    }
+
+    if let Some(due_date) = task.due_date {
+        words.push(format!("due:{}", due_date));
+    }
+
+    if let Some(estimate_minutes) = task.estimate_minutes {
+        words.push(format!("estimate:{}", estimate_minutes));
+    }
+
+    if !task.is_done() && task.status != STATUSES[0] {
+        words.push(format!("status:{}", task.status));
+    }
+
+    if let (true, Some(priority)) = (task.is_done(), &task.priority) {
+        words.push(format!("pri:{}", todo_txt_priority(priority)));
+    }
+
+    words.join(" ")
+}
+
+/// Whether `word` is a tag or field read from the end of a todo.txt line.
+fn is_tag(word: &str) -> bool {
+    if let Some(name) = word.strip_prefix(['+', '@']) {
+        return !name.is_empty();
+    }
+
+    word.split_once(':').is_some_and(|(key, value)| {
+        ["due", "estimate", "status", "pri"].contains(&key) && !value.is_empty()
+    })
+}
+
+/// Reads one todo.txt line. Returns `None` for blank lines.
+pub fn parse_todo_txt(line: &str) -> Option<Result<PlainTask, String>> {
+    let line = line.trim();
+
+    if line.is_empty() {
+        return None;
+    }
+
+    Some(parse_todo_txt_line(line))
+}
+
+/// Splits the first word off `rest` when `accept` takes it and a title
+/// follows. The leading words are separated by single spaces, so this keeps
+/// the spacing of the title.
+fn next_word(rest: &mut &str, accept: impl Fn(&str) -> bool) -> Option<String> {
+    let (word, after) = rest.split_once(' ').unwrap_or((rest, ""));
+
+    if !after.is_empty() && accept(word) {
+        *rest = after;
+        Some(word.to_string())
+    } else {
+        None
+    }
+}
+
+fn parse_todo_txt_line(line: &str) -> Result<PlainTask, String> {
+    let mut task = PlainTask {
+        status: STATUSES[0].to_string(),
+        ..Default::default()
+    };
+
+    let mut rest = line;
+
+    if next_word(&mut rest, |word| word == "x").is_some() {
+        task.status = COMPLETED.to_string();
+
+        if let Some(date) = next_word(&mut rest, |word| parse_date(word).is_some()) {
+            task.date_completed = parse_date(&date);
+
+            if let Some(date) = next_word(&mut rest, |word| parse_date(word).is_some()) {
+                task.date_created = parse_date(&date);
+            }
+        }
+    } else {
+        let is_priority = |word: &str| {
+            word.len() == 3
+                && word.starts_with('(')
+                && word.ends_with(')')
+                && word.chars().nth(1).is_some_and(|c| c.is_ascii_uppercase())
+        };
+
+        if let Some(priority) = next_word(&mut rest, is_priority) {
+            task.priority = priority.chars().nth(1).and_then(task_priority);
+        }
+
+        if let Some(date) = next_word(&mut rest, |word| parse_date(word).is_some()) {
+            task.date_created = parse_date(&date);
+        }
+    }
+
+    let mut words: Vec<&str> = rest.split(' ').collect();
+    let mut tags = Vec::new();
+
+    while words.len() > 1 && words.last().is_some_and(|word| is_tag(word)) {
+        tags.extend(words.pop());
+    }
+
+    task.title = words.join(" ").trim().to_string();
+
+    for tag in tags.into_iter().rev() {
+        if let Some(project) = tag.strip_prefix('+') {
+            if task.project.is_some() {
+                return Err("A task can only be in one project.".to_string());
+            }
+
+            task.project = Some(project.to_string());
+        } else if let Some(label) = tag.strip_prefix('@') {
+            task.labels.push(label.to_string());
+        } else if let Some((key, value)) = tag.split_once(':') {
+            let invalid = || format!("Invalid value {} for {}.", value, key);
+
+            match key {
+                "due" => task.due_date = Some(parse_date(value).ok_or_else(invalid)?),
+                "estimate" => task.estimate_minutes = Some(value.parse().map_err(|_| invalid())?),
+                "status" if !task.is_done() => task.status = value.to_string(),
+                "pri" => {
+                    let letter = value.parse().map_err(|_| invalid())?;
+                    task.priority = Some(task_priority(letter).ok_or_else(invalid)?);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(task)
+}
+
+/// `task` as one Markdown checklist item, without the line break.
+pub fn to_markdown(task: &PlainTask) -> String {
+    format!(
+        "- [{}] {}",
+        if task.is_done() { 'x' } else { ' ' },
+        task.title
+    )
+}
+
+/// Reads one Markdown checklist item, nested or not. Returns `None` for any
+/// other line, such as headings or text around the list.
+pub fn parse_markdown(line: &str) -> Option<PlainTask> {
+    let item = line
+        .trim_start()
+        .strip_prefix(['-', '*', '+'])?
+        .strip_prefix(' ')?;
+    let (done, title) = match item.get(..4)? {
+        "[ ] " => (false, &item[4..]),
+        "[x] " | "[X] " => (true, &item[4..]),
+        _ => return None,
+    };
+
+    Some(PlainTask {
+        title: title.trim().to_string(),
+        status: if done { COMPLETED } else { STATUSES[0] }.to_string(),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_todo_txt_and_markdown() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+
+        let tasks = [
+            PlainTask {
+                title: "Email a+b@example.com about  2025-01-01 plans".to_string(),
+                status: "in_progress".to_string(),
+                priority: Some("high".to_string()),
+                project: Some("WORK".to_string()),
+                labels: vec!["email".to_string(), "follow_up".to_string()],
+                due_date: Some(date(20)),
+                estimate_minutes: Some(90),
+                date_created: Some(date(10)),
+                date_completed: None,
+            },
+            PlainTask {
+                title: "(A) 2025-01-02 read: notes".to_string(),
+                status: COMPLETED.to_string(),
+                priority: Some("urgent".to_string()),
+                date_created: Some(date(1)),
+                date_completed: Some(date(3)),
+                ..Default::default()
+            },
+        ];
+
+        for task in &tasks {
+            let line = to_todo_txt(task);
+            assert_eq!(parse_todo_txt(&line), Some(Ok(task.clone())), "{}", line);
+
+            let item = parse_markdown(&to_markdown(task)).unwrap();
+            assert_eq!((&item.title, item.is_done()), (&task.title, task.is_done()));
+        }
+
+        assert_eq!(
+            to_todo_txt(&tasks[0]),
+            "(B) 2025-01-10 Email a+b@example.com about  2025-01-01 plans +WORK @email \
+             @follow_up due:2025-01-20 estimate:90 status:in_progress"
+        );
+        assert_eq!(to_markdown(&tasks[1]), "- [x] (A) 2025-01-02 read: notes");
+
+        assert_eq!(parse_todo_txt("  "), None);
+        assert!(parse_todo_txt("Plan due:tomorrow").unwrap().is_err());
+        assert_eq!(parse_markdown("## Tasks"), None);
+    }
+}
@@ -0,0 +1,433 @@
+//! Quick add: a task written as one line of text, such as
+//! `Pay rent tomorrow 9am !high #finance @alice every month`.
+//!
+//! The words understood are taken out of the title:
+//!
+//! - a priority, `!urgent`, `!high`, `!medium` or `!low`, or `!1` (urgent)
+//!   to `!4` (low);
+//! - labels, `#label`, and assignees, `@username`;
+//! - a due date: `today`, `tomorrow`, a weekday, `next monday`, `next week`,
+//!   `in 3 days`, `march 5`, `5 mar` or `2025-03-05`, optionally after `on`,
+//!   `by` or `due`, and followed by a time such as `9am` or `at 17:30`. A time
+//!   alone means today, or tomorrow once it has passed. Tasks only have due
+//!   dates, so times only pick the day;
+//! - a recurrence: `daily`, `weekly`, `monthly`, `yearly`, `every day`,
+//!   `every other week`, `every 3 months` or `every friday`.
+//!
+//! Only the first priority, due date and recurrence count; later ones stay
+//! in the title.
+
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::recurrence::{Frequency, Recurrence, MAX_INTERVAL};
+
+/// The kind of thing a part of the text was understood as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    DueDate,
+    Priority,
+    Label,
+    Assignee,
+    Recurrence,
+}
+
+impl Part {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Part::DueDate => "due_date",
+            Part::Priority => "priority",
+            Part::Label => "label",
+            Part::Assignee => "assignee",
+            Part::Recurrence => "recurrence",
+        }
+    }
+}
+
+/// Words of the text understood as `part`, with the value they stand for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub text: String,
+    pub part: Part,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuickTask {
+    pub title: String,
+    pub due_date: Option<NaiveDate>,
+    pub priority: Option<String>,
+    pub labels: Vec<String>,
+    /// Usernames, without the `@`.
+    pub assignees: Vec<String>,
+    pub recurrence: Option<Recurrence>,
+    pub matches: Vec<Match>,
+}
+
+fn priority(word: &str) -> Option<&'static str> {
+    match word.strip_prefix('!')? {
+        "urgent" | "1" => Some("urgent"),
+        "high" | "2" => Some("high"),
+        "medium" | "3" => Some("medium"),
+        "low" | "4" => Some("low"),
+        _ => None,
+    }
+}
+
+/// The name after `prefix`, when `word` is a tag like `#label` or `@user`.
+fn tag(word: &str, prefix: char) -> Option<&str> {
+    word.strip_prefix(prefix).filter(|name| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || ['_', '-', '.'].contains(&c))
+    })
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn month(word: &str) -> Option<u32> {
+    let months = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+
+    months
+        .iter()
+        .position(|month| word.len() >= 3 && month.starts_with(word))
+        .map(|i| i as u32 + 1)
+}
+
+fn day_of_month(word: &str) -> Option<u32> {
+    let day = word
+        .strip_suffix("st")
+        .or(word.strip_suffix("nd"))
+        .or(word.strip_suffix("rd"))
+        .or(word.strip_suffix("th"))
+        .unwrap_or(word);
+
+    day.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+fn count(word: &str) -> Option<u32> {
+    match word {
+        "a" | "an" | "one" => Some(1),
+        "two" => Some(2),
+        "three" => Some(3),
+        "four" => Some(4),
+        "five" => Some(5),
+        "six" => Some(6),
+        _ => word.parse().ok(),
+    }
+}
+
+fn unit(word: &str) -> Option<Frequency> {
+    match word.strip_suffix('s').unwrap_or(word) {
+        "day" => Some(Frequency::Daily),
+        "week" => Some(Frequency::Weekly),
+        "month" => Some(Frequency::Monthly),
+        "year" => Some(Frequency::Yearly),
+        _ => None,
+    }
+}
+
+/// The first `weekday` from `today`, or after it.
+fn coming(today: NaiveDate, weekday: Weekday, include_today: bool) -> NaiveDate {
+    let days = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    let days = if days == 0 && !include_today { 7 } else { days };
+
+    today + Days::new(days.into())
+}
+
+/// `month` and `day` this year, or next year once they have passed.
+fn in_year(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+
+    if date < today {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    } else {
+        Some(date)
+    }
+}
+
+/// A date at the start of `words`, with the number of words it takes.
+fn date(words: &[&str], today: NaiveDate) -> Option<(usize, NaiveDate)> {
+    let word = |i: usize| words.get(i).copied().unwrap_or_default();
+
+    match word(0) {
+        "today" | "tonight" => return Some((1, today)),
+        "tomorrow" | "tmr" | "tmrw" => return Some((1, today + Days::new(1))),
+        "next" => {
+            if let Some(weekday) = weekday(word(1)) {
+                return Some((2, coming(today, weekday, false)));
+            }
+
+            let frequency = unit(word(1)).filter(|_| !word(1).ends_with('s'))?;
+
+            return Some((2, Recurrence::new(frequency, 1).nth(today, 1)?));
+        }
+        "in" => {
+            let frequency = unit(word(2))?;
+            let steps = count(word(1)).filter(|steps| *steps <= MAX_INTERVAL)?;
+
+            return Some((3, Recurrence::new(frequency, steps).nth(today, 1)?));
+        }
+        _ => {}
+    }
+
+    if let Some(weekday) = weekday(word(0)) {
+        return Some((1, coming(today, weekday, true)));
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(word(0), "%Y-%m-%d") {
+        return Some((1, date));
+    }
+
+    match (month(word(0)), day_of_month(word(1))) {
+        (Some(month), Some(day)) => in_year(today, month, day).map(|date| (2, date)),
+        _ => match (day_of_month(word(0)), month(word(1))) {
+            (Some(day), Some(month)) => in_year(today, month, day).map(|date| (2, date)),
+            _ => None,
+        },
+    }
+}
+
+/// A time such as `9am`, `9:30 pm` or `17:30`, optionally after `at`.
+fn time(words: &[&str]) -> Option<(usize, NaiveTime)> {
+    let (skipped, words) = match words.first() {
+        Some(&"at") => (1, &words[1..]),
+        _ => (0, words),
+    };
+
+    let word = *words.first()?;
+
+    if word == "noon" {
+        return Some((skipped + 1, NaiveTime::from_hms_opt(12, 0, 0)?));
+    }
+
+    let (clock, meridiem, taken) = match (word.strip_suffix("am"), word.strip_suffix("pm")) {
+        (Some(clock), _) => (clock, Some(0), 1),
+        (_, Some(clock)) => (clock, Some(12), 1),
+        _ => match words.get(1).copied() {
+            Some("am") => (word, Some(0), 2),
+            Some("pm") => (word, Some(12), 2),
+            _ => (word, None, 1),
+        },
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour.parse().ok()?, minute.parse().ok()?),
+        None if meridiem.is_some() => (clock.parse::<u32>().ok()?, 0),
+        _ => return None,
+    };
+
+    let hour = match meridiem {
+        Some(offset) if (1..=12).contains(&hour) => hour % 12 + offset,
+        Some(_) => return None,
+        None => hour,
+    };
+
+    Some((skipped + taken, NaiveTime::from_hms_opt(hour, minute, 0)?))
+}
+
+/// A due date at the start of `words`, given as a date, a time or both.
+fn due_date(words: &[&str], now: NaiveDateTime) -> Option<(usize, NaiveDate)> {
+    let today = now.date();
+    let skipped = usize::from(matches!(words.first(), Some(&("on" | "by" | "due"))));
+    let words = &words[skipped..];
+
+    if let Some((taken, date)) = date(words, today) {
+        let timed = time(&words[taken..]).map_or(0, |(taken, _)| taken);
+
+        return Some((skipped + taken + timed, date));
+    }
+
+    let (taken, time) = time(words)?;
+    let date = if time > now.time() {
+        today
+    } else {
+        today + Days::new(1)
+    };
+
+    Some((skipped + taken, date))
+}
+
+/// A recurrence at the start of `words`, with the first due date it implies,
+/// if any.
+fn recurrence(words: &[&str], today: NaiveDate) -> Option<(usize, Recurrence, Option<NaiveDate>)> {
+    let word = |i: usize| words.get(i).copied().unwrap_or_default();
+
+    let frequency = match word(0) {
+        "daily" => Some(Frequency::Daily),
+        "weekly" => Some(Frequency::Weekly),
+        "monthly" => Some(Frequency::Monthly),
+        "yearly" | "annually" => Some(Frequency::Yearly),
+        _ => None,
+    };
+
+    if let Some(frequency) = frequency {
+        return Some((1, Recurrence::new(frequency, 1), None));
+    }
+
+    if word(0) != "every" {
+        return None;
+    }
+
+    let (taken, interval) = match word(1) {
+        "other" => (2, 2),
+        word => match count(word).filter(|interval| (1..=MAX_INTERVAL).contains(interval)) {
+            Some(interval) => (2, interval),
+            None => (1, 1),
+        },
+    };
+
+    if let Some(weekday) = weekday(word(taken)) {
+        let first = coming(today, weekday, true);
+
+        return Some((
+            taken + 1,
+            Recurrence::new(Frequency::Weekly, interval),
+            Some(first),
+        ));
+    }
+
+    let frequency = unit(word(taken))?;
+
+    Some((taken + 1, Recurrence::new(frequency, interval), None))
+}
+
+/// Reads `text` as written at `now`, the local time of the user.
+pub fn parse(text: &str, now: NaiveDateTime) -> QuickTask {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let lowercase: Vec<String> = words
+        .iter()
+        .map(|word| word.trim_end_matches([',', ';']).to_lowercase())
+        .collect();
+    let lowercase: Vec<&str> = lowercase.iter().map(String::as_str).collect();
+
+    let mut task = QuickTask::default();
+    let mut title = Vec::new();
+    let mut first_due_date = None;
+    let mut i = 0;
+
+    while i < words.len() {
+        let rest = &lowercase[i..];
+
+        let found = if let (Some(priority), None) = (priority(rest[0]), &task.priority) {
+            task.priority = Some(priority.to_string());
+            Some((1, Part::Priority, priority.to_string()))
+        } else if let Some(label) = tag(words[i], '#') {
+            task.labels.push(label.to_string());
+            Some((1, Part::Label, label.to_string()))
+        } else if let Some(username) = tag(words[i], '@') {
+            task.assignees.push(username.to_string());
+            Some((1, Part::Assignee, username.to_string()))
+        } else if let Some((taken, date)) = due_date(rest, now).filter(|_| task.due_date.is_none())
+        {
+            task.due_date = Some(date);
+            Some((taken, Part::DueDate, date.to_string()))
+        } else if let Some((taken, recurrence, first)) =
+            recurrence(rest, now.date()).filter(|_| task.recurrence.is_none())
+        {
+            task.recurrence = Some(recurrence);
+            first_due_date = first;
+            Some((taken, Part::Recurrence, recurrence.to_string()))
+        } else {
+            None
+        };
+
+        match found {
+            Some((taken, part, value)) => {
+                task.matches.push(Match {
+                    text: words[i..i + taken].join(" "),
+                    part,
+                    value,
+                });
+                i += taken;
+            }
+            None => {
+                title.push(words[i]);
+                i += 1;
+            }
+        }
+    }
+
+    if task.recurrence.is_some() && task.due_date.is_none() {
+        task.due_date = Some(first_due_date.unwrap_or(now.date()));
+    }
+
+    task.title = title.join(" ");
+    task
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dates_tags_and_recurrence() {
+        // A Friday morning.
+        let now = NaiveDate::from_ymd_opt(2025, 1, 10)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap();
+        let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day);
+
+        let task = parse(
+            "Pay rent tomorrow 9am !high #finance @alice every month",
+            now,
+        );
+        assert_eq!(task.title, "Pay rent");
+        assert_eq!(task.due_date, date(1, 11));
+        assert_eq!(task.priority.as_deref(), Some("high"));
+        assert_eq!(task.labels, vec!["finance"]);
+        assert_eq!(task.assignees, vec!["alice"]);
+        assert_eq!(task.recurrence, Recurrence::parse("FREQ=MONTHLY"));
+        assert_eq!(
+            task.matches[0],
+            Match {
+                text: "tomorrow 9am".to_string(),
+                part: Part::DueDate,
+                value: "2025-01-11".to_string(),
+            }
+        );
+
+        assert_eq!(parse("Standup at 7:30am", now).due_date, date(1, 11));
+        assert_eq!(parse("Call mom 6 pm", now).due_date, date(1, 10));
+        assert_eq!(parse("Review by next monday", now).due_date, date(1, 13));
+        assert_eq!(parse("Renew in 2 weeks", now).due_date, date(1, 24));
+        assert_eq!(
+            parse("Taxes on Jan 5th", now).due_date,
+            NaiveDate::from_ymd_opt(2026, 1, 5)
+        );
+
+        let task = parse("Water plants every other friday", now);
+        assert_eq!(task.title, "Water plants");
+        assert_eq!(task.due_date, date(1, 10));
+        assert_eq!(task.recurrence, Recurrence::parse("FREQ=WEEKLY;INTERVAL=2"));
+
+        let task = parse("Buy 9 eggs !now, then friday or monday", now);
+        assert_eq!(task.title, "Buy 9 eggs !now, then or monday");
+        assert_eq!(task.due_date, date(1, 10));
+    }
+}
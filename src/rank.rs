@@ -0,0 +1,120 @@
+//! Lexicographic rank keys used to order tasks inside a board column.
+//!
+//! Keys are base-36 strings compared byte by byte, so a task can be moved
+//! between two others by writing a single new key. Generated keys never end
+//! with `0`, which guarantees there is always room after them.
+
+const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE: usize = DIGITS.len();
+
+/// Keys longer than this are a sign that a column needs to be rebalanced.
+pub const MAX_LENGTH: usize = 32;
+
+fn value(digit: u8) -> usize {
+    DIGITS
+        .iter()
+        .position(|d| *d == digit)
+        .expect("Rank keys only contain base-36 digits")
+}
+
+/// Returns a key sorting strictly between `lower` and `upper`, where `None`
+/// stands for the start or the end of the column. Returns `None` when there
+/// is no such key, e.g. because both keys are equal.
+pub fn between(lower: Option<&str>, upper: Option<&str>) -> Option<String> {
+    let lower = lower.unwrap_or_default().as_bytes();
+    let mut upper = upper.map(str::as_bytes);
+
+    let mut key = Vec::new();
+
+    for i in 0.. {
+        let low = lower.get(i).map_or(0, |digit| value(*digit));
+
+        let high = match upper {
+            Some(upper) => value(*upper.get(i)?),
+            None => BASE,
+        };
+
+        if low > high {
+            return None;
+        }
+
+        if high - low > 1 {
+            key.push(DIGITS[(low + high) / 2]);
+            break;
+        }
+
+        key.push(DIGITS[low]);
+
+        if high - low == 1 {
+            // The key is now below `upper` whatever follows.
+            upper = None;
+        }
+    }
+
+    Some(String::from_utf8(key).expect("Rank keys are ASCII"))
+}
+
+/// Returns `count` evenly spread, increasing keys for rebalancing a column.
+pub fn spread(count: usize) -> Vec<String> {
+    let mut width = 1;
+
+    while BASE.pow(width as u32) <= count {
+        width += 1;
+    }
+
+    (1..=count)
+        .map(|position| {
+            let mut digits = vec![DIGITS[0]; width];
+            let mut rest = position;
+
+            for digit in digits.iter_mut().rev() {
+                *digit = DIGITS[rest % BASE];
+                rest /= BASE;
+            }
+
+            digits.push(DIGITS[BASE / 2]);
+
+            String::from_utf8(digits).expect("Rank keys are ASCII")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_keys_between_neighbours() {
+        assert_eq!(between(None, None).as_deref(), Some("i"));
+        assert_eq!(between(Some("a"), Some("b")).as_deref(), Some("ai"));
+        assert_eq!(between(Some("a"), Some("a1")).as_deref(), Some("a0i"));
+        assert_eq!(between(Some("a"), Some("a")), None);
+        assert_eq!(between(Some("b"), Some("a")), None);
+
+        let mut keys = vec![between(None, None).unwrap()];
+
+        for i in 0..200 {
+            let key = match i % 3 {
+                0 => between(None, Some(&keys[0])),
+                1 => between(Some(keys.last().unwrap()), None),
+                _ => between(Some(&keys[0]), Some(&keys[1])),
+            }
+            .unwrap();
+
+            keys.push(key);
+            keys.sort();
+            keys.dedup();
+        }
+
+        assert_eq!(keys.len(), 201);
+    }
+
+    #[test]
+    fn spreads_increasing_keys() {
+        let keys = spread(100);
+
+        assert_eq!(keys.len(), 100);
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(between(Some(&keys[0]), Some(&keys[1])).is_some());
+    }
+}
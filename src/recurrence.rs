@@ -0,0 +1,138 @@
+//! Recurrence rules of repeating tasks, stored as the subset of iCalendar
+//! `RRULE` values (RFC 5545) made of a `FREQ` and an optional `INTERVAL`,
+//! such as `FREQ=MONTHLY` or `FREQ=WEEKLY;INTERVAL=2`.
+
+use std::fmt;
+
+use chrono::{Days, Months, NaiveDate};
+
+/// Longest interval a rule can have, whatever its frequency.
+pub const MAX_INTERVAL: u32 = 999;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+}
+
+impl Recurrence {
+    pub fn new(frequency: Frequency, interval: u32) -> Self {
+        Self {
+            frequency,
+            interval,
+        }
+    }
+
+    /// Reads a rule. Rules with other parts, such as `BYDAY`, are not
+    /// supported.
+    pub fn parse(rule: &str) -> Option<Self> {
+        let mut frequency = None;
+        let mut interval = 1;
+
+        for part in rule.split(';') {
+            let (name, value) = part.split_once('=')?;
+
+            match name.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => interval = value.parse().ok()?,
+                _ => return None,
+            }
+        }
+
+        (1..=MAX_INTERVAL)
+            .contains(&interval)
+            .then_some(Self::new(frequency?, interval))
+    }
+
+    /// The `n`th occurrence after `start`. Months and years are counted from
+    /// `start`, so a task due on the 31st stays at the end of shorter months.
+    pub fn nth(&self, start: NaiveDate, n: u32) -> Option<NaiveDate> {
+        let steps = self.interval * n;
+
+        match self.frequency {
+            Frequency::Daily => start.checked_add_days(Days::new(steps.into())),
+            Frequency::Weekly => start.checked_add_days(Days::new(u64::from(steps) * 7)),
+            Frequency::Monthly => start.checked_add_months(Months::new(steps)),
+            Frequency::Yearly => start.checked_add_months(Months::new(steps * 12)),
+        }
+    }
+
+    /// Due date of the occurrence after one due on `due_date`, or started
+    /// `today` when it had none, skipping the occurrences already past.
+    pub fn next_due_date(&self, due_date: Option<NaiveDate>, today: NaiveDate) -> NaiveDate {
+        let start = due_date.unwrap_or(today);
+
+        (1..)
+            .map_while(|n| self.nth(start, n))
+            .find(|date| *date > today)
+            .unwrap_or(NaiveDate::MAX)
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FREQ={}", self.frequency.as_str())?;
+
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_next_due_date() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+
+        let monthly = Recurrence::parse("FREQ=MONTHLY").unwrap();
+        assert_eq!(monthly.to_string(), "FREQ=MONTHLY");
+        assert_eq!(
+            monthly.next_due_date(Some(date(1, 31)), date(1, 20)),
+            date(2, 28)
+        );
+        assert_eq!(
+            monthly.next_due_date(Some(date(1, 31)), date(3, 1)),
+            date(3, 31)
+        );
+
+        let fortnightly = Recurrence::parse("freq=weekly;interval=2").unwrap();
+        assert_eq!(fortnightly.to_string(), "FREQ=WEEKLY;INTERVAL=2");
+        assert_eq!(fortnightly.next_due_date(None, date(1, 1)), date(1, 15));
+
+        assert_eq!(Recurrence::parse("FREQ=WEEKLY;BYDAY=MO"), None);
+        assert_eq!(Recurrence::parse("FREQ=DAILY;INTERVAL=0"), None);
+        assert_eq!(Recurrence::parse("INTERVAL=2"), None);
+    }
+}
@@ -1,8 +1,26 @@
-use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use crate::models::_entities::{permission, role, task, user, user_profile};
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize)]
+use crate::{
+    models::{
+        _entities::{
+            calendar_feed, notification, permission, project, project_member, role, saved_view,
+            task, task_checklist_item, task_event, task_template, time_entry, user, user_profile,
+            webhook, webhook_delivery,
+        },
+        saved_view::{view_columns, view_filters},
+        task::{labels_of, COMPLETED},
+        task_checklist_item::ChecklistProgress,
+        time_entry::TimeReportRow,
+    },
+    quick_add::{Match, QuickTask},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, SimpleObject)]
+#[graphql(name = "UserSummary")]
 pub struct UserSerializer {
     pub id: i32,
     pub name: String,
@@ -21,11 +39,14 @@ impl From<user::Model> for UserSerializer {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema, SimpleObject)]
+#[graphql(name = "UserProfile")]
 pub struct UserProfileSerializer {
     pub id: i32,
     pub address: Option<String>,
     pub mobile_number: Option<String>,
+    pub weekly_capacity_minutes: i32,
+    pub timezone: String,
 }
 
 impl From<user_profile::Model> for UserProfileSerializer {
@@ -34,11 +55,13 @@ impl From<user_profile::Model> for UserProfileSerializer {
             id: value.id,
             address: value.address,
             mobile_number: value.mobile_number,
+            weekly_capacity_minutes: value.weekly_capacity_minutes,
+            timezone: value.timezone,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserWithProfileSerializer {
     pub id: i32,
     pub name: String,
@@ -63,30 +86,339 @@ impl From<(user::Model, Option<user_profile::Model>)> for UserWithProfileSeriali
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, SimpleObject)]
+#[graphql(name = "Task", complex)]
 pub struct TaskSerializer {
     pub id: i32,
     pub title: String,
     pub description: String,
     pub status: String,
+    pub reporter_id: i32,
+    pub key: Option<String>,
+    pub project_id: Option<i32>,
+    pub rank: String,
+    pub assignees: Vec<UserSerializer>,
+    pub checklist: ChecklistProgressSerializer,
+    pub auto_complete: bool,
+    pub estimate_minutes: Option<i32>,
+    pub due_date: Option<chrono::naive::NaiveDate>,
+    pub priority: Option<String>,
+    pub labels: Vec<String>,
+    pub recurrence: Option<String>,
     pub date_created: chrono::naive::NaiveDateTime,
     pub date_updated: Option<String>,
 }
 
 impl From<task::Model> for TaskSerializer {
     fn from(value: task::Model) -> Self {
+        Self::from((value, Vec::new(), ChecklistProgress::default()))
+    }
+}
+
+impl From<(task::Model, Vec<user::Model>, ChecklistProgress)> for TaskSerializer {
+    fn from(value: (task::Model, Vec<user::Model>, ChecklistProgress)) -> Self {
+        let (task, assignees, checklist) = value;
+        let labels = labels_of(&task);
+
+        Self {
+            id: task.id,
+            title: task.title,
+            description: task.description,
+            status: task.status,
+            reporter_id: task.user_id,
+            key: task.key,
+            project_id: task.project_id,
+            rank: task.rank,
+            assignees: assignees.into_iter().map(UserSerializer::from).collect(),
+            checklist: ChecklistProgressSerializer::from(checklist),
+            auto_complete: task.auto_complete,
+            estimate_minutes: task.estimate_minutes,
+            due_date: task.due_date,
+            labels,
+            priority: task.priority,
+            recurrence: task.recurrence,
+            date_created: task.date_created,
+            date_updated: task.date_updated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, SimpleObject)]
+#[graphql(name = "ChecklistProgress")]
+pub struct ChecklistProgressSerializer {
+    pub done: i64,
+    pub total: i64,
+}
+
+impl From<ChecklistProgress> for ChecklistProgressSerializer {
+    fn from(value: ChecklistProgress) -> Self {
+        Self {
+            done: value.done,
+            total: value.total,
+        }
+    }
+}
+
+/// Status of a task in `/api/v2`. Statuses stored before they were validated
+/// read as `pending`, where new tasks start.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
+
+impl From<&str> for TaskStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "in_progress" => Self::InProgress,
+            COMPLETED => Self::Completed,
+            _ => Self::Pending,
+        }
+    }
+}
+
+/// A task as `/api/v2` returns it: the status is one of `TaskStatus`, and
+/// `date_updated` a timestamp like `date_created` rather than SQLite text.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskV2Serializer {
+    pub id: i32,
+    pub title: String,
+    pub description: String,
+    pub status: TaskStatus,
+    pub reporter_id: i32,
+    pub key: Option<String>,
+    pub project_id: Option<i32>,
+    pub rank: String,
+    pub assignees: Vec<UserSerializer>,
+    pub checklist: ChecklistProgressSerializer,
+    pub auto_complete: bool,
+    pub estimate_minutes: Option<i32>,
+    pub due_date: Option<chrono::naive::NaiveDate>,
+    pub priority: Option<String>,
+    pub labels: Vec<String>,
+    pub recurrence: Option<String>,
+    pub date_created: chrono::naive::NaiveDateTime,
+    pub date_updated: Option<chrono::naive::NaiveDateTime>,
+}
+
+impl From<TaskSerializer> for TaskV2Serializer {
+    fn from(value: TaskSerializer) -> Self {
         Self {
             id: value.id,
             title: value.title,
             description: value.description,
-            status: value.status,
+            status: TaskStatus::from(value.status.as_str()),
+            reporter_id: value.reporter_id,
+            key: value.key,
+            project_id: value.project_id,
+            rank: value.rank,
+            assignees: value.assignees,
+            checklist: value.checklist,
+            auto_complete: value.auto_complete,
+            estimate_minutes: value.estimate_minutes,
+            due_date: value.due_date,
+            priority: value.priority,
+            labels: value.labels,
+            recurrence: value.recurrence,
+            date_created: value.date_created,
+            // Written by the `set_date_updated` trigger as CURRENT_TIMESTAMP.
+            date_updated: value.date_updated.and_then(|date_updated| {
+                chrono::NaiveDateTime::parse_from_str(&date_updated, "%Y-%m-%d %H:%M:%S").ok()
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChecklistItemSerializer {
+    pub id: i32,
+    pub task_id: i32,
+    pub text: String,
+    pub is_done: bool,
+    pub position: i32,
+    pub date_created: chrono::naive::NaiveDateTime,
+}
+
+impl From<task_checklist_item::Model> for ChecklistItemSerializer {
+    fn from(value: task_checklist_item::Model) -> Self {
+        Self {
+            id: value.id,
+            task_id: value.task_id,
+            text: value.text,
+            is_done: value.is_done,
+            position: value.position,
             date_created: value.date_created,
-            date_updated: value.date_updated,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TimeEntrySerializer {
+    pub id: i32,
+    pub user_id: i32,
+    pub task_id: i32,
+    pub started_at: chrono::naive::NaiveDateTime,
+    pub ended_at: Option<chrono::naive::NaiveDateTime>,
+    pub duration_seconds: Option<i64>,
+    pub note: Option<String>,
+}
+
+impl From<time_entry::Model> for TimeEntrySerializer {
+    fn from(value: time_entry::Model) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            task_id: value.task_id,
+            started_at: value.started_at,
+            ended_at: value.ended_at,
+            duration_seconds: value
+                .ended_at
+                .map(|ended_at| (ended_at - value.started_at).num_seconds()),
+            note: value.note,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TimeReportRowSerializer {
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub task_id: Option<i32>,
+    pub task_key: Option<String>,
+    pub task_title: Option<String>,
+    pub project_id: Option<i32>,
+    pub project_name: Option<String>,
+    pub day: Option<String>,
+    pub hours: f64,
+}
+
+impl From<TimeReportRow> for TimeReportRowSerializer {
+    fn from(value: TimeReportRow) -> Self {
+        Self {
+            user_id: value.user_id,
+            username: value.username,
+            task_id: value.task_id,
+            task_key: value.task_key,
+            task_title: value.task_title,
+            project_id: value.project_id,
+            project_name: value.project_name,
+            day: value.day,
+            hours: (value.seconds as f64 / 36.0).round() / 100.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TimeReportSerializer {
+    pub from: chrono::naive::NaiveDate,
+    pub to: chrono::naive::NaiveDate,
+    pub group_by: Vec<String>,
+    pub total_hours: f64,
+    pub rows: Vec<TimeReportRowSerializer>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkloadDaySerializer {
+    pub date: chrono::naive::NaiveDate,
+    pub capacity_minutes: i32,
+    pub assigned_minutes: i32,
+    pub overloaded: bool,
+    pub task_ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserWorkloadSerializer {
+    pub user: UserSerializer,
+    pub weekly_capacity_minutes: i32,
+    pub capacity_minutes: i32,
+    pub assigned_minutes: i32,
+    pub utilization: Option<f64>,
+    pub overloaded: bool,
+    pub unestimated_task_ids: Vec<i32>,
+    pub days: Vec<WorkloadDaySerializer>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkloadSerializer {
+    pub from: chrono::naive::NaiveDate,
+    pub to: chrono::naive::NaiveDate,
+    pub users: Vec<UserWorkloadSerializer>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AssigneeCountSerializer {
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DailyTaskCountSerializer {
+    pub date: String,
+    pub created: i64,
+    pub completed: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskStatsSerializer {
+    pub from: chrono::naive::NaiveDate,
+    pub to: chrono::naive::NaiveDate,
+    pub total: i64,
+    pub by_status: BTreeMap<String, i64>,
+    pub by_priority: BTreeMap<String, i64>,
+    pub by_assignee: Vec<AssigneeCountSerializer>,
+    pub daily: Vec<DailyTaskCountSerializer>,
+    pub average_cycle_time_hours: Option<f64>,
+    pub average_lead_time_hours: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BurndownPointSerializer {
+    pub date: String,
+    pub remaining_tasks: i64,
+    pub remaining_minutes: i64,
+    pub ideal_tasks: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BurndownSerializer {
+    pub from: chrono::naive::NaiveDate,
+    pub to: chrono::naive::NaiveDate,
+    pub project_id: Option<i32>,
+    pub series: Vec<BurndownPointSerializer>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskEventSerializer {
+    pub id: i32,
+    pub task_id: i32,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub user: Option<UserSerializer>,
+    pub date_created: chrono::naive::NaiveDateTime,
+}
+
+impl From<(task_event::Model, Option<user::Model>)> for TaskEventSerializer {
+    fn from(value: (task_event::Model, Option<user::Model>)) -> Self {
+        let (event, user) = value;
+
+        Self {
+            id: event.id,
+            task_id: event.task_id,
+            field: event.field,
+            old_value: event.old_value,
+            new_value: event.new_value,
+            user: user.map(UserSerializer::from),
+            date_created: event.date_created,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema, SimpleObject)]
+#[graphql(name = "Permission")]
 pub struct PermissionSerializer {
     pub id: i32,
     pub name: String,
@@ -103,7 +435,8 @@ impl From<permission::Model> for PermissionSerializer {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema, SimpleObject)]
+#[graphql(name = "Role")]
 pub struct RoleSerializer {
     pub id: i32,
     pub name: String,
@@ -117,3 +450,357 @@ impl From<role::Model> for RoleSerializer {
         }
     }
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProjectSerializer {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub owner_id: i32,
+    pub key_prefix: String,
+    pub is_inbox: bool,
+    pub is_archived: bool,
+    pub date_created: chrono::naive::NaiveDateTime,
+}
+
+impl From<project::Model> for ProjectSerializer {
+    fn from(value: project::Model) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            description: value.description,
+            owner_id: value.owner_id,
+            key_prefix: value.key_prefix,
+            is_inbox: value.is_inbox,
+            is_archived: value.is_archived,
+            date_created: value.date_created,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProjectMemberSerializer {
+    pub user: Option<UserSerializer>,
+    pub role: String,
+    pub date_created: chrono::naive::NaiveDateTime,
+}
+
+impl From<(project_member::Model, Option<user::Model>)> for ProjectMemberSerializer {
+    fn from(value: (project_member::Model, Option<user::Model>)) -> Self {
+        let (member, user) = value;
+
+        Self {
+            user: user.map(UserSerializer::from),
+            role: member.role,
+            date_created: member.date_created,
+        }
+    }
+}
+
+/// A column of the board, with its tasks as `T` of the API version.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BoardColumnSerializer<T = TaskSerializer> {
+    pub status: String,
+    pub wip_limit: Option<i32>,
+    pub count: usize,
+    pub tasks: Vec<T>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BoardSerializer<T = TaskSerializer> {
+    pub project: ProjectSerializer,
+    pub columns: Vec<BoardColumnSerializer<T>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookSerializer {
+    pub id: i32,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub date_created: chrono::naive::NaiveDateTime,
+}
+
+impl From<webhook::Model> for WebhookSerializer {
+    fn from(value: webhook::Model) -> Self {
+        Self {
+            id: value.id,
+            url: value.url,
+            event_types: value
+                .event_types
+                .split(',')
+                .map(|event_type| event_type.to_string())
+                .collect(),
+            is_active: value.is_active,
+            date_created: value.date_created,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliverySerializer {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: chrono::naive::NaiveDateTime,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub date_created: chrono::naive::NaiveDateTime,
+    pub date_delivered: Option<chrono::naive::NaiveDateTime>,
+}
+
+impl From<webhook_delivery::Model> for WebhookDeliverySerializer {
+    fn from(value: webhook_delivery::Model) -> Self {
+        Self {
+            id: value.id,
+            webhook_id: value.webhook_id,
+            event_type: value.event_type,
+            payload: serde_json::from_str(&value.payload).unwrap_or_default(),
+            status: value.status,
+            attempts: value.attempts,
+            next_attempt_at: value.next_attempt_at,
+            response_status: value.response_status,
+            last_error: value.last_error,
+            date_created: value.date_created,
+            date_delivered: value.date_delivered,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationSerializer {
+    pub id: i32,
+    pub kind: String,
+    pub message: String,
+    pub task_id: Option<i32>,
+    pub actor: Option<UserSerializer>,
+    pub is_read: bool,
+    pub read_at: Option<chrono::naive::NaiveDateTime>,
+    pub date_created: chrono::naive::NaiveDateTime,
+}
+
+impl From<(notification::Model, Option<user::Model>)> for NotificationSerializer {
+    fn from(value: (notification::Model, Option<user::Model>)) -> Self {
+        let (notification, actor) = value;
+
+        Self {
+            id: notification.id,
+            kind: notification.kind,
+            message: notification.message,
+            task_id: notification.task_id,
+            actor: actor.map(UserSerializer::from),
+            is_read: notification.read_at.is_some(),
+            read_at: notification.read_at,
+            date_created: notification.date_created,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationPreferenceSerializer {
+    pub kind: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SavedViewSerializer {
+    pub id: i32,
+    pub owner_id: i32,
+    pub name: String,
+    pub slug: String,
+    pub filters: HashMap<String, String>,
+    pub sort: Option<String>,
+    pub columns: Vec<String>,
+    pub visibility: String,
+    pub role_ids: Vec<i32>,
+    pub date_created: chrono::naive::NaiveDateTime,
+}
+
+impl From<(saved_view::Model, Vec<i32>)> for SavedViewSerializer {
+    fn from(value: (saved_view::Model, Vec<i32>)) -> Self {
+        let (view, role_ids) = value;
+
+        Self {
+            id: view.id,
+            owner_id: view.user_id,
+            filters: view_filters(&view),
+            columns: view_columns(&view),
+            name: view.name,
+            slug: view.slug,
+            sort: view.sort,
+            visibility: view.visibility,
+            role_ids,
+            date_created: view.date_created,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskTemplateSerializer {
+    pub id: i32,
+    pub owner_id: i32,
+    pub project_id: Option<i32>,
+    pub name: String,
+    pub description: String,
+    pub placeholders: Vec<String>,
+    pub tasks: serde_json::Value,
+    pub date_created: chrono::naive::NaiveDateTime,
+}
+
+impl From<(task_template::Model, BTreeSet<String>)> for TaskTemplateSerializer {
+    fn from(value: (task_template::Model, BTreeSet<String>)) -> Self {
+        let (template, placeholders) = value;
+
+        Self {
+            id: template.id,
+            owner_id: template.user_id,
+            project_id: template.project_id,
+            name: template.name,
+            description: template.description,
+            placeholders: placeholders.into_iter().collect(),
+            tasks: serde_json::from_str(&template.tasks).unwrap_or_default(),
+            date_created: template.date_created,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportErrorSerializer {
+    pub row: usize,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportReportSerializer {
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub valid_rows: usize,
+    pub imported: usize,
+    pub task_ids: Vec<i32>,
+    pub errors: Vec<ImportErrorSerializer>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarFeedSerializer {
+    pub path: String,
+    pub date_created: chrono::naive::NaiveDateTime,
+}
+
+impl From<calendar_feed::Model> for CalendarFeedSerializer {
+    fn from(value: calendar_feed::Model) -> Self {
+        Self {
+            path: format!("/api/calendar/tasks.ics?token={}", value.token),
+            date_created: value.date_created,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuickAddMatchSerializer {
+    pub text: String,
+    pub part: &'static str,
+    pub value: String,
+}
+
+impl From<Match> for QuickAddMatchSerializer {
+    fn from(value: Match) -> Self {
+        Self {
+            text: value.text,
+            part: value.part.as_str(),
+            value: value.value,
+        }
+    }
+}
+
+/// What quick add understood of the text of a task.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuickAddParseSerializer {
+    pub title: String,
+    pub due_date: Option<chrono::naive::NaiveDate>,
+    pub priority: Option<String>,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub recurrence: Option<String>,
+    pub matches: Vec<QuickAddMatchSerializer>,
+}
+
+impl From<QuickTask> for QuickAddParseSerializer {
+    fn from(value: QuickTask) -> Self {
+        Self {
+            title: value.title,
+            due_date: value.due_date,
+            priority: value.priority,
+            labels: value.labels,
+            assignees: value.assignees,
+            recurrence: value.recurrence.map(|recurrence| recurrence.to_string()),
+            matches: value
+                .matches
+                .into_iter()
+                .map(QuickAddMatchSerializer::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuickAddSerializer<T = TaskSerializer> {
+    pub task: T,
+    pub parsed: QuickAddParseSerializer,
+}
+
+/// A task in the change feed, with the version it was changed to.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncTaskSerializer {
+    pub version: i32,
+    #[serde(flatten)]
+    pub task: TaskSerializer,
+}
+
+/// A task deleted, or no longer visible, since the last sync.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncTombstoneSerializer {
+    pub id: i32,
+    pub version: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncChangesSerializer {
+    /// Token to pass as `since` on the next sync.
+    pub token: String,
+    /// Whether more changes are waiting after `token`.
+    pub has_more: bool,
+    pub created: Vec<SyncTaskSerializer>,
+    pub updated: Vec<SyncTaskSerializer>,
+    pub deleted: Vec<SyncTombstoneSerializer>,
+}
+
+/// A field changed on the server since the base version of a client change.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncConflictSerializer {
+    pub field: String,
+    pub client_value: serde_json::Value,
+    pub server_value: serde_json::Value,
+    pub server_version: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncErrorSerializer {
+    pub field: Option<String>,
+    pub message: String,
+}
+
+/// Outcome of one client change. `task` is the task as now on the server.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncResultSerializer {
+    pub client_id: Option<String>,
+    pub id: Option<i32>,
+    pub status: &'static str,
+    pub version: Option<i32>,
+    pub task: Option<TaskSerializer>,
+    pub conflicts: Vec<SyncConflictSerializer>,
+    pub errors: Vec<SyncErrorSerializer>,
+}
@@ -0,0 +1,398 @@
+//! Task statistics computed with SQL aggregates.
+//!
+//! Completion and start times come from the `status` entries of the task
+//! history, so tasks changed before the history existed only count towards
+//! the totals that need no timestamps.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use sea_orm::{
+    sea_query::{Expr, Query},
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, FromQueryResult, JoinType,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait, Statement,
+};
+
+use crate::models::{
+    _entities::{task, task_assignee, task_event, user},
+    task::COMPLETED,
+};
+
+/// SQL for the first or `latest` time a task was moved to `status`, or NULL.
+fn status_changed_at(status: &str, latest: bool) -> String {
+    format!(
+        "(SELECT {}(task_event.date_created) FROM task_event WHERE task_event.task_id = task.id AND task_event.field = 'status' AND task_event.new_value = '{}')",
+        if latest { "MAX" } else { "MIN" },
+        status
+    )
+}
+
+#[derive(Debug, FromQueryResult)]
+pub struct AssigneeCount {
+    pub user_id: i32,
+    pub username: String,
+    pub count: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+pub struct DayCount {
+    pub day: String,
+    pub count: i64,
+}
+
+/// Number of tasks matching `scope` per value of `column`.
+pub async fn count_by<C>(
+    db: &C,
+    scope: Condition,
+    column: task::Column,
+) -> Result<Vec<(Option<String>, i64)>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    task::Entity::find()
+        .select_only()
+        .column(column)
+        .column_as(task::Column::Id.count(), "count")
+        .filter(scope)
+        .group_by(column)
+        .order_by_asc(column)
+        .into_tuple()
+        .all(db)
+        .await
+}
+
+/// Number of tasks matching `scope` per assignee, and the number of tasks
+/// without any.
+pub async fn count_by_assignee<C>(
+    db: &C,
+    scope: Condition,
+) -> Result<(Vec<AssigneeCount>, u64), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let assignees = task_assignee::Entity::find()
+        .select_only()
+        .column(task_assignee::Column::UserId)
+        .column(user::Column::Username)
+        .column_as(task_assignee::Column::TaskId.count(), "count")
+        .join(JoinType::InnerJoin, task_assignee::Relation::Task.def())
+        .join(JoinType::InnerJoin, task_assignee::Relation::User.def())
+        .filter(scope.clone())
+        .group_by(task_assignee::Column::UserId)
+        .group_by(user::Column::Username)
+        .order_by_asc(task_assignee::Column::UserId)
+        .into_model::<AssigneeCount>()
+        .all(db)
+        .await?;
+
+    let unassigned = task::Entity::find()
+        .filter(scope)
+        .filter(
+            task::Column::Id.not_in_subquery(
+                Query::select()
+                    .column(task_assignee::Column::TaskId)
+                    .from(task_assignee::Entity)
+                    .to_owned(),
+            ),
+        )
+        .count(db)
+        .await?;
+
+    Ok((assignees, unassigned))
+}
+
+/// Tasks matching `scope` created per day between `from` and `to`.
+pub async fn created_per_day<C>(
+    db: &C,
+    scope: Condition,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> Result<Vec<DayCount>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    task::Entity::find()
+        .select_only()
+        .column_as(Expr::cust("date(task.date_created)"), "day")
+        .column_as(task::Column::Id.count(), "count")
+        .filter(scope)
+        .filter(task::Column::DateCreated.gte(from))
+        .filter(task::Column::DateCreated.lt(to))
+        .group_by(Expr::cust("date(task.date_created)"))
+        .into_model::<DayCount>()
+        .all(db)
+        .await
+}
+
+/// Tasks matching `scope` moved to completed per day between `from` and `to`.
+pub async fn completed_per_day<C>(
+    db: &C,
+    scope: Condition,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> Result<Vec<DayCount>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    task_event::Entity::find()
+        .select_only()
+        .column_as(Expr::cust("date(task_event.date_created)"), "day")
+        .column_as(Expr::cust("COUNT(DISTINCT task_event.task_id)"), "count")
+        .join(JoinType::InnerJoin, task_event::Relation::Task.def())
+        .filter(scope)
+        .filter(task_event::Column::Field.eq("status"))
+        .filter(task_event::Column::NewValue.eq(COMPLETED))
+        .filter(task_event::Column::DateCreated.gte(from))
+        .filter(task_event::Column::DateCreated.lt(to))
+        .group_by(Expr::cust("date(task_event.date_created)"))
+        .into_model::<DayCount>()
+        .all(db)
+        .await
+}
+
+/// Average cycle time (created to completed) and lead time (created to first
+/// started) in hours, of the tasks matching `scope` completed between `from`
+/// and `to`.
+pub async fn average_times<C>(
+    db: &C,
+    scope: Condition,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> Result<(Option<f64>, Option<f64>), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let completed_at = status_changed_at(COMPLETED, true);
+    let started_at = status_changed_at("in_progress", false);
+
+    let times: Option<(Option<f64>, Option<f64>)> = task::Entity::find()
+        .select_only()
+        .column_as(
+            Expr::cust(format!(
+                "AVG((julianday({}) - julianday(task.date_created)) * 24)",
+                completed_at
+            )),
+            "cycle_time",
+        )
+        .column_as(
+            Expr::cust(format!(
+                "AVG((julianday({}) - julianday(task.date_created)) * 24)",
+                started_at
+            )),
+            "lead_time",
+        )
+        .filter(scope)
+        .filter(task::Column::Status.eq(COMPLETED))
+        .filter(Expr::cust_with_values(
+            format!("{} >= ? AND {} < ?", completed_at, completed_at),
+            [from, to],
+        ))
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    Ok(times.unwrap_or_default())
+}
+
+#[derive(Debug, FromQueryResult)]
+pub struct BurndownPoint {
+    pub day: String,
+    pub remaining_tasks: i64,
+    pub remaining_minutes: i64,
+}
+
+/// Open tasks matching `scope`, and their remaining estimate, at the end of
+/// every day between `from` and `to`. The status of a task on a given day is
+/// the last one recorded in its history up to then.
+pub async fn burndown<C>(
+    db: &C,
+    scope: Condition,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<BurndownPoint>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    // The scope is rendered by sea-query so the day series can be joined with
+    // it in a single statement.
+    let scope = task::Entity::find()
+        .select_only()
+        .column(task::Column::Id)
+        .filter(scope)
+        .build(db.get_database_backend());
+    let scope_sql = scope.sql;
+
+    let status_on_day = "COALESCE(\
+            (SELECT e.new_value FROM task_event e WHERE e.task_id = task.id AND e.field = 'status' \
+                AND date(e.date_created) <= days.day ORDER BY e.date_created DESC, e.id DESC LIMIT 1), \
+            (SELECT e.old_value FROM task_event e WHERE e.task_id = task.id AND e.field = 'status' \
+                ORDER BY e.date_created, e.id LIMIT 1), \
+            task.status)";
+
+    let sql = format!(
+        "WITH RECURSIVE days(day) AS (\
+            SELECT date('{from}') \
+            UNION ALL SELECT date(day, '+1 day') FROM days WHERE day < date('{to}')\
+        ) \
+        SELECT days.day AS day, \
+            COUNT(task.id) AS remaining_tasks, \
+            COALESCE(SUM(task.estimate_minutes), 0) AS remaining_minutes \
+        FROM days \
+        LEFT JOIN task ON date(task.date_created) <= days.day \
+            AND task.id IN ({scope_sql}) \
+            AND {status_on_day} != '{COMPLETED}' \
+        GROUP BY days.day \
+        ORDER BY days.day",
+    );
+
+    BurndownPoint::find_by_statement(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        sql,
+        scope.values.map(|values| values.0).unwrap_or_default(),
+    ))
+    .all(db)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{ActiveModelTrait, ActiveValue::NotSet, Set};
+
+    use super::*;
+    use crate::{
+        testing::{app_state, create_project, create_task, create_user},
+        AppState,
+    };
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn day(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    /// Backdates `task` to `created`, with `changes` of status as `(old, new,
+    /// when)`, the last one being its current status.
+    async fn seed(
+        app_state: &AppState,
+        task: task::Model,
+        created: &str,
+        estimate_minutes: Option<i32>,
+        changes: &[(&str, &str, &str)],
+    ) {
+        let status = changes.last().map_or("pending", |(_, new, _)| *new);
+
+        let mut task: task::ActiveModel = task.into();
+        task.date_created = Set(at(created));
+        task.estimate_minutes = Set(estimate_minutes);
+        task.status = Set(status.to_string());
+        let task = task.update(&app_state.db).await.unwrap();
+
+        for (old, new, when) in changes {
+            task_event::ActiveModel {
+                id: NotSet,
+                task_id: Set(task.id),
+                user_id: Set(Some(task.user_id)),
+                field: Set("status".to_string()),
+                old_value: Set(Some(old.to_string())),
+                new_value: Set(Some(new.to_string())),
+                date_created: Set(at(when)),
+            }
+            .insert(&app_state.db)
+            .await
+            .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn aggregates_count_seeded_tasks() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let project = create_project(&app_state, &alice, "ops", &[]).await;
+        let other = create_project(&app_state, &alice, "web", &[]).await;
+
+        let report = create_task(&app_state, &alice, &project, "Write report").await;
+        seed(
+            &app_state,
+            report,
+            "2025-01-01 08:00",
+            Some(30),
+            &[
+                ("pending", "in_progress", "2025-01-02 10:00"),
+                ("in_progress", COMPLETED, "2025-01-03 09:00"),
+            ],
+        )
+        .await;
+
+        let review = create_task(&app_state, &alice, &project, "Review").await;
+        seed(&app_state, review, "2025-01-02 09:00", Some(60), &[]).await;
+
+        let deploy = create_task(&app_state, &alice, &project, "Deploy").await;
+        seed(
+            &app_state,
+            deploy,
+            "2025-01-02 12:00",
+            None,
+            &[("pending", "in_progress", "2025-01-02 13:00")],
+        )
+        .await;
+
+        // Out of scope.
+        let elsewhere = create_task(&app_state, &alice, &other, "Elsewhere").await;
+        seed(&app_state, elsewhere, "2025-01-02 12:00", Some(15), &[]).await;
+
+        let scope = Condition::all().add(task::Column::ProjectId.eq(project.id));
+        let (from, to) = (at("2025-01-01 00:00"), at("2025-01-05 00:00"));
+
+        let by_status = count_by(&app_state.db, scope.clone(), task::Column::Status)
+            .await
+            .unwrap();
+        assert_eq!(
+            by_status,
+            vec![
+                (Some(COMPLETED.to_string()), 1),
+                (Some("in_progress".to_string()), 1),
+                (Some("pending".to_string()), 1),
+            ]
+        );
+
+        let per_day = |days: Vec<DayCount>| -> Vec<(String, i64)> {
+            days.into_iter().map(|day| (day.day, day.count)).collect()
+        };
+
+        let created = created_per_day(&app_state.db, scope.clone(), from, to)
+            .await
+            .unwrap();
+        assert_eq!(
+            per_day(created),
+            vec![("2025-01-01".to_string(), 1), ("2025-01-02".to_string(), 2)]
+        );
+
+        let completed = completed_per_day(&app_state.db, scope.clone(), from, to)
+            .await
+            .unwrap();
+        assert_eq!(per_day(completed), vec![("2025-01-03".to_string(), 1)]);
+
+        let (cycle_time, lead_time) = average_times(&app_state.db, scope.clone(), from, to)
+            .await
+            .unwrap();
+        assert_eq!(cycle_time.map(f64::round), Some(49.0));
+        assert_eq!(lead_time.map(f64::round), Some(26.0));
+
+        let series: Vec<(String, i64, i64)> =
+            burndown(&app_state.db, scope, day("2025-01-01"), day("2025-01-04"))
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|point| (point.day, point.remaining_tasks, point.remaining_minutes))
+                .collect();
+        assert_eq!(
+            series,
+            vec![
+                ("2025-01-01".to_string(), 1, 30),
+                ("2025-01-02".to_string(), 3, 90),
+                ("2025-01-03".to_string(), 2, 60),
+                ("2025-01-04".to_string(), 2, 60),
+            ]
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Fixtures for the tests that need a database: a migrated in-memory SQLite
+//! database and the users, projects and tasks to fill it with.
+
+use std::sync::{Arc, Once};
+
+use axum::{body::to_bytes, response::IntoResponse};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ActiveModelTrait, ColumnTrait, Database, EntityTrait, QueryFilter, Set};
+
+use crate::{
+    auth::jwt::create_user_token,
+    collaboration,
+    controller::task_controller::insert_task,
+    events,
+    form::task_form::CreateTaskRequest,
+    models::{
+        _entities::{permission, project, task, user, user_permission},
+        project::{add_member, inbox, ProjectPermission, ProjectRole},
+    },
+    AppState,
+};
+
+/// An application state on a fresh database with every migration applied.
+pub async fn app_state() -> Arc<AppState> {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+
+    Migrator::up(&db, None).await.unwrap();
+
+    Arc::new(AppState {
+        db,
+        events: events::EventHub::default(),
+        collaboration: collaboration::CollaborationHub::default(),
+    })
+}
+
+/// Creates the user `username`, with their Inbox.
+pub async fn create_user(app_state: &AppState, username: &str) -> user::Model {
+    let user = user::ActiveModel {
+        name: Set(username.to_string()),
+        username: Set(username.to_string()),
+        email: Set(format!("{}@example.com", username)),
+        password: Set("password".to_string()),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+
+    inbox(&app_state.db, &user).await.unwrap();
+
+    user
+}
+
+/// A bearer token of `user`, signed with a secret set for the tests unless
+/// the environment has one.
+pub async fn token_of(user: &user::Model) -> String {
+    static SECRET: Once = Once::new();
+
+    SECRET.call_once(|| {
+        if std::env::var("JWT_SECRET").is_err() {
+            std::env::set_var("JWT_SECRET", "test-secret");
+        }
+    });
+
+    create_user_token(&user.email, 60).await
+}
+
+/// Grants `permission` on every project to `user`.
+pub async fn grant(app_state: &AppState, user: &user::Model, permission: ProjectPermission) {
+    let code_name = permission.code_name();
+
+    let permission = match permission::Entity::find()
+        .filter(permission::Column::CodeName.eq(code_name))
+        .one(&app_state.db)
+        .await
+        .unwrap()
+    {
+        Some(permission) => permission,
+        None => permission::ActiveModel {
+            name: Set(code_name.to_string()),
+            code_name: Set(code_name.to_string()),
+            ..Default::default()
+        }
+        .insert(&app_state.db)
+        .await
+        .unwrap(),
+    };
+
+    user_permission::ActiveModel {
+        user_id: Set(user.id),
+        permission_id: Set(permission.id),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+}
+
+/// Creates the project `name` owned by `owner`, with `members` as editors.
+pub async fn create_project(
+    app_state: &AppState,
+    owner: &user::Model,
+    name: &str,
+    members: &[&user::Model],
+) -> project::Model {
+    let project = project::ActiveModel {
+        name: Set(name.to_string()),
+        owner_id: Set(owner.id),
+        key_prefix: Set(name.to_ascii_uppercase()),
+        is_inbox: Set(false),
+        is_archived: Set(false),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+
+    add_member(&app_state.db, project.id, owner.id, ProjectRole::Owner)
+        .await
+        .unwrap();
+
+    for member in members {
+        add_member(&app_state.db, project.id, member.id, ProjectRole::Editor)
+            .await
+            .unwrap();
+    }
+
+    project
+}
+
+/// Creates the pending task `title` of `reporter` in `project`.
+pub async fn create_task(
+    app_state: &AppState,
+    reporter: &user::Model,
+    project: &project::Model,
+    title: &str,
+) -> task::Model {
+    let task = insert_task(
+        app_state,
+        reporter,
+        CreateTaskRequest {
+            title: title.to_string(),
+            description: String::new(),
+            status: "pending".to_string(),
+            user_id: reporter.id,
+            project_id: Some(project.id),
+            auto_complete: false,
+            estimate_minutes: None,
+            due_date: None,
+            priority: None,
+            labels: Vec::new(),
+            recurrence: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    task::Entity::find_by_id(task.id)
+        .one(&app_state.db)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+/// The `data` of the response of a handler.
+pub async fn response_data(response: impl IntoResponse) -> serde_json::Value {
+    let body = to_bytes(response.into_response().into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let mut response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    response["data"].take()
+}
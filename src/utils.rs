@@ -1,5 +1,6 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
+use chrono::NaiveDate;
 use hmac::{self, Hmac, Mac};
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use sea_orm::ColumnTrait;
@@ -52,3 +53,41 @@ pub async fn verify_token(app_state: Arc<AppState>, token: &str) -> Result<user:
 
     Ok(user)
 }
+
+/// Reads the inclusive `from` and `to` dates of a report from its query
+/// parameters.
+pub fn parse_date_range(
+    params: &HashMap<String, String>,
+) -> Result<(NaiveDate, NaiveDate), AppError> {
+    let parse_date = |name: &str| {
+        let value = params
+            .get(name)
+            .ok_or(AppError::BadRequest(format!("{} is required.", name)))?;
+
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| AppError::BadRequest(format!("{} must be a date like 2025-01-31.", name)))
+    };
+
+    let from = parse_date("from")?;
+    let to = parse_date("to")?;
+
+    if to < from {
+        return Err(AppError::BadRequest(
+            "to must not be before from.".to_string(),
+        ));
+    }
+
+    Ok((from, to))
+}
+
+/// Reads an optional id from query parameters.
+pub fn parse_id(params: &HashMap<String, String>, name: &str) -> Result<Option<i32>, AppError> {
+    params
+        .get(name)
+        .map(|value| {
+            value
+                .parse::<i32>()
+                .map_err(|_| AppError::BadRequest(format!("Invalid {}.", name)))
+        })
+        .transpose()
+}
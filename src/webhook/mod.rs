@@ -0,0 +1,295 @@
+use std::net::{IpAddr, SocketAddr};
+
+use hmac::{Hmac, Mac};
+use sea_orm::{
+    ActiveValue::NotSet, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+use serde::Serialize;
+use serde_json::json;
+use sha2::Sha256;
+use url::{Host, Url};
+
+use crate::models::_entities::{webhook, webhook_delivery};
+
+pub mod worker;
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_DELIVERED: &str = "delivered";
+pub const STATUS_FAILED: &str = "failed";
+
+/// Subscribes a webhook to every event type.
+pub const WILDCARD: &str = "*";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    TaskCreated,
+    TaskUpdated,
+    TaskStatusChanged,
+    TaskDeleted,
+    UserCreated,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 5] = [
+        WebhookEvent::TaskCreated,
+        WebhookEvent::TaskUpdated,
+        WebhookEvent::TaskStatusChanged,
+        WebhookEvent::TaskDeleted,
+        WebhookEvent::UserCreated,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::TaskCreated => "task.created",
+            WebhookEvent::TaskUpdated => "task.updated",
+            WebhookEvent::TaskStatusChanged => "task.status_changed",
+            WebhookEvent::TaskDeleted => "task.deleted",
+            WebhookEvent::UserCreated => "user.created",
+        }
+    }
+
+    pub fn is_valid(event_type: &str) -> bool {
+        event_type == WILDCARD || Self::ALL.iter().any(|event| event.as_str() == event_type)
+    }
+}
+
+/// Hex encoded HMAC-SHA256 of the payload, sent in the `X-Webhook-Signature` header.
+pub fn sign(secret: &str, payload: &str) -> String {
+    let mut mac: Hmac<Sha256> =
+        Hmac::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+
+    mac.update(payload.as_bytes());
+
+    hex::encode(mac.finalize().into_bytes())
+}
+
+pub fn subscribes_to(webhook: &webhook::Model, event: WebhookEvent) -> bool {
+    webhook
+        .event_types
+        .split(',')
+        .map(str::trim)
+        .any(|event_type| event_type == WILDCARD || event_type == event.as_str())
+}
+
+/// Whether `ip` is reachable from the internet, rather than an address of the
+/// server itself or of a private network webhooks must not reach into.
+pub fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let shared = ip.octets()[0] == 100 && ip.octets()[1] & 0xc0 == 64;
+
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || shared)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                let unique_local = ip.segments()[0] & 0xfe00 == 0xfc00;
+                let link_local = ip.segments()[0] & 0xffc0 == 0xfe80;
+
+                !(ip.is_loopback() || ip.is_unspecified() || unique_local || link_local)
+            }
+        },
+    }
+}
+
+/// Checks that `url` can be the target of a webhook: an HTTP(S) URL whose host
+/// is not a private, loopback or link-local address. Host names are checked
+/// by `resolve_target` once they are resolved.
+pub fn check_url(url: &str) -> Result<Url, String> {
+    let url = Url::parse(url).map_err(|err| err.to_string())?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("Must be an HTTP or HTTPS URL".to_string());
+    }
+
+    let public = match url.host() {
+        Some(Host::Ipv4(ip)) => is_public(ip.into()),
+        Some(Host::Ipv6(ip)) => is_public(ip.into()),
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+
+            domain != "localhost" && !domain.ends_with(".localhost")
+        }
+        None => false,
+    };
+
+    if !public {
+        return Err("Must not target a private address".to_string());
+    }
+
+    Ok(url)
+}
+
+/// A webhook URL checked by `resolve_target`, with the addresses its host name
+/// resolved to. Deliveries must connect to these addresses only.
+pub struct Target {
+    pub url: Url,
+    pub addresses: Vec<SocketAddr>,
+}
+
+/// Checks `url` like `check_url`, then every address its host resolves to, so
+/// a public name cannot point deliveries at a private address.
+pub async fn resolve_target(url: &str) -> Result<Target, String> {
+    let url = check_url(url)?;
+    let mut addresses = Vec::new();
+
+    if let Some(Host::Domain(domain)) = url.host() {
+        let port = url.port_or_known_default().unwrap_or(80);
+
+        for address in tokio::net::lookup_host((domain, port))
+            .await
+            .map_err(|err| err.to_string())?
+        {
+            if !is_public(address.ip()) {
+                return Err(format!("{} resolves to a private address", domain));
+            }
+
+            addresses.push(address);
+        }
+
+        if addresses.is_empty() {
+            return Err(format!("{} does not resolve", domain));
+        }
+    }
+
+    Ok(Target { url, addresses })
+}
+
+/// Queues a delivery of `data` for every active webhook subscribed to `event`
+/// whose owner is in `audience`, the users who may see `data`. `None` is for
+/// data every user may see.
+///
+/// Deliveries are only written to the outbox here; `worker` sends them. Pass the
+/// transaction of the change that triggered the event so both commit together.
+pub async fn dispatch<C>(
+    db: &C,
+    event: WebhookEvent,
+    data: impl Serialize,
+    audience: Option<&[i32]>,
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let mut webhook_query = webhook::Entity::find().filter(webhook::Column::IsActive.eq(true));
+
+    if let Some(audience) = audience {
+        webhook_query = webhook_query.filter(webhook::Column::UserId.is_in(audience.to_vec()));
+    }
+
+    let webhooks: Vec<webhook::Model> = webhook_query
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(|webhook| subscribes_to(webhook, event))
+        .collect();
+
+    if webhooks.is_empty() {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now();
+
+    let payload = json!({
+        "event": event.as_str(),
+        "data": data,
+        "timestamp": now.to_rfc3339(),
+    })
+    .to_string();
+
+    let deliveries: Vec<webhook_delivery::ActiveModel> = webhooks
+        .iter()
+        .map(|webhook| webhook_delivery::ActiveModel {
+            id: NotSet,
+            webhook_id: Set(webhook.id),
+            event_type: Set(event.as_str().to_string()),
+            payload: Set(payload.clone()),
+            status: Set(STATUS_PENDING.to_string()),
+            attempts: Set(0),
+            next_attempt_at: Set(now.naive_utc()),
+            response_status: Set(None),
+            last_error: Set(None),
+            date_created: NotSet,
+            date_delivered: Set(None),
+        })
+        .collect();
+
+    webhook_delivery::Entity::insert_many(deliveries)
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::ActiveModelTrait;
+
+    use super::*;
+    use crate::testing::{app_state, create_project, create_task, create_user};
+
+    #[test]
+    fn check_url_rejects_private_targets() {
+        assert!(check_url("https://example.com/hook").is_ok());
+        assert!(check_url("http://93.184.216.34:8080/hook").is_ok());
+
+        for url in [
+            "ftp://example.com/hook",
+            "http://localhost:8080/hook",
+            "http://api.localhost/hook",
+            "http://127.0.0.1/hook",
+            "http://10.0.0.5/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(check_url(url).is_err(), "{} was accepted", url);
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatch_reaches_only_users_who_see_the_task() {
+        let app_state = app_state().await;
+        let alice = create_user(&app_state, "alice").await;
+        let bob = create_user(&app_state, "bob").await;
+        let carol = create_user(&app_state, "carol").await;
+
+        for user in [&alice, &bob, &carol] {
+            webhook::ActiveModel {
+                id: NotSet,
+                user_id: Set(user.id),
+                url: Set("https://example.com/hook".to_string()),
+                secret: Set("webhook-secret-123".to_string()),
+                event_types: Set(WILDCARD.to_string()),
+                is_active: Set(true),
+                date_created: NotSet,
+            }
+            .insert(&app_state.db)
+            .await
+            .unwrap();
+        }
+
+        let project = create_project(&app_state, &alice, "ops", &[&bob]).await;
+        create_task(&app_state, &alice, &project, "Deploy").await;
+
+        let mut recipients: Vec<i32> = webhook_delivery::Entity::find()
+            .find_also_related(webhook::Entity)
+            .all(&app_state.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|(_, webhook)| webhook.map(|webhook| webhook.user_id))
+            .collect();
+        recipients.sort();
+
+        assert_eq!(recipients, vec![alice.id, bob.id]);
+    }
+}
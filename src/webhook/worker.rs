@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use tokio::task::JoinHandle;
+
+use url::Host;
+
+use super::{resolve_target, sign, Target, STATUS_DELIVERED, STATUS_FAILED, STATUS_PENDING};
+use crate::models::_entities::{webhook, webhook_delivery};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const BATCH_SIZE: u64 = 20;
+
+/// A delivery is marked as failed after this many unsuccessful attempts.
+pub const MAX_ATTEMPTS: i32 = 8;
+
+const BASE_RETRY_DELAY_SECONDS: i64 = 30;
+const MAX_RETRY_DELAY_SECONDS: i64 = 6 * 60 * 60;
+
+/// Starts the background task that sends pending webhook deliveries.
+pub fn spawn(db: DatabaseConnection) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(err) = deliver_pending(&db).await {
+                tracing::error!("Webhook delivery failed: {}", err);
+            }
+        }
+    })
+}
+
+/// Delay before the next attempt, doubling after every failure.
+pub fn retry_delay(attempts: i32) -> chrono::Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    let seconds = BASE_RETRY_DELAY_SECONDS.saturating_mul(2_i64.pow(exponent));
+
+    chrono::Duration::seconds(seconds.min(MAX_RETRY_DELAY_SECONDS))
+}
+
+/// A client sending to `target`. It connects to the addresses checked by
+/// `resolve_target` rather than resolving the host again, and does not follow
+/// redirects, which could lead to a private address.
+pub fn client_for(target: &Target) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none());
+
+    if let Some(Host::Domain(domain)) = target.url.host() {
+        builder = builder.resolve_to_addrs(domain, &target.addresses);
+    }
+
+    builder.build().map_err(|err| err.to_string())
+}
+
+async fn deliver_pending(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let now = Utc::now().naive_utc();
+
+    let deliveries = webhook_delivery::Entity::find()
+        .filter(webhook_delivery::Column::Status.eq(STATUS_PENDING))
+        .filter(webhook_delivery::Column::NextAttemptAt.lte(now))
+        .find_also_related(webhook::Entity)
+        .order_by_asc(webhook_delivery::Column::NextAttemptAt)
+        .limit(BATCH_SIZE)
+        .all(db)
+        .await?;
+
+    for (delivery, webhook) in deliveries {
+        let attempts = delivery.attempts + 1;
+        let mut active_delivery: webhook_delivery::ActiveModel = delivery.clone().into();
+
+        active_delivery.attempts = Set(attempts);
+
+        let result = match webhook.filter(|webhook| webhook.is_active) {
+            Some(webhook) => {
+                match resolve_target(&webhook.url)
+                    .await
+                    .and_then(|target| Ok((client_for(&target)?, target)))
+                {
+                    Ok((client, target)) => {
+                        send(
+                            &client,
+                            target.url.as_str(),
+                            &webhook.secret,
+                            &delivery.event_type,
+                            delivery.id,
+                            &delivery.payload,
+                        )
+                        .await
+                    }
+                    Err(err) => Err(err),
+                }
+            }
+            None => Err("Webhook is disabled.".to_string()),
+        };
+
+        match result {
+            Ok(status) if (200..300).contains(&status) => {
+                active_delivery.status = Set(STATUS_DELIVERED.to_string());
+                active_delivery.response_status = Set(Some(status.into()));
+                active_delivery.last_error = Set(None);
+                active_delivery.date_delivered = Set(Some(Utc::now().naive_utc()));
+            }
+            result => {
+                let (response_status, error) = match result {
+                    Ok(status) => (Some(status.into()), format!("Received status {}", status)),
+                    Err(err) => (None, err),
+                };
+
+                active_delivery.response_status = Set(response_status);
+                active_delivery.last_error = Set(Some(error));
+
+                if attempts >= MAX_ATTEMPTS {
+                    active_delivery.status = Set(STATUS_FAILED.to_string());
+                } else {
+                    active_delivery.next_attempt_at =
+                        Set(Utc::now().naive_utc() + retry_delay(attempts));
+                }
+            }
+        }
+
+        active_delivery.update(db).await?;
+    }
+
+    Ok(())
+}
+
+/// Posts a signed payload to `url` and returns the response status code.
+pub async fn send(
+    client: &reqwest::Client,
+    url: &str,
+    secret: &str,
+    event_type: &str,
+    delivery_id: i32,
+    payload: &str,
+) -> Result<u16, String> {
+    let response = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-Webhook-Event", event_type)
+        .header("X-Webhook-Delivery", delivery_id.to_string())
+        .header(
+            "X-Webhook-Signature",
+            format!("sha256={}", sign(secret, payload)),
+        )
+        .body(payload.to_string())
+        .send()
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(response.status().as_u16())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Bytes,
+        extract::State,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Router,
+    };
+    use tokio::{net::TcpListener, sync::mpsc};
+
+    async fn receiver(
+        State(sender): State<mpsc::UnboundedSender<(HeaderMap, Bytes)>>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        sender.send((headers, body)).unwrap();
+        StatusCode::NO_CONTENT
+    }
+
+    #[tokio::test]
+    async fn send_signs_payload() {
+        let (sender, mut received) = mpsc::unbounded_channel();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = Router::new()
+            .route("/hook", post(receiver))
+            .with_state(sender);
+
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let payload = r#"{"event":"task.created","data":{"id":1}}"#;
+
+        let status = send(
+            &reqwest::Client::new(),
+            &format!("http://{}/hook", address),
+            "webhook-secret",
+            "task.created",
+            7,
+            payload,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(status, 204);
+
+        let (headers, body) = received.recv().await.unwrap();
+
+        assert_eq!(body, payload.as_bytes());
+        assert_eq!(headers["X-Webhook-Event"], "task.created");
+        assert_eq!(headers["X-Webhook-Delivery"], "7");
+        assert_eq!(
+            headers["X-Webhook-Signature"].to_str().unwrap(),
+            format!("sha256={}", sign("webhook-secret", payload))
+        );
+    }
+
+    #[tokio::test]
+    async fn redirects_are_not_followed_and_checked_addresses_are_used() {
+        let (sender, mut received) = mpsc::unbounded_channel();
+
+        let private_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let private_address = private_listener.local_addr().unwrap();
+        let private = Router::new()
+            .route("/metadata", post(receiver))
+            .with_state(sender);
+
+        tokio::spawn(async move { axum::serve(private_listener, private).await.unwrap() });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let location = format!("http://{}/metadata", private_address);
+        let app = Router::new().route(
+            "/hook",
+            post(move || async move {
+                (
+                    StatusCode::TEMPORARY_REDIRECT,
+                    [(axum::http::header::LOCATION, location)],
+                )
+            }),
+        );
+
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        // The host name does not resolve, so the request can only reach the
+        // server through the address given with the target.
+        let target = Target {
+            url: url::Url::parse(&format!("http://hooks.invalid:{}/hook", address.port())).unwrap(),
+            addresses: vec![address],
+        };
+
+        let status = send(
+            &client_for(&target).unwrap(),
+            target.url.as_str(),
+            "webhook-secret",
+            "task.created",
+            7,
+            "{}",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(status, 307);
+        assert!(received.try_recv().is_err());
+    }
+
+    #[test]
+    fn retry_delay_backs_off_exponentially() {
+        assert_eq!(retry_delay(1).num_seconds(), 30);
+        assert_eq!(retry_delay(2).num_seconds(), 60);
+        assert_eq!(retry_delay(4).num_seconds(), 240);
+        assert_eq!(retry_delay(30).num_seconds(), MAX_RETRY_DELAY_SECONDS);
+    }
+}